@@ -57,4 +57,68 @@ pub mod v3 {
             Self { threepids }
         }
     }
+
+    #[cfg(all(test, feature = "client", feature = "server"))]
+    mod tests {
+        use ruma_common::{
+            api::{IncomingResponse, OutgoingResponse},
+            thirdparty::{Medium, ThirdPartyIdentifierInit},
+            MilliSecondsSinceUnixEpoch,
+        };
+        use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+        use super::Response;
+
+        #[test]
+        fn response_round_trip() {
+            let res = Response::new(vec![
+                ThirdPartyIdentifierInit {
+                    address: "alice@example.org".to_owned(),
+                    medium: Medium::Email,
+                    validated_at: MilliSecondsSinceUnixEpoch(
+                        1_535_176_800_000_u64.try_into().unwrap(),
+                    ),
+                    added_at: MilliSecondsSinceUnixEpoch(1_535_336_848_756_u64.try_into().unwrap()),
+                }
+                .into(),
+                ThirdPartyIdentifierInit {
+                    address: "33612345678".to_owned(),
+                    medium: Medium::Msisdn,
+                    validated_at: MilliSecondsSinceUnixEpoch(
+                        1_535_176_800_000_u64.try_into().unwrap(),
+                    ),
+                    added_at: MilliSecondsSinceUnixEpoch(1_535_336_848_756_u64.try_into().unwrap()),
+                }
+                .into(),
+            ]);
+
+            let http_res = res.try_into_http_response::<Vec<u8>>().unwrap();
+            assert_eq!(
+                from_json_slice::<JsonValue>(http_res.body()).unwrap(),
+                json!({
+                    "threepids": [
+                        {
+                            "medium": "email",
+                            "address": "alice@example.org",
+                            "validated_at": 1_535_176_800_000_u64,
+                            "added_at": 1_535_336_848_756_u64,
+                        },
+                        {
+                            "medium": "msisdn",
+                            "address": "33612345678",
+                            "validated_at": 1_535_176_800_000_u64,
+                            "added_at": 1_535_336_848_756_u64,
+                        },
+                    ],
+                })
+            );
+
+            let res = Response::try_from_http_response(http_res).unwrap();
+            assert_eq!(res.threepids.len(), 2);
+            assert_eq!(res.threepids[0].medium, Medium::Email);
+            assert_eq!(res.threepids[0].address, "alice@example.org");
+            assert_eq!(res.threepids[1].medium, Medium::Msisdn);
+            assert_eq!(res.threepids[1].address, "33612345678");
+        }
+    }
 }
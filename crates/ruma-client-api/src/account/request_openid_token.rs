@@ -69,4 +69,45 @@ pub mod v3 {
             Self { access_token, token_type, matrix_server_name, expires_in }
         }
     }
+
+    #[cfg(all(test, feature = "client", feature = "server"))]
+    mod tests {
+        use std::time::Duration;
+
+        use ruma_common::{
+            api::{IncomingResponse, OutgoingResponse},
+            authentication::TokenType,
+            owned_server_name,
+        };
+        use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+        use super::Response;
+
+        #[test]
+        fn response_round_trip() {
+            let res = Response::new(
+                "SomeT0kenHere".to_owned(),
+                TokenType::Bearer,
+                owned_server_name!("example.com"),
+                Duration::from_secs(3600),
+            );
+
+            let http_res = res.try_into_http_response::<Vec<u8>>().unwrap();
+            assert_eq!(
+                from_json_slice::<JsonValue>(http_res.body()).unwrap(),
+                json!({
+                    "access_token": "SomeT0kenHere",
+                    "token_type": "Bearer",
+                    "matrix_server_name": "example.com",
+                    "expires_in": 3600,
+                })
+            );
+
+            let res = Response::try_from_http_response(http_res).unwrap();
+            assert_eq!(res.access_token, "SomeT0kenHere");
+            assert_eq!(res.token_type, TokenType::Bearer);
+            assert_eq!(res.matrix_server_name, "example.com");
+            assert_eq!(res.expires_in, Duration::from_secs(3600));
+        }
+    }
 }
@@ -55,4 +55,54 @@ pub mod v3 {
             Self { user_id, device_id: None, is_guest }
         }
     }
+
+    #[cfg(all(test, feature = "client", feature = "server"))]
+    mod tests {
+        use ruma_common::{
+            api::{IncomingResponse, OutgoingResponse},
+            owned_device_id, owned_user_id,
+        };
+        use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+        use super::Response;
+
+        #[test]
+        fn non_guest_round_trip() {
+            let response = Response {
+                device_id: Some(owned_device_id!("ABCDEFG")),
+                ..Response::new(owned_user_id!("@alice:example.org"), false)
+            };
+            let http_response = response.try_into_http_response::<Vec<u8>>().unwrap();
+            assert_eq!(
+                from_json_slice::<JsonValue>(http_response.body()).unwrap(),
+                json!({
+                    "user_id": "@alice:example.org",
+                    "device_id": "ABCDEFG",
+                })
+            );
+
+            let response = Response::try_from_http_response(http_response).unwrap();
+            assert_eq!(response.user_id, "@alice:example.org");
+            assert_eq!(response.device_id.as_deref().map(|d| d.as_str()), Some("ABCDEFG"));
+            assert!(!response.is_guest);
+        }
+
+        #[test]
+        fn guest_round_trip() {
+            let response = Response::new(owned_user_id!("@guest:example.org"), true);
+            let http_response = response.try_into_http_response::<Vec<u8>>().unwrap();
+            assert_eq!(
+                from_json_slice::<JsonValue>(http_response.body()).unwrap(),
+                json!({
+                    "user_id": "@guest:example.org",
+                    "is_guest": true,
+                })
+            );
+
+            let response = Response::try_from_http_response(http_response).unwrap();
+            assert_eq!(response.user_id, "@guest:example.org");
+            assert_eq!(response.device_id, None);
+            assert!(response.is_guest);
+        }
+    }
 }
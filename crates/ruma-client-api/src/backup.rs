@@ -143,3 +143,87 @@ impl From<EncryptedSessionDataInit> for EncryptedSessionData {
         Self { ephemeral, ciphertext, mac }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use assert_matches2::assert_matches;
+    use js_int::uint;
+    use ruma_common::{device_key_id, owned_user_id, serde::Base64};
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::{BackupAlgorithm, EncryptedSessionDataInit, KeyBackupData, KeyBackupDataInit};
+
+    #[test]
+    fn backup_algorithm_serde() {
+        let algorithm = BackupAlgorithm::MegolmBackupV1Curve25519AesSha2 {
+            public_key: Base64::parse("abcdefg").unwrap(),
+            signatures: [(
+                owned_user_id!("@alice:example.org"),
+                [(device_key_id!("ed25519:deviceid").to_owned(), "signature".to_owned())].into(),
+            )]
+            .into(),
+        };
+
+        let json = json!({
+            "algorithm": "m.megolm_backup.v1.curve25519-aes-sha2",
+            "auth_data": {
+                "public_key": "abcdefg",
+                "signatures": {
+                    "@alice:example.org": {
+                        "ed25519:deviceid": "signature",
+                    },
+                },
+            },
+        });
+        assert_eq!(to_json_value(&algorithm).unwrap(), json);
+
+        assert_matches!(
+            from_json_value::<BackupAlgorithm>(json).unwrap(),
+            BackupAlgorithm::MegolmBackupV1Curve25519AesSha2 { public_key, signatures }
+        );
+        assert_eq!(public_key.encode(), "abcdefg");
+        assert_eq!(
+            signatures[&owned_user_id!("@alice:example.org")][device_key_id!("ed25519:deviceid")],
+            "signature"
+        );
+    }
+
+    #[test]
+    fn key_backup_data_serde() {
+        let key_backup_data: KeyBackupData = KeyBackupDataInit {
+            first_message_index: uint!(1),
+            forwarded_count: uint!(0),
+            is_verified: true,
+            session_data: EncryptedSessionDataInit {
+                ephemeral: Base64::parse("base64+ephemeral+key").unwrap(),
+                ciphertext: Base64::parse("base64+ciphertext+of+JSON+datQ").unwrap(),
+                mac: Base64::parse("base64+mac+of+ciphertext").unwrap(),
+            }
+            .into(),
+        }
+        .into();
+
+        let json = json!({
+            "first_message_index": 1,
+            "forwarded_count": 0,
+            "is_verified": true,
+            "session_data": {
+                "ephemeral": "base64+ephemeral+key",
+                "ciphertext": "base64+ciphertext+of+JSON+datQ",
+                "mac": "base64+mac+of+ciphertext",
+            },
+        });
+        assert_eq!(to_json_value(key_backup_data).unwrap(), json);
+
+        let key_backup_data = from_json_value::<KeyBackupData>(json).unwrap();
+        assert_eq!(key_backup_data.first_message_index, uint!(1));
+        assert_eq!(key_backup_data.forwarded_count, uint!(0));
+        assert!(key_backup_data.is_verified);
+        assert_eq!(key_backup_data.session_data.ephemeral.encode(), "base64+ephemeral+key");
+        assert_eq!(
+            key_backup_data.session_data.ciphertext.encode(),
+            "base64+ciphertext+of+JSON+datQ"
+        );
+        assert_eq!(key_backup_data.session_data.mac.encode(), "base64+mac+of+ciphertext");
+    }
+}
@@ -131,5 +131,79 @@ pub mod v3 {
                 r#"{"chunk":[],"next_batch":"next_batch_token","prev_batch":"prev_batch_token","total_room_count_estimate":10}"#
             );
         }
+
+        #[cfg(all(feature = "client", feature = "server"))]
+        #[test]
+        fn response_round_trip() {
+            use assert_matches2::assert_matches;
+            use ruma_common::{
+                api::{IncomingResponse as _, OutgoingResponse as _},
+                directory::{PublicRoomJoinRule, PublicRoomsChunk, PublicRoomsChunkInit},
+                owned_mxc_uri, owned_room_alias_id, owned_room_id,
+                room::RoomType,
+            };
+            use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+            let mut chunk: PublicRoomsChunk = PublicRoomsChunkInit {
+                num_joined_members: uint!(42),
+                room_id: owned_room_id!("!space:example.org"),
+                world_readable: true,
+                guest_can_join: false,
+            }
+            .into();
+            chunk.canonical_alias = Some(owned_room_alias_id!("#space:example.org"));
+            chunk.name = Some("The Space".to_owned());
+            chunk.topic = Some("A space for everyone".to_owned());
+            chunk.avatar_url = Some(owned_mxc_uri!("mxc://example.org/abcdef"));
+            chunk.join_rule = PublicRoomJoinRule::Knock;
+            chunk.room_type = Some(RoomType::Space);
+
+            let res = super::Response {
+                chunk: vec![chunk],
+                next_batch: Some("next_batch_token".into()),
+                prev_batch: None,
+                total_room_count_estimate: Some(uint!(115)),
+            }
+            .try_into_http_response::<Vec<u8>>()
+            .unwrap();
+
+            assert_eq!(
+                from_json_slice::<JsonValue>(res.body()).unwrap(),
+                json!({
+                    "chunk": [
+                        {
+                            "canonical_alias": "#space:example.org",
+                            "name": "The Space",
+                            "num_joined_members": 42,
+                            "room_id": "!space:example.org",
+                            "topic": "A space for everyone",
+                            "world_readable": true,
+                            "guest_can_join": false,
+                            "avatar_url": "mxc://example.org/abcdef",
+                            "join_rule": "knock",
+                            "room_type": "m.space",
+                        },
+                    ],
+                    "next_batch": "next_batch_token",
+                    "total_room_count_estimate": 115,
+                })
+            );
+
+            let res = super::Response::try_from_http_response(res).unwrap();
+            assert_eq!(res.next_batch.as_deref(), Some("next_batch_token"));
+            assert_eq!(res.prev_batch, None);
+            assert_eq!(res.total_room_count_estimate, Some(uint!(115)));
+            assert_matches!(res.chunk.as_slice(), [chunk]);
+            assert_eq!(chunk.room_id, "!space:example.org");
+            assert_eq!(chunk.canonical_alias.as_deref().unwrap(), "#space:example.org");
+            assert_eq!(chunk.name.as_deref(), Some("The Space"));
+            assert_eq!(chunk.topic.as_deref(), Some("A space for everyone"));
+            assert_eq!(chunk.num_joined_members, uint!(42));
+            assert_eq!(chunk.avatar_url.as_deref().unwrap(), "mxc://example.org/abcdef");
+            assert_eq!(chunk.join_rule, PublicRoomJoinRule::Knock);
+            assert_eq!(chunk.room_type, Some(RoomType::Space));
+            assert!(chunk.world_readable);
+            assert!(!chunk.guest_can_join);
+        }
     }
 }
@@ -297,9 +297,11 @@ mod tests {
     use std::borrow::Cow;
 
     use assert_matches2::assert_matches;
-    use serde_json::json;
+    use maplit::btreemap;
+    use ruma_common::RoomVersionId;
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
-    use super::Capabilities;
+    use super::{Capabilities, RoomVersionStability, RoomVersionsCapability};
 
     #[test]
     fn capabilities_iter() -> serde_json::Result<()> {
@@ -340,4 +342,56 @@ mod tests {
         assert_matches!(caps_iter.next(), None);
         Ok(())
     }
+
+    #[test]
+    fn capabilities_round_trip() {
+        let json = json!({
+            "m.change_password": { "enabled": false },
+            "m.room_versions": {
+                "default": "10",
+                "available": {
+                    "9": "stable",
+                    "10": "stable",
+                    "org.example.custom": "unstable",
+                },
+            },
+            "m.set_displayname": { "enabled": false },
+            "org.example.custom_cap": { "max": 5 },
+        });
+
+        let caps = from_json_value::<Capabilities>(json.clone()).unwrap();
+        assert!(!caps.change_password.enabled);
+        assert_eq!(caps.room_versions.default, RoomVersionId::V10);
+        assert_eq!(
+            caps.room_versions.available,
+            btreemap! {
+                RoomVersionId::V9 => RoomVersionStability::Stable,
+                RoomVersionId::V10 => RoomVersionStability::Stable,
+                "org.example.custom".try_into().unwrap() => RoomVersionStability::Unstable,
+            }
+        );
+        assert!(!caps.set_displayname.enabled);
+        assert!(caps.set_avatar_url.enabled);
+        assert!(caps.thirdparty_id_changes.enabled);
+        assert_eq!(caps.get("org.example.custom_cap"), Some(Cow::Borrowed(&json!({ "max": 5 }))));
+
+        assert_eq!(to_json_value(&caps).unwrap(), json);
+
+        let caps = Capabilities {
+            room_versions: RoomVersionsCapability::new(
+                RoomVersionId::V10,
+                btreemap! { RoomVersionId::V10 => RoomVersionStability::Stable },
+            ),
+            ..Default::default()
+        };
+        assert_eq!(
+            to_json_value(&caps).unwrap(),
+            json!({
+                "m.room_versions": {
+                    "default": "10",
+                    "available": { "10": "stable" },
+                },
+            })
+        );
+    }
 }
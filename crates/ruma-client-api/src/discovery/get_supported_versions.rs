@@ -69,6 +69,18 @@ impl Response {
             // Return an iterator over just the values (`MatrixVersion`s)
             .into_values()
     }
+
+    /// Whether the given Matrix version is advertised in this response.
+    pub fn is_version_supported(&self, version: MatrixVersion) -> bool {
+        self.known_versions().any(|v| v == version)
+    }
+
+    /// Whether the given experimental feature is advertised as enabled in this response.
+    ///
+    /// Features that are missing from `unstable_features` are considered to be unsupported.
+    pub fn supports_feature(&self, name: &str) -> bool {
+        self.unstable_features.get(name).copied().unwrap_or(false)
+    }
 }
 
 #[cfg(test)]
@@ -130,4 +142,29 @@ mod tests {
             vec![MatrixVersion::V1_0, MatrixVersion::V1_1, MatrixVersion::V1_2],
         );
     }
+
+    #[test]
+    #[cfg(feature = "client")]
+    fn supported_versions_and_features() {
+        use ruma_common::api::IncomingResponse;
+        use serde_json::json;
+
+        let body = json!({
+            "versions": ["r0.6.1", "v1.1", "v1.2"],
+            "unstable_features": {
+                "org.matrix.msc3440.stable": true,
+                "org.matrix.msc2716": false,
+            },
+        });
+        let http_response = http::Response::new(serde_json::to_vec(&body).unwrap());
+        let response = Response::try_from_http_response(http_response).unwrap();
+
+        assert!(response.is_version_supported(MatrixVersion::V1_0));
+        assert!(response.is_version_supported(MatrixVersion::V1_2));
+        assert!(!response.is_version_supported(MatrixVersion::V1_3));
+
+        assert!(response.supports_feature("org.matrix.msc3440.stable"));
+        assert!(!response.supports_feature("org.matrix.msc2716"));
+        assert!(!response.supports_feature("org.matrix.msc3575"));
+    }
 }
@@ -72,6 +72,9 @@ pub enum ErrorKind {
     /// M_USER_IN_USE
     UserInUse,
 
+    /// M_USER_LOCKED
+    UserLocked,
+
     /// M_INVALID_USERNAME
     InvalidUsername,
 
@@ -93,6 +96,9 @@ pub enum ErrorKind {
     /// M_THREEPID_DENIED
     ThreepidDenied,
 
+    /// M_THREEPID_MEDIUM_NOT_SUPPORTED
+    ThreepidMediumNotSupported,
+
     /// M_SERVER_NOT_TRUSTED
     ServerNotTrusted,
 
@@ -204,6 +210,7 @@ impl AsRef<str> for ErrorKind {
             Self::Unauthorized => "M_UNAUTHORIZED",
             Self::UserDeactivated => "M_USER_DEACTIVATED",
             Self::UserInUse => "M_USER_IN_USE",
+            Self::UserLocked => "M_USER_LOCKED",
             Self::InvalidUsername => "M_INVALID_USERNAME",
             Self::RoomInUse => "M_ROOM_IN_USE",
             Self::InvalidRoomState => "M_INVALID_ROOM_STATE",
@@ -211,6 +218,7 @@ impl AsRef<str> for ErrorKind {
             Self::ThreepidNotFound => "M_THREEPID_NOT_FOUND",
             Self::ThreepidAuthFailed => "M_THREEPID_AUTH_FAILED",
             Self::ThreepidDenied => "M_THREEPID_DENIED",
+            Self::ThreepidMediumNotSupported => "M_THREEPID_MEDIUM_NOT_SUPPORTED",
             Self::ServerNotTrusted => "M_SERVER_NOT_TRUSTED",
             Self::UnsupportedRoomVersion => "M_UNSUPPORTED_ROOM_VERSION",
             Self::IncompatibleRoomVersion { .. } => "M_INCOMPATIBLE_ROOM_VERSION",
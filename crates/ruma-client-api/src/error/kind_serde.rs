@@ -185,6 +185,7 @@ impl<'de> Visitor<'de> for ErrorKindVisitor {
             ErrCode::Unauthorized => ErrorKind::Unauthorized,
             ErrCode::UserDeactivated => ErrorKind::UserDeactivated,
             ErrCode::UserInUse => ErrorKind::UserInUse,
+            ErrCode::UserLocked => ErrorKind::UserLocked,
             ErrCode::InvalidUsername => ErrorKind::InvalidUsername,
             ErrCode::RoomInUse => ErrorKind::RoomInUse,
             ErrCode::InvalidRoomState => ErrorKind::InvalidRoomState,
@@ -192,6 +193,7 @@ impl<'de> Visitor<'de> for ErrorKindVisitor {
             ErrCode::ThreepidNotFound => ErrorKind::ThreepidNotFound,
             ErrCode::ThreepidAuthFailed => ErrorKind::ThreepidAuthFailed,
             ErrCode::ThreepidDenied => ErrorKind::ThreepidDenied,
+            ErrCode::ThreepidMediumNotSupported => ErrorKind::ThreepidMediumNotSupported,
             ErrCode::ServerNotTrusted => ErrorKind::ServerNotTrusted,
             ErrCode::UnsupportedRoomVersion => ErrorKind::UnsupportedRoomVersion,
             ErrCode::IncompatibleRoomVersion => ErrorKind::IncompatibleRoomVersion {
@@ -258,6 +260,7 @@ enum ErrCode {
     Unauthorized,
     UserDeactivated,
     UserInUse,
+    UserLocked,
     InvalidUsername,
     RoomInUse,
     InvalidRoomState,
@@ -265,6 +268,7 @@ enum ErrCode {
     ThreepidNotFound,
     ThreepidAuthFailed,
     ThreepidDenied,
+    ThreepidMediumNotSupported,
     ServerNotTrusted,
     UnsupportedRoomVersion,
     IncompatibleRoomVersion,
@@ -279,6 +283,7 @@ enum ErrCode {
     ResourceLimitExceeded,
     CannotLeaveServerNoticeRoom,
     WeakPassword,
+    #[ruma_enum(rename = "M_UNABLE_TO_AUTHORISE_JOIN")]
     UnableToAuthorizeJoin,
     UnableToGrantJoin,
     BadAlias,
@@ -328,6 +333,14 @@ impl Serialize for ErrorKind {
             Self::ResourceLimitExceeded { admin_contact } => {
                 st.serialize_entry("admin_contact", admin_contact)?;
             }
+            Self::BadStatus { status, body } => {
+                if let Some(status) = status {
+                    st.serialize_entry("status", &status.as_u16())?;
+                }
+                if let Some(body) = body {
+                    st.serialize_entry("body", body)?;
+                }
+            }
             Self::_Custom { extra, .. } => {
                 for (k, v) in &extra.0 {
                     st.serialize_entry(k, v)?;
@@ -341,11 +354,86 @@ impl Serialize for ErrorKind {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use ruma_common::room_version_id;
-    use serde_json::{from_value as from_json_value, json};
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
     use super::ErrorKind;
 
+    #[test]
+    fn round_trip_unit_variants() {
+        for (kind, errcode) in [
+            (ErrorKind::Forbidden, "M_FORBIDDEN"),
+            (ErrorKind::MissingToken, "M_MISSING_TOKEN"),
+            (ErrorKind::UserInUse, "M_USER_IN_USE"),
+            (ErrorKind::UserLocked, "M_USER_LOCKED"),
+            (ErrorKind::InvalidUsername, "M_INVALID_USERNAME"),
+            (ErrorKind::ThreepidMediumNotSupported, "M_THREEPID_MEDIUM_NOT_SUPPORTED"),
+            (ErrorKind::UnableToAuthorizeJoin, "M_UNABLE_TO_AUTHORISE_JOIN"),
+        ] {
+            assert_eq!(kind.to_string(), errcode);
+
+            let json = json!({ "errcode": errcode });
+            assert_eq!(to_json_value(&kind).unwrap(), json);
+            assert_eq!(from_json_value::<ErrorKind>(json).unwrap(), kind);
+        }
+    }
+
+    #[test]
+    fn round_trip_unknown_token() {
+        let json = json!({ "errcode": "M_UNKNOWN_TOKEN", "soft_logout": true });
+        let kind = from_json_value::<ErrorKind>(json.clone()).unwrap();
+        assert_eq!(kind, ErrorKind::UnknownToken { soft_logout: true });
+        assert_eq!(kind.to_string(), "M_UNKNOWN_TOKEN");
+        assert_eq!(to_json_value(&kind).unwrap(), json);
+
+        // `soft_logout` defaults to `false` and is not serialized in that case.
+        let json = json!({ "errcode": "M_UNKNOWN_TOKEN" });
+        let kind = from_json_value::<ErrorKind>(json.clone()).unwrap();
+        assert_eq!(kind, ErrorKind::UnknownToken { soft_logout: false });
+        assert_eq!(to_json_value(&kind).unwrap(), json);
+    }
+
+    #[test]
+    fn round_trip_limit_exceeded() {
+        let json = json!({ "errcode": "M_LIMIT_EXCEEDED", "retry_after_ms": 2000 });
+        let kind = from_json_value::<ErrorKind>(json.clone()).unwrap();
+        assert_eq!(
+            kind,
+            ErrorKind::LimitExceeded { retry_after_ms: Some(Duration::from_millis(2000)) }
+        );
+        assert_eq!(kind.to_string(), "M_LIMIT_EXCEEDED");
+        assert_eq!(to_json_value(&kind).unwrap(), json);
+
+        let json = json!({ "errcode": "M_LIMIT_EXCEEDED" });
+        let kind = from_json_value::<ErrorKind>(json.clone()).unwrap();
+        assert_eq!(kind, ErrorKind::LimitExceeded { retry_after_ms: None });
+        assert_eq!(to_json_value(&kind).unwrap(), json);
+    }
+
+    #[test]
+    fn round_trip_bad_status() {
+        let json = json!({ "errcode": "M_BAD_STATUS", "status": 404, "body": "Not found" });
+        let kind = from_json_value::<ErrorKind>(json.clone()).unwrap();
+        assert_eq!(
+            kind,
+            ErrorKind::BadStatus {
+                status: Some(http::StatusCode::NOT_FOUND),
+                body: Some("Not found".to_owned())
+            }
+        );
+        assert_eq!(to_json_value(&kind).unwrap(), json);
+    }
+
+    #[test]
+    fn round_trip_custom() {
+        let json = json!({ "errcode": "ORG_EXAMPLE_CUSTOM", "reason": "custom" });
+        let kind = from_json_value::<ErrorKind>(json.clone()).unwrap();
+        assert_eq!(kind.to_string(), "ORG_EXAMPLE_CUSTOM");
+        assert_eq!(to_json_value(&kind).unwrap(), json);
+    }
+
     #[test]
     fn deserialize_forbidden() {
         let deserialized: ErrorKind = from_json_value(json!({ "errcode": "M_FORBIDDEN" })).unwrap();
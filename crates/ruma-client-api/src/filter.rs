@@ -405,6 +405,41 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn lazy_loading_filter_definition_roundtrip() -> serde_json::Result<()> {
+        let filter = FilterDefinition::with_lazy_loading();
+        let json = to_json_value(&filter)?;
+        assert_eq!(
+            json,
+            json!({
+                "room": {
+                    "state": { "lazy_load_members": true },
+                },
+            })
+        );
+
+        let incoming_filter = from_json_value::<FilterDefinition>(json)?;
+        assert_eq!(
+            incoming_filter.room.state.lazy_load_options,
+            LazyLoadOptions::Enabled { include_redundant_members: false }
+        );
+        assert!(incoming_filter.room.timeline.lazy_load_options.is_disabled());
+
+        let mut room_event_filter = RoomEventFilter::with_lazy_loading();
+        room_event_filter.lazy_load_options =
+            LazyLoadOptions::Enabled { include_redundant_members: true };
+        let json = to_json_value(&room_event_filter)?;
+        assert_eq!(json, json!({ "lazy_load_members": true, "include_redundant_members": true }));
+
+        let incoming_room_event_filter = from_json_value::<RoomEventFilter>(json)?;
+        assert_eq!(
+            incoming_room_event_filter.lazy_load_options,
+            LazyLoadOptions::Enabled { include_redundant_members: true }
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn issue_366() {
         let obj = json!({
@@ -73,4 +73,48 @@ pub mod v3 {
 
     /// The one-time keys for a given device.
     pub type OneTimeKeys = BTreeMap<OwnedDeviceId, BTreeMap<OwnedDeviceKeyId, Raw<OneTimeKey>>>;
+
+    #[cfg(all(test, feature = "client"))]
+    mod tests {
+        use assert_matches2::assert_matches;
+        use ruma_common::{
+            api::IncomingResponse as _, device_id, device_key_id, encryption::OneTimeKey, user_id,
+        };
+        use serde_json::{json, to_vec as to_json_vec};
+
+        use super::Response;
+
+        #[test]
+        fn deserialize_response() {
+            let body = json!({
+                "failures": {},
+                "one_time_keys": {
+                    "@alice:example.com": {
+                        "JLAFKJWSCS": {
+                            "signed_curve25519:AAAAHg": {
+                                "key": "zKbLg+NrIjpnagy+pIY6uPL4ZwEG2v+8F9lmgsnlZzs",
+                                "signatures": {
+                                    "@alice:example.com": {
+                                        "ed25519:JLAFKJWSCS": "FLWxXqGbwrb8SM3Y795eB6OA8bwBcoMZFXBqnTn58AYWZSqiD45tlBVcDa2L7RwdKXebW/VzDlnfVJ+9jok1Bw",
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+            });
+            let response =
+                Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                    .unwrap();
+
+            assert!(response.failures.is_empty());
+            let device_keys =
+                &response.one_time_keys[user_id!("@alice:example.com")][device_id!("JLAFKJWSCS")];
+            let key =
+                device_keys[device_key_id!("signed_curve25519:AAAAHg")].deserialize().unwrap();
+            assert_matches!(key, OneTimeKey::SignedKey(key));
+            assert_eq!(key.key.encode(), "zKbLg+NrIjpnagy+pIY6uPL4ZwEG2v+8F9lmgsnlZzs");
+            assert_eq!(key.signatures.len(), 1);
+        }
+    }
 }
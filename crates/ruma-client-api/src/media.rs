@@ -2,6 +2,8 @@
 
 use std::time::Duration;
 
+use percent_encoding::percent_decode_str;
+
 pub mod create_content;
 pub mod create_content_async;
 pub mod create_mxc_uri;
@@ -21,3 +23,153 @@ fn default_download_timeout() -> Duration {
 fn is_default_download_timeout(timeout: &Duration) -> bool {
     timeout.as_secs() == 20
 }
+
+/// Extracts the filename from the value of a `Content-Disposition` HTTP header.
+///
+/// The `filename*` parameter, encoded according to [RFC 5987] in UTF-8 or ISO-8859-1, is
+/// preferred over the `filename` parameter, that can be a token or a quoted string.
+///
+/// Returns `None` if the header doesn't contain a valid non-empty filename. The filename is
+/// provided by the server, so it should be sanitized before it is used to write a file.
+///
+/// [RFC 5987]: https://datatracker.ietf.org/doc/html/rfc5987
+pub fn filename_from_content_disposition(content_disposition: &str) -> Option<String> {
+    let params = content_disposition_params(content_disposition);
+    let param = |name: &str| params.iter().find(|(n, _)| n == name).map(|(_, value)| value);
+
+    param("filename*")
+        .and_then(|value| decode_ext_value(value))
+        .filter(|filename| !filename.is_empty())
+        .or_else(|| param("filename").filter(|filename| !filename.is_empty()).cloned())
+}
+
+/// Parses the parameters of a `Content-Disposition` header into a list of lowercase names and
+/// unquoted values.
+fn content_disposition_params(content_disposition: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+
+    // Skip the disposition type.
+    let Some((_, mut rest)) = content_disposition.split_once(';') else {
+        return params;
+    };
+
+    loop {
+        rest = rest.trim_start();
+        let Some(pos) = rest.find(['=', ';']) else {
+            break;
+        };
+
+        if rest.as_bytes()[pos] == b';' {
+            // Ignore parameters without a value.
+            rest = &rest[pos + 1..];
+            continue;
+        }
+
+        let name = rest[..pos].trim().to_ascii_lowercase();
+        rest = rest[pos + 1..].trim_start();
+
+        let value = if let Some(quoted) = rest.strip_prefix('"') {
+            let mut value = String::new();
+            let mut end = quoted.len();
+            let mut chars = quoted.char_indices();
+
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    '\\' => value.extend(chars.next().map(|(_, c)| c)),
+                    '"' => {
+                        end = i + 1;
+                        break;
+                    }
+                    c => value.push(c),
+                }
+            }
+
+            rest = quoted[end..].split_once(';').map_or("", |(_, rest)| rest);
+            value
+        } else {
+            let (value, next) = rest.split_once(';').unwrap_or((rest, ""));
+            rest = next;
+            value.trim().to_owned()
+        };
+
+        params.push((name, value));
+    }
+
+    params
+}
+
+/// Decodes a parameter value encoded according to RFC 5987, like `UTF-8'en'file%20name.txt`.
+fn decode_ext_value(value: &str) -> Option<String> {
+    let (charset, rest) = value.split_once('\'')?;
+    let (_language, encoded) = rest.split_once('\'')?;
+    let bytes: Vec<u8> = percent_decode_str(encoded).collect();
+
+    if charset.eq_ignore_ascii_case("utf-8") {
+        String::from_utf8(bytes).ok()
+    } else if charset.eq_ignore_ascii_case("iso-8859-1") {
+        Some(bytes.into_iter().map(char::from).collect())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::filename_from_content_disposition;
+
+    #[test]
+    fn quoted_filename() {
+        assert_eq!(
+            filename_from_content_disposition(r#"attachment; filename="my file.txt""#).as_deref(),
+            Some("my file.txt")
+        );
+        assert_eq!(
+            filename_from_content_disposition(r#"inline; FileName="say \"hi\".txt"; size=12"#)
+                .as_deref(),
+            Some(r#"say "hi".txt"#)
+        );
+        assert_eq!(
+            filename_from_content_disposition("attachment; filename=plain.txt").as_deref(),
+            Some("plain.txt")
+        );
+    }
+
+    #[test]
+    fn rfc5987_filename() {
+        assert_eq!(
+            filename_from_content_disposition("attachment; filename*=UTF-8''%E2%82%AC%20rates.pdf")
+                .as_deref(),
+            Some("€ rates.pdf")
+        );
+        assert_eq!(
+            filename_from_content_disposition(
+                "attachment; filename*=iso-8859-1'en'%A3%20rates.pdf"
+            )
+            .as_deref(),
+            Some("£ rates.pdf")
+        );
+
+        // The extended parameter has priority, unless it is invalid.
+        assert_eq!(
+            filename_from_content_disposition(
+                r#"attachment; filename="EUR rates.pdf"; filename*=utf-8''%E2%82%AC%20rates.pdf"#
+            )
+            .as_deref(),
+            Some("€ rates.pdf")
+        );
+        assert_eq!(
+            filename_from_content_disposition(
+                r#"attachment; filename*=UTF-8''%FF.pdf; filename="fallback.pdf""#
+            )
+            .as_deref(),
+            Some("fallback.pdf")
+        );
+    }
+
+    #[test]
+    fn no_filename() {
+        assert_eq!(filename_from_content_disposition("attachment"), None);
+        assert_eq!(filename_from_content_disposition("inline; size=12"), None);
+        assert_eq!(filename_from_content_disposition(r#"attachment; filename="""#), None);
+    }
+}
@@ -9,11 +9,15 @@ pub mod v3 {
 
     use std::time::Duration;
 
-    use http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+    use http::{
+        header::{CONTENT_DISPOSITION, CONTENT_RANGE, CONTENT_TYPE, RANGE},
+        StatusCode,
+    };
     use ruma_common::{
         api::{request, response, Metadata},
         metadata, IdParseError, MxcUri, OwnedServerName,
     };
+    use ruma_events::room::message::FileMessageEventContent;
 
     use crate::http_headers::CROSS_ORIGIN_RESOURCE_POLICY;
 
@@ -67,6 +71,16 @@ pub mod v3 {
         #[ruma_api(query)]
         #[serde(default, skip_serializing_if = "ruma_common::serde::is_default")]
         pub allow_redirect: bool,
+
+        /// The value of the `Range` HTTP header, to only request a part of the content.
+        ///
+        /// The server should only return the given ranges, e.g. `bytes=0-1023`.
+        ///
+        /// See [MDN] for the syntax.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Range#syntax
+        #[ruma_api(header = RANGE)]
+        pub range: Option<String>,
     }
 
     /// Response type for the `get_media_content` endpoint.
@@ -96,6 +110,22 @@ pub mod v3 {
         /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Cross-Origin-Resource-Policy#syntax
         #[ruma_api(header = CROSS_ORIGIN_RESOURCE_POLICY)]
         pub cross_origin_resource_policy: Option<String>,
+
+        /// The value of the `Content-Range` HTTP header, if only a part of the content is
+        /// returned.
+        ///
+        /// See [MDN] for the syntax.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Range#syntax
+        #[ruma_api(header = CONTENT_RANGE)]
+        pub content_range: Option<String>,
+
+        /// The HTTP status code of the response.
+        ///
+        /// This is `206 Partial Content` if only a part of the content is returned, following a
+        /// `Range` header in the request, and `200 OK` otherwise.
+        #[ruma_api(status)]
+        pub status: StatusCode,
     }
 
     impl Request {
@@ -107,6 +137,7 @@ pub mod v3 {
                 allow_remote: true,
                 timeout_ms: crate::media::default_download_timeout(),
                 allow_redirect: false,
+                range: None,
             }
         }
 
@@ -128,7 +159,155 @@ pub mod v3 {
                 content_type: None,
                 content_disposition: None,
                 cross_origin_resource_policy: Some("cross-origin".to_owned()),
+                content_range: None,
+                status: StatusCode::OK,
+            }
+        }
+
+        /// Creates a new `Response` with the given part of the file contents and the value of the
+        /// `Content-Range` HTTP header.
+        ///
+        /// The status code is `206 Partial Content`.
+        pub fn partial(file: Vec<u8>, content_range: String) -> Self {
+            Self {
+                content_range: Some(content_range),
+                status: StatusCode::PARTIAL_CONTENT,
+                ..Self::new(file)
             }
         }
+
+        /// Whether this response only contains a part of the content.
+        pub fn is_partial(&self) -> bool {
+            self.status == StatusCode::PARTIAL_CONTENT
+        }
+
+        /// The filename from the `Content-Disposition` HTTP header, if any.
+        ///
+        /// See [`filename_from_content_disposition()`] for details.
+        ///
+        /// [`filename_from_content_disposition()`]: crate::media::filename_from_content_disposition
+        pub fn filename(&self) -> Option<String> {
+            self.content_disposition
+                .as_deref()
+                .and_then(crate::media::filename_from_content_disposition)
+        }
+
+        /// The filename to use for the file of the given message.
+        ///
+        /// This is the filename from the `Content-Disposition` HTTP header, falling back to the
+        /// `filename` and then the `body` of the message.
+        pub fn filename_for_message(&self, content: &FileMessageEventContent) -> String {
+            self.filename()
+                .or_else(|| content.filename.clone())
+                .unwrap_or_else(|| content.body.clone())
+        }
+    }
+
+    #[cfg(all(test, any(feature = "client", feature = "server")))]
+    mod tests {
+        #[cfg(feature = "client")]
+        #[test]
+        fn serialize_range_request() {
+            use ruma_common::{
+                api::{MatrixVersion, OutgoingRequest, SendAccessToken},
+                owned_server_name,
+            };
+
+            let mut req =
+                super::Request::new("abcdef".to_owned(), owned_server_name!("matrix.org"));
+            req.range = Some("bytes=0-1023".to_owned());
+
+            let http_req = req
+                .try_into_http_request::<Vec<u8>>(
+                    "https://matrix.org",
+                    SendAccessToken::None,
+                    &[MatrixVersion::V1_1],
+                )
+                .unwrap();
+            assert_eq!(http_req.headers().get(http::header::RANGE).unwrap(), "bytes=0-1023");
+        }
+
+        #[cfg(feature = "client")]
+        #[test]
+        fn deserialize_partial_content_response() {
+            use http::{header, StatusCode};
+            use ruma_common::api::IncomingResponse;
+
+            let res = super::Response::try_from_http_response(
+                http::Response::builder()
+                    .status(StatusCode::PARTIAL_CONTENT)
+                    .header(header::CONTENT_TYPE, "audio/ogg")
+                    .header(header::CONTENT_RANGE, "bytes 0-3/1024")
+                    .body(b"OggS" as &[u8])
+                    .unwrap(),
+            )
+            .unwrap();
+            assert!(res.is_partial());
+            assert_eq!(res.file, b"OggS");
+            assert_eq!(res.content_type.as_deref(), Some("audio/ogg"));
+            assert_eq!(res.content_range.as_deref(), Some("bytes 0-3/1024"));
+
+            let res = super::Response::try_from_http_response(
+                http::Response::builder().body(b"OggS" as &[u8]).unwrap(),
+            )
+            .unwrap();
+            assert!(!res.is_partial());
+            assert_eq!(res.content_range, None);
+        }
+
+        #[cfg(feature = "client")]
+        #[test]
+        fn filename_from_response() {
+            use http::header;
+            use ruma_common::{api::IncomingResponse, mxc_uri};
+            use ruma_events::room::message::FileMessageEventContent;
+
+            let content = FileMessageEventContent::plain(
+                "Here is the report".to_owned(),
+                mxc_uri!("mxc://matrix.org/abcdef").to_owned(),
+            )
+            .filename("report.pdf".to_owned());
+
+            let res = super::Response::try_from_http_response(
+                http::Response::builder()
+                    .header(
+                        header::CONTENT_DISPOSITION,
+                        "attachment; filename*=UTF-8''r%C3%A9sum%C3%A9.pdf",
+                    )
+                    .body(b"%PDF" as &[u8])
+                    .unwrap(),
+            )
+            .unwrap();
+            assert_eq!(res.filename().as_deref(), Some("résumé.pdf"));
+            assert_eq!(res.filename_for_message(&content), "résumé.pdf");
+
+            // Without the header, fall back to the filename of the message, then its body.
+            let res = super::Response::try_from_http_response(
+                http::Response::builder().body(b"%PDF" as &[u8]).unwrap(),
+            )
+            .unwrap();
+            assert_eq!(res.filename(), None);
+            assert_eq!(res.filename_for_message(&content), "report.pdf");
+            assert_eq!(res.filename_for_message(&content.filename(None)), "Here is the report");
+        }
+
+        #[cfg(feature = "server")]
+        #[test]
+        fn serialize_partial_content_response() {
+            use http::{header, StatusCode};
+            use ruma_common::api::OutgoingResponse;
+
+            let res = super::Response::partial(b"OggS".to_vec(), "bytes 0-3/1024".to_owned())
+                .try_into_http_response::<Vec<u8>>()
+                .unwrap();
+            assert_eq!(res.status(), StatusCode::PARTIAL_CONTENT);
+            assert_eq!(res.headers().get(header::CONTENT_RANGE).unwrap(), "bytes 0-3/1024");
+            assert_eq!(res.body(), b"OggS");
+
+            let res =
+                super::Response::new(b"OggS".to_vec()).try_into_http_response::<Vec<u8>>().unwrap();
+            assert_eq!(res.status(), StatusCode::OK);
+            assert_eq!(res.headers().get(header::CONTENT_RANGE), None);
+        }
     }
 }
@@ -9,11 +9,15 @@ pub mod v3 {
 
     use std::time::Duration;
 
-    use http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+    use http::{
+        header::{CONTENT_DISPOSITION, CONTENT_RANGE, CONTENT_TYPE, RANGE},
+        StatusCode,
+    };
     use ruma_common::{
         api::{request, response, Metadata},
         metadata, IdParseError, MxcUri, OwnedServerName,
     };
+    use ruma_events::room::message::FileMessageEventContent;
 
     use crate::http_headers::CROSS_ORIGIN_RESOURCE_POLICY;
 
@@ -71,6 +75,16 @@ pub mod v3 {
         #[ruma_api(query)]
         #[serde(default, skip_serializing_if = "ruma_common::serde::is_default")]
         pub allow_redirect: bool,
+
+        /// The value of the `Range` HTTP header, to only request a part of the content.
+        ///
+        /// The server should only return the given ranges, e.g. `bytes=0-1023`.
+        ///
+        /// See [MDN] for the syntax.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Range#syntax
+        #[ruma_api(header = RANGE)]
+        pub range: Option<String>,
     }
 
     /// Response type for the `get_media_content_as_filename` endpoint.
@@ -100,6 +114,22 @@ pub mod v3 {
         /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Cross-Origin-Resource-Policy#syntax
         #[ruma_api(header = CROSS_ORIGIN_RESOURCE_POLICY)]
         pub cross_origin_resource_policy: Option<String>,
+
+        /// The value of the `Content-Range` HTTP header, if only a part of the content is
+        /// returned.
+        ///
+        /// See [MDN] for the syntax.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Range#syntax
+        #[ruma_api(header = CONTENT_RANGE)]
+        pub content_range: Option<String>,
+
+        /// The HTTP status code of the response.
+        ///
+        /// This is `206 Partial Content` if only a part of the content is returned, following a
+        /// `Range` header in the request, and `200 OK` otherwise.
+        #[ruma_api(status)]
+        pub status: StatusCode,
     }
 
     impl Request {
@@ -112,6 +142,7 @@ pub mod v3 {
                 allow_remote: true,
                 timeout_ms: crate::media::default_download_timeout(),
                 allow_redirect: false,
+                range: None,
             }
         }
 
@@ -133,7 +164,47 @@ pub mod v3 {
                 content_type: None,
                 content_disposition: None,
                 cross_origin_resource_policy: Some("cross-origin".to_owned()),
+                content_range: None,
+                status: StatusCode::OK,
+            }
+        }
+
+        /// Creates a new `Response` with the given part of the file contents and the value of the
+        /// `Content-Range` HTTP header.
+        ///
+        /// The status code is `206 Partial Content`.
+        pub fn partial(file: Vec<u8>, content_range: String) -> Self {
+            Self {
+                content_range: Some(content_range),
+                status: StatusCode::PARTIAL_CONTENT,
+                ..Self::new(file)
             }
         }
+
+        /// Whether this response only contains a part of the content.
+        pub fn is_partial(&self) -> bool {
+            self.status == StatusCode::PARTIAL_CONTENT
+        }
+
+        /// The filename from the `Content-Disposition` HTTP header, if any.
+        ///
+        /// See [`filename_from_content_disposition()`] for details.
+        ///
+        /// [`filename_from_content_disposition()`]: crate::media::filename_from_content_disposition
+        pub fn filename(&self) -> Option<String> {
+            self.content_disposition
+                .as_deref()
+                .and_then(crate::media::filename_from_content_disposition)
+        }
+
+        /// The filename to use for the file of the given message.
+        ///
+        /// This is the filename from the `Content-Disposition` HTTP header, falling back to the
+        /// `filename` and then the `body` of the message.
+        pub fn filename_for_message(&self, content: &FileMessageEventContent) -> String {
+            self.filename()
+                .or_else(|| content.filename.clone())
+                .unwrap_or_else(|| content.body.clone())
+        }
     }
 }
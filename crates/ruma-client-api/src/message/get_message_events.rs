@@ -181,6 +181,92 @@ pub mod v3 {
         use super::Request;
         use crate::filter::{LazyLoadOptions, RoomEventFilter};
 
+        #[cfg(feature = "server")]
+        #[test]
+        fn paginate_backward_round_trip() {
+            use ruma_common::api::{IncomingRequest, IncomingResponse, OutgoingResponse};
+            use ruma_events::AnyTimelineEvent;
+            use serde_json::{from_value as from_json_value, json};
+
+            use super::Response;
+
+            let request = Request {
+                from: Some("t47-1234".to_owned()),
+                limit: uint!(2),
+                filter: RoomEventFilter::with_lazy_loading(),
+                ..Request::backward(owned_room_id!("!room:example.org"))
+            };
+            let http_request: http::Request<Vec<u8>> = request
+                .try_into_http_request(
+                    "https://homeserver.tld",
+                    SendAccessToken::IfRequired("auth_tok"),
+                    &[MatrixVersion::V1_1],
+                )
+                .unwrap();
+            assert_eq!(
+                http_request.uri().path(),
+                "/_matrix/client/v3/rooms/!room:example.org/messages"
+            );
+            assert_eq!(
+                http_request.uri().query(),
+                Some("from=t47-1234&dir=b&limit=2&filter=%7B%22lazy_load_members%22%3Atrue%7D")
+            );
+
+            let request =
+                Request::try_from_http_request(http_request, &["!room:example.org"]).unwrap();
+            assert_eq!(request.room_id, "!room:example.org");
+            assert_eq!(request.from.as_deref(), Some("t47-1234"));
+            assert_eq!(request.to, None);
+            assert_eq!(request.dir, Direction::Backward);
+            assert_eq!(request.limit, uint!(2));
+            assert_eq!(
+                request.filter.lazy_load_options,
+                LazyLoadOptions::Enabled { include_redundant_members: false }
+            );
+
+            // Events are returned in reverse chronological order when paginating backward.
+            let chunk = vec![
+                from_json_value(json!({
+                    "content": { "body": "second", "msgtype": "m.text" },
+                    "event_id": "$second:example.org",
+                    "origin_server_ts": 2,
+                    "room_id": "!room:example.org",
+                    "sender": "@alice:example.org",
+                    "type": "m.room.message",
+                }))
+                .unwrap(),
+                from_json_value(json!({
+                    "content": { "body": "first", "msgtype": "m.text" },
+                    "event_id": "$first:example.org",
+                    "origin_server_ts": 1,
+                    "room_id": "!room:example.org",
+                    "sender": "@alice:example.org",
+                    "type": "m.room.message",
+                }))
+                .unwrap(),
+            ];
+            let response = Response {
+                start: "t47-1234".to_owned(),
+                end: Some("t45-1230".to_owned()),
+                chunk,
+                state: vec![],
+            };
+            let http_response = response.try_into_http_response::<Vec<u8>>().unwrap();
+
+            let response = Response::try_from_http_response(http_response).unwrap();
+            assert_eq!(response.start, "t47-1234");
+            assert_eq!(response.end.as_deref(), Some("t45-1230"));
+            assert!(response.state.is_empty());
+
+            let event_ids: Vec<_> = response
+                .chunk
+                .iter()
+                .map(|raw| raw.deserialize().unwrap())
+                .map(|event: AnyTimelineEvent| event.event_id().to_owned())
+                .collect();
+            assert_eq!(event_ids, ["$second:example.org", "$first:example.org"]);
+        }
+
         #[test]
         fn serialize_some_room_event_filter() {
             let room_id = owned_room_id!("!roomid:example.org");
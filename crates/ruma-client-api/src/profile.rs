@@ -1,7 +1,35 @@
 //! Endpoints for user profiles.
 
+#[cfg(feature = "unstable-msc4133")]
+use ruma_common::serde::StringEnum;
+
+#[cfg(feature = "unstable-msc4133")]
+use crate::PrivOwnedStr;
+
 pub mod get_avatar_url;
 pub mod get_display_name;
 pub mod get_profile;
 pub mod set_avatar_url;
 pub mod set_display_name;
+
+/// A field of a user's profile.
+///
+/// This uses the extensible profiles from [MSC4133].
+///
+/// [MSC4133]: https://github.com/matrix-org/matrix-spec-proposals/pull/4133
+#[cfg(feature = "unstable-msc4133")]
+#[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/doc/string_enum.md"))]
+#[derive(Clone, PartialEq, Eq, StringEnum)]
+#[non_exhaustive]
+pub enum ProfileField {
+    /// The user's display name.
+    #[ruma_enum(rename = "displayname")]
+    DisplayName,
+
+    /// The URL of the user's avatar.
+    #[ruma_enum(rename = "avatar_url")]
+    AvatarUrl,
+
+    #[doc(hidden)]
+    _Custom(PrivOwnedStr),
+}
@@ -7,10 +7,18 @@ pub mod v3 {
     //!
     //! [spec]: https://spec.matrix.org/latest/client-server-api/#get_matrixclientv3profileuserid
 
+    #[cfg(feature = "unstable-msc4133")]
+    use std::{borrow::Cow, collections::BTreeMap};
+
     use ruma_common::{
         api::{request, response, Metadata},
         metadata, OwnedMxcUri, OwnedUserId,
     };
+    #[cfg(feature = "unstable-msc4133")]
+    use serde_json::{from_value as from_json_value, Value as JsonValue};
+
+    #[cfg(feature = "unstable-msc4133")]
+    use crate::profile::ProfileField;
 
     const METADATA: Metadata = metadata! {
         method: GET,
@@ -56,6 +64,15 @@ pub mod v3 {
         #[cfg(feature = "unstable-msc2448")]
         #[serde(rename = "xyz.amorgan.blurhash", skip_serializing_if = "Option::is_none")]
         pub blurhash: Option<String>,
+
+        /// The custom fields of the user's profile, using the extensible profiles from
+        /// [MSC4133](https://github.com/matrix-org/matrix-spec-proposals/pull/4133).
+        ///
+        /// Prefer to use the public fields of `Response` where possible and
+        /// [`Response::get()`] to access any field.
+        #[cfg(feature = "unstable-msc4133")]
+        #[serde(flatten)]
+        pub custom_fields: BTreeMap<String, JsonValue>,
     }
 
     impl Request {
@@ -73,7 +90,134 @@ pub mod v3 {
                 displayname,
                 #[cfg(feature = "unstable-msc2448")]
                 blurhash: None,
+                #[cfg(feature = "unstable-msc4133")]
+                custom_fields: BTreeMap::new(),
+            }
+        }
+
+        /// Returns the value of the given profile field, if set.
+        ///
+        /// Prefer to use the public fields of `Response` where possible; this method is meant to
+        /// be used for custom fields only.
+        #[cfg(feature = "unstable-msc4133")]
+        pub fn get(&self, field: &ProfileField) -> Option<Cow<'_, JsonValue>> {
+            match field {
+                ProfileField::DisplayName => {
+                    self.displayname.as_ref().map(|d| Cow::Owned(JsonValue::String(d.clone())))
+                }
+                ProfileField::AvatarUrl => self
+                    .avatar_url
+                    .as_ref()
+                    .map(|url| Cow::Owned(JsonValue::String(url.as_str().to_owned()))),
+                _ => self.custom_fields.get(field.as_str()).map(Cow::Borrowed),
             }
         }
+
+        /// Sets the given profile field to the given value.
+        ///
+        /// Prefer to use the public fields of `Response` where possible; this method is meant to
+        /// be used for custom fields only and does not allow setting arbitrary data for the
+        /// supported ones.
+        #[cfg(feature = "unstable-msc4133")]
+        pub fn set(&mut self, field: ProfileField, value: JsonValue) -> serde_json::Result<()> {
+            match field {
+                ProfileField::DisplayName => self.displayname = from_json_value(value)?,
+                ProfileField::AvatarUrl => self.avatar_url = from_json_value(value)?,
+                _ => {
+                    self.custom_fields.insert(field.as_str().to_owned(), value);
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    #[cfg(all(test, any(feature = "client", feature = "server")))]
+    mod tests {
+        #[cfg(feature = "client")]
+        #[test]
+        fn deserialize_response() {
+            use ruma_common::api::IncomingResponse;
+            use serde_json::json;
+
+            let body = json!({
+                "avatar_url": "mxc://matrix.org/SDGdghriugerRg",
+                "displayname": "Alice Margatroid",
+            });
+            let res = super::Response::try_from_http_response(
+                http::Response::builder().body(serde_json::to_vec(&body).unwrap()).unwrap(),
+            )
+            .unwrap();
+            assert_eq!(res.avatar_url.as_deref().unwrap(), "mxc://matrix.org/SDGdghriugerRg");
+            assert_eq!(res.displayname.as_deref(), Some("Alice Margatroid"));
+
+            let res = super::Response::try_from_http_response(
+                http::Response::builder()
+                    .body(serde_json::to_vec(&json!({ "displayname": "Alice" })).unwrap())
+                    .unwrap(),
+            )
+            .unwrap();
+            assert_eq!(res.avatar_url, None);
+            assert_eq!(res.displayname.as_deref(), Some("Alice"));
+        }
+
+        #[cfg(feature = "server")]
+        #[test]
+        fn serialize_response() {
+            use ruma_common::{api::OutgoingResponse, owned_mxc_uri};
+            use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+            let res = super::Response::new(
+                Some(owned_mxc_uri!("mxc://matrix.org/SDGdghriugerRg")),
+                Some("Alice Margatroid".to_owned()),
+            )
+            .try_into_http_response::<Vec<u8>>()
+            .unwrap();
+            assert_eq!(
+                from_json_slice::<JsonValue>(res.body()).unwrap(),
+                json!({
+                    "avatar_url": "mxc://matrix.org/SDGdghriugerRg",
+                    "displayname": "Alice Margatroid",
+                })
+            );
+
+            let res = super::Response::new(None, Some("Alice".to_owned()))
+                .try_into_http_response::<Vec<u8>>()
+                .unwrap();
+            assert_eq!(
+                from_json_slice::<JsonValue>(res.body()).unwrap(),
+                json!({ "displayname": "Alice" })
+            );
+        }
+
+        #[cfg(all(feature = "unstable-msc4133", feature = "client", feature = "server"))]
+        #[test]
+        fn custom_fields_round_trip() {
+            use ruma_common::api::{IncomingResponse, OutgoingResponse};
+            use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+            use crate::profile::ProfileField;
+
+            let mut res = super::Response::new(None, Some("Alice".to_owned()));
+            res.set(ProfileField::from("m.tz"), json!("Europe/Paris")).unwrap();
+            res.set(ProfileField::AvatarUrl, json!("mxc://matrix.org/SDGdghriugerRg")).unwrap();
+
+            let http_res = res.try_into_http_response::<Vec<u8>>().unwrap();
+            assert_eq!(
+                from_json_slice::<JsonValue>(http_res.body()).unwrap(),
+                json!({
+                    "avatar_url": "mxc://matrix.org/SDGdghriugerRg",
+                    "displayname": "Alice",
+                    "m.tz": "Europe/Paris",
+                })
+            );
+
+            let res = super::Response::try_from_http_response(http_res).unwrap();
+            assert_eq!(res.avatar_url.as_deref().unwrap(), "mxc://matrix.org/SDGdghriugerRg");
+            assert_eq!(res.get(&ProfileField::DisplayName).unwrap().as_str(), Some("Alice"));
+            assert_eq!(res.get(&"m.tz".into()).unwrap().as_str(), Some("Europe/Paris"));
+            assert_eq!(res.custom_fields.len(), 1);
+            assert_eq!(res.get(&"m.pronouns".into()), None);
+        }
     }
 }
@@ -27,3 +27,27 @@ pub enum Visibility {
     #[doc(hidden)]
     _Custom(PrivOwnedStr),
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::Visibility;
+
+    #[test]
+    fn visibility_round_trip() {
+        assert_eq!(to_json_value(Visibility::Public).unwrap(), json!("public"));
+        assert_eq!(to_json_value(Visibility::Private).unwrap(), json!("private"));
+
+        assert_eq!(from_json_value::<Visibility>(json!("public")).unwrap(), Visibility::Public);
+        assert_eq!(from_json_value::<Visibility>(json!("private")).unwrap(), Visibility::Private);
+        assert_eq!(Visibility::default(), Visibility::Private);
+
+        // Unknown values are kept as-is rather than falling back to the default.
+        let custom = from_json_value::<Visibility>(json!("publc")).unwrap();
+        assert_ne!(custom, Visibility::Public);
+        assert_ne!(custom, Visibility::Private);
+        assert_eq!(custom.as_str(), "publc");
+        assert_eq!(to_json_value(custom).unwrap(), json!("publc"));
+    }
+}
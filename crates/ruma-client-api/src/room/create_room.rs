@@ -201,4 +201,137 @@ pub mod v3 {
         #[doc(hidden)]
         _Custom(PrivOwnedStr),
     }
+
+    #[cfg(all(test, feature = "client", feature = "server"))]
+    mod tests {
+        use assert_matches2::assert_matches;
+        use assign::assign;
+        use ruma_common::{
+            api::{IncomingRequest as _, MatrixVersion, OutgoingRequest as _, SendAccessToken},
+            owned_user_id, RoomVersionId,
+        };
+        use ruma_events::{
+            room::{
+                guest_access::{GuestAccess, RoomGuestAccessEventContent},
+                history_visibility::{HistoryVisibility, RoomHistoryVisibilityEventContent},
+            },
+            AnyInitialStateEvent, InitialStateEvent,
+        };
+        use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+        use super::{Request, RoomPreset};
+        use crate::room::Visibility;
+
+        #[test]
+        fn visibility_round_trip() {
+            let mut req = Request::new();
+            req.visibility = Visibility::Public;
+
+            let http_req = req
+                .try_into_http_request::<Vec<u8>>(
+                    "https://homeserver.tld",
+                    SendAccessToken::IfRequired("auth_tok"),
+                    &[MatrixVersion::V1_1],
+                )
+                .unwrap();
+            assert_eq!(
+                from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+                json!({ "visibility": "public" })
+            );
+
+            let req = Request::try_from_http_request(http_req, &[] as &[String]).unwrap();
+            assert_eq!(req.visibility, Visibility::Public);
+        }
+
+        #[test]
+        fn public_room_with_initial_state_round_trip() {
+            let req = assign!(Request::new(), {
+                initial_state: vec![
+                    InitialStateEvent::new(RoomGuestAccessEventContent::new(GuestAccess::CanJoin))
+                        .to_raw_any(),
+                    InitialStateEvent::new(RoomHistoryVisibilityEventContent::new(
+                        HistoryVisibility::WorldReadable,
+                    ))
+                    .to_raw_any(),
+                ],
+                invite: vec![owned_user_id!("@alice:example.org")],
+                name: Some("Ruma Lounge".to_owned()),
+                preset: Some(RoomPreset::PublicChat),
+                room_alias_name: Some("ruma-lounge".to_owned()),
+                room_version: Some(RoomVersionId::V10),
+                topic: Some("All things Ruma".to_owned()),
+                visibility: Visibility::Public,
+            });
+
+            let http_req = req
+                .try_into_http_request::<Vec<u8>>(
+                    "https://homeserver.tld",
+                    SendAccessToken::IfRequired("auth_tok"),
+                    &[MatrixVersion::V1_1],
+                )
+                .unwrap();
+            assert_eq!(
+                from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+                json!({
+                    "initial_state": [
+                        {
+                            "type": "m.room.guest_access",
+                            "state_key": "",
+                            "content": { "guest_access": "can_join" },
+                        },
+                        {
+                            "type": "m.room.history_visibility",
+                            "state_key": "",
+                            "content": { "history_visibility": "world_readable" },
+                        },
+                    ],
+                    "invite": ["@alice:example.org"],
+                    "name": "Ruma Lounge",
+                    "preset": "public_chat",
+                    "room_alias_name": "ruma-lounge",
+                    "room_version": "10",
+                    "topic": "All things Ruma",
+                    "visibility": "public",
+                })
+            );
+
+            let req = Request::try_from_http_request(http_req, &[] as &[String]).unwrap();
+            assert_eq!(req.initial_state.len(), 2);
+            assert_matches!(
+                req.initial_state[0].deserialize().unwrap(),
+                AnyInitialStateEvent::RoomGuestAccess(guest_access)
+            );
+            assert_eq!(guest_access.content.guest_access, GuestAccess::CanJoin);
+            assert_matches!(
+                req.initial_state[1].deserialize().unwrap(),
+                AnyInitialStateEvent::RoomHistoryVisibility(history_visibility)
+            );
+            assert_eq!(
+                history_visibility.content.history_visibility,
+                HistoryVisibility::WorldReadable
+            );
+            assert_eq!(req.invite, ["@alice:example.org"]);
+            assert_eq!(req.name.as_deref(), Some("Ruma Lounge"));
+            assert_eq!(req.preset, Some(RoomPreset::PublicChat));
+            assert_eq!(req.room_alias_name.as_deref(), Some("ruma-lounge"));
+            assert_eq!(req.room_version, Some(RoomVersionId::V10));
+            assert_eq!(req.topic.as_deref(), Some("All things Ruma"));
+            assert_eq!(req.visibility, Visibility::Public);
+        }
+
+        #[test]
+        fn default_visibility() {
+            let http_req = Request::new()
+                .try_into_http_request::<Vec<u8>>(
+                    "https://homeserver.tld",
+                    SendAccessToken::IfRequired("auth_tok"),
+                    &[MatrixVersion::V1_1],
+                )
+                .unwrap();
+            assert_eq!(from_json_slice::<JsonValue>(http_req.body()).unwrap(), json!({}));
+
+            let req = Request::try_from_http_request(http_req, &[] as &[String]).unwrap();
+            assert_eq!(req.visibility, Visibility::Private);
+        }
+    }
 }
@@ -86,4 +86,71 @@ pub mod v1 {
             Default::default()
         }
     }
+
+    #[cfg(all(test, feature = "client", feature = "server"))]
+    mod tests {
+        use assert_matches2::assert_matches;
+        use js_int::uint;
+        use ruma_common::{
+            api::{IncomingResponse as _, OutgoingResponse as _},
+            room::RoomType,
+            space::SpaceRoomJoinRule,
+        };
+        use serde_json::{
+            from_slice as from_json_slice, json, to_vec as to_json_vec, Value as JsonValue,
+        };
+
+        use super::Response;
+
+        #[test]
+        fn response_round_trip() {
+            let body = json!({
+                "next_batch": "next_batch_token",
+                "rooms": [
+                    {
+                        "avatar_url": "mxc://example.org/abcdef",
+                        "canonical_alias": "#general:example.org",
+                        "children_state": [
+                            {
+                                "content": {
+                                    "via": ["example.org"],
+                                },
+                                "origin_server_ts": 1_629_413_349_153_u64,
+                                "sender": "@alice:example.org",
+                                "state_key": "!a:example.org",
+                                "type": "m.space.child",
+                            },
+                        ],
+                        "guest_can_join": false,
+                        "join_rule": "knock",
+                        "name": "The First Space",
+                        "num_joined_members": 42,
+                        "room_id": "!space:example.org",
+                        "room_type": "m.space",
+                        "topic": "No other spaces were created first, ever",
+                        "world_readable": true,
+                    },
+                ],
+            });
+
+            let res =
+                Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                    .unwrap();
+            assert_eq!(res.next_batch.as_deref(), Some("next_batch_token"));
+            assert_matches!(res.rooms.as_slice(), [room]);
+            assert_eq!(room.room_id, "!space:example.org");
+            assert_eq!(room.name.as_deref(), Some("The First Space"));
+            assert_eq!(room.num_joined_members, uint!(42));
+            assert_eq!(room.join_rule, SpaceRoomJoinRule::Knock);
+            assert_eq!(room.room_type, Some(RoomType::Space));
+            assert_matches!(room.children_state.as_slice(), [child]);
+            let child = child.deserialize().unwrap();
+            assert_eq!(child.state_key, "!a:example.org");
+            assert_eq!(child.sender, "@alice:example.org");
+            assert_eq!(child.content.via, ["example.org"]);
+
+            let http_res = res.try_into_http_response::<Vec<u8>>().unwrap();
+            assert_eq!(from_json_slice::<JsonValue>(http_res.body()).unwrap(), body);
+        }
+    }
 }
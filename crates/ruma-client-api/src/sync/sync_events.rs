@@ -62,3 +62,36 @@ impl DeviceLists {
         self.changed.is_empty() && self.left.is_empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ruma_common::owned_user_id;
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::DeviceLists;
+
+    #[test]
+    fn device_lists_serde() {
+        let device_lists = DeviceLists::new();
+        assert!(device_lists.is_empty());
+        assert_eq!(to_json_value(&device_lists).unwrap(), json!({}));
+
+        let device_lists = from_json_value::<DeviceLists>(json!({})).unwrap();
+        assert!(device_lists.is_empty());
+
+        let mut device_lists = DeviceLists::new();
+        device_lists.changed = vec![owned_user_id!("@alice:example.org")];
+        device_lists.left = vec![owned_user_id!("@bob:example.org")];
+        assert!(!device_lists.is_empty());
+
+        let json = json!({
+            "changed": ["@alice:example.org"],
+            "left": ["@bob:example.org"],
+        });
+        assert_eq!(to_json_value(&device_lists).unwrap(), json);
+
+        let device_lists = from_json_value::<DeviceLists>(json).unwrap();
+        assert_eq!(device_lists.changed, ["@alice:example.org"]);
+        assert_eq!(device_lists.left, ["@bob:example.org"]);
+    }
+}
@@ -495,6 +495,74 @@ impl RoomSummary {
             && self.joined_member_count.is_none()
             && self.invited_member_count.is_none()
     }
+
+    /// Computes the name of a room without a name or canonical alias from this summary.
+    ///
+    /// This follows the [algorithm of the spec] to calculate the display name of a room from its
+    /// heroes, like `Alice, Bob and 3 others`. `display_name` is called with the user ID of each
+    /// hero and should return its display name in the room, if it has one. Otherwise the user ID
+    /// is used.
+    ///
+    /// If both member counts are missing, the heroes are assumed to be the only other members of
+    /// the room.
+    ///
+    /// [algorithm of the spec]: https://spec.matrix.org/latest/client-server-api/#calculating-the-display-name-for-a-room
+    pub fn fallback_room_name<F>(&self, mut display_name: F) -> String
+    where
+        F: FnMut(&str) -> Option<String>,
+    {
+        let names: Vec<_> = self
+            .heroes
+            .iter()
+            .map(|user_id| display_name(user_id).unwrap_or_else(|| user_id.clone()))
+            .collect();
+
+        let member_count = match (self.joined_member_count, self.invited_member_count) {
+            (None, None) => names.len() + 1,
+            (joined, invited) => {
+                let count = joined.unwrap_or_default() + invited.unwrap_or_default();
+                count.try_into().unwrap_or(usize::MAX)
+            }
+        };
+
+        if member_count <= 1 {
+            return if names.is_empty() {
+                "Empty Room".to_owned()
+            } else {
+                format!("Empty Room (was {})", join_names(&names, None))
+            };
+        }
+
+        let others = (member_count - 1).saturating_sub(names.len());
+        if names.is_empty() {
+            return others_label(others);
+        }
+
+        join_names(&names, (others > 0).then_some(others))
+    }
+}
+
+/// Joins the given names like `Alice, Bob and Charlie`, or `Alice, Bob and 3 others` if there
+/// are other members.
+fn join_names(names: &[String], others: Option<usize>) -> String {
+    let mut parts: Vec<_> = names.iter().map(String::as_str).collect();
+    let others = others.map(others_label);
+    parts.extend(others.as_deref());
+
+    match parts.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {last}", rest.join(", ")),
+        Some((last, _)) => (*last).to_owned(),
+        None => String::new(),
+    }
+}
+
+/// The label for the given number of other members, like `1 other` or `3 others`.
+fn others_label(count: usize) -> String {
+    if count == 1 {
+        "1 other".to_owned()
+    } else {
+        format!("{count} others")
+    }
 }
 
 /// Updates to the rooms that the user has been invited to.
@@ -596,9 +664,10 @@ impl ToDevice {
 #[cfg(test)]
 mod tests {
     use assign::assign;
+    use js_int::uint;
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
-    use super::Timeline;
+    use super::{RoomSummary, Timeline};
 
     #[test]
     fn timeline_serde() {
@@ -615,15 +684,103 @@ mod tests {
         let timeline_default_deserialized = from_json_value::<Timeline>(json!({})).unwrap();
         assert!(!timeline_default_deserialized.limited);
     }
+
+    fn display_name(user_id: &str) -> Option<String> {
+        match user_id {
+            "@alice:example.org" => Some("Alice".to_owned()),
+            "@bob:example.org" => Some("Bob".to_owned()),
+            "@carol:example.org" => Some("Carol".to_owned()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn fallback_room_name_two_members() {
+        let summary = from_json_value::<RoomSummary>(json!({
+            "m.heroes": ["@alice:example.org"],
+            "m.joined_member_count": 2,
+        }))
+        .unwrap();
+        assert_eq!(summary.fallback_room_name(display_name), "Alice");
+
+        // The other member is invited and doesn't have a display name.
+        let summary = from_json_value::<RoomSummary>(json!({
+            "m.heroes": ["@dave:example.org"],
+            "m.joined_member_count": 1,
+            "m.invited_member_count": 1,
+        }))
+        .unwrap();
+        assert_eq!(summary.fallback_room_name(display_name), "@dave:example.org");
+
+        // The other member left.
+        let summary = from_json_value::<RoomSummary>(json!({
+            "m.heroes": ["@alice:example.org"],
+            "m.joined_member_count": 1,
+        }))
+        .unwrap();
+        assert_eq!(summary.fallback_room_name(display_name), "Empty Room (was Alice)");
+
+        assert_eq!(RoomSummary::new().fallback_room_name(display_name), "Empty Room");
+    }
+
+    #[test]
+    fn fallback_room_name_five_members() {
+        let mut summary = from_json_value::<RoomSummary>(json!({
+            "m.heroes": [
+                "@alice:example.org",
+                "@bob:example.org",
+                "@carol:example.org",
+                "@dave:example.org",
+            ],
+            "m.joined_member_count": 5,
+        }))
+        .unwrap();
+        assert_eq!(
+            summary.fallback_room_name(display_name),
+            "Alice, Bob, Carol and @dave:example.org"
+        );
+
+        // Without member counts, the heroes are the only other members.
+        summary.joined_member_count = None;
+        assert_eq!(
+            summary.fallback_room_name(display_name),
+            "Alice, Bob, Carol and @dave:example.org"
+        );
+
+        // Not all the other members are heroes.
+        summary.heroes.truncate(2);
+        summary.joined_member_count = Some(uint!(4));
+        summary.invited_member_count = Some(uint!(1));
+        assert_eq!(summary.fallback_room_name(display_name), "Alice, Bob and 2 others");
+
+        summary.invited_member_count = None;
+        assert_eq!(summary.fallback_room_name(display_name), "Alice, Bob and 1 other");
+    }
+
+    #[test]
+    fn fallback_room_name_without_heroes() {
+        let mut summary = from_json_value::<RoomSummary>(json!({
+            "m.joined_member_count": 2,
+        }))
+        .unwrap();
+        assert_eq!(summary.fallback_room_name(display_name), "1 other");
+
+        summary.joined_member_count = Some(uint!(4));
+        assert_eq!(summary.fallback_room_name(display_name), "3 others");
+    }
 }
 
 #[cfg(all(test, feature = "client"))]
 mod client_tests {
     use std::time::Duration;
 
-    use ruma_common::api::{MatrixVersion, OutgoingRequest as _, SendAccessToken};
+    use ruma_common::{
+        api::{IncomingResponse as _, MatrixVersion, OutgoingRequest as _, SendAccessToken},
+        room_id,
+    };
+    use serde_json::{json, to_vec as to_json_vec};
 
-    use super::{Filter, PresenceState, Request};
+    use super::{Filter, PresenceState, Request, Response};
 
     #[test]
     fn serialize_all_params() {
@@ -651,6 +808,61 @@ mod client_tests {
         assert!(query.contains("set_presence=offline"));
         assert!(query.contains("timeout=30000"));
     }
+
+    #[test]
+    fn deserialize_minimal_response_with_joined_room() {
+        let body = json!({
+            "next_batch": "s72595_4483_1934",
+            "rooms": {
+                "join": {
+                    "!726s6s6q:example.com": {
+                        "timeline": {
+                            "events": [
+                                {
+                                    "content": {
+                                        "body": "I am a fish",
+                                        "msgtype": "m.text",
+                                    },
+                                    "event_id": "$143273582443PhrSn:example.org",
+                                    "origin_server_ts": 1_432_735_824_653_u64,
+                                    "sender": "@example:example.org",
+                                    "type": "m.room.message",
+                                },
+                            ],
+                            "limited": true,
+                            "prev_batch": "t34-23535_0_0",
+                        },
+                    },
+                },
+            },
+        });
+        let http_response = http::Response::new(to_json_vec(&body).unwrap());
+
+        let response = Response::try_from_http_response(http_response).unwrap();
+        assert_eq!(response.next_batch, "s72595_4483_1934");
+        assert!(response.presence.is_empty());
+        assert!(response.account_data.is_empty());
+        assert!(response.to_device.is_empty());
+        assert!(response.device_lists.is_empty());
+        assert!(response.device_one_time_keys_count.is_empty());
+        assert_eq!(response.device_unused_fallback_key_types, None);
+
+        let rooms = response.rooms;
+        assert!(rooms.leave.is_empty());
+        assert!(rooms.invite.is_empty());
+        assert!(rooms.knock.is_empty());
+        assert_eq!(rooms.join.len(), 1);
+
+        let joined_room = rooms.join.get(room_id!("!726s6s6q:example.com")).unwrap();
+        assert!(joined_room.state.is_empty());
+        assert!(joined_room.timeline.limited);
+        assert_eq!(joined_room.timeline.prev_batch.as_deref(), Some("t34-23535_0_0"));
+        assert_eq!(joined_room.timeline.events.len(), 1);
+
+        let event = joined_room.timeline.events[0].deserialize().unwrap();
+        assert_eq!(event.event_id(), "$143273582443PhrSn:example.org");
+        assert_eq!(event.sender(), "@example:example.org");
+    }
 }
 
 #[cfg(all(test, feature = "server"))]
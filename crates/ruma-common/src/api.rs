@@ -44,6 +44,33 @@ use crate::UserId;
 ///     }
 /// };
 /// ```
+///
+/// The deprecation and removal versions are available with [`Metadata::deprecated_in()`] and
+/// [`Metadata::removed_in()`]. Since the `Request` and `Response` types don't have access to the
+/// metadata, the versions should also be set in the [`request`] and [`response`] macros, with an
+/// optional note pointing to the replacement. The types are then marked as `#[deprecated]`, and
+/// compilation fails if the versions don't match the history of the `METADATA` in scope:
+///
+/// ```
+/// # mod qux {
+/// # use ruma_common::{metadata, api::{request, response, Metadata}};
+/// # const METADATA: Metadata = metadata! {
+/// #     method: GET,
+/// #     rate_limited: true,
+/// #     authentication: AccessToken,
+/// #     history: {
+/// #         1.0 => "/_matrix/media/r0/qux",
+/// #         1.2 => deprecated,
+/// #         1.3 => removed,
+/// #     }
+/// # };
+/// #[request(deprecated = 1.2, removed = 1.3, note = "Use the new qux endpoint instead.")]
+/// pub struct Request {}
+///
+/// #[response(deprecated = 1.2, removed = 1.3, note = "Use the new qux endpoint instead.")]
+/// pub struct Response {}
+/// # }
+/// ```
 #[macro_export]
 macro_rules! metadata {
     ( $( $field:ident: $rhs:tt ),+ $(,)? ) => {
@@ -95,14 +122,40 @@ macro_rules! metadata {
     ( @optional_version $version:literal ) => { Some($crate::api::MatrixVersion::from_lit(stringify!($version))) }
 }
 
+// Used by the `request` and `response` macros to wrap the (de)serialization of the body with
+// `body_format = "cbor"`, so it fails with a clear error if the `cbor` feature is not enabled.
+#[doc(hidden)]
+#[cfg(feature = "cbor")]
+#[macro_export]
+macro_rules! __require_cbor_feature {
+    ( $($tt:tt)* ) => { $($tt)* };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "cbor"))]
+#[macro_export]
+macro_rules! __require_cbor_feature {
+    ( $($tt:tt)* ) => {{
+        ::std::compile_error!(
+            "`body_format = \"cbor\"` requires the `cbor` feature of `ruma` or `ruma-common`"
+        );
+        // Don't diverge, to avoid unreachable code warnings after the error.
+        ::std::option::Option::None.unwrap()
+    }};
+}
+
 /// Generates [`OutgoingRequest`] and [`IncomingRequest`] implementations.
 ///
 /// The `OutgoingRequest` impl is on the `Request` type this attribute is used on. It is
 /// feature-gated behind `cfg(feature = "client")`.
 ///
-/// The `IncomingRequest` impl is on `IncomingRequest`, which is either a type alias to
-/// `Request` or a fully-owned version of the same, depending of whether `Request` has any
-/// lifetime parameters. It is feature-gated behind `cfg(feature = "server")`.
+/// The `IncomingRequest` impl is also on the `Request` type. It is feature-gated behind
+/// `cfg(feature = "server")`.
+///
+/// The `Request` type is fully owned, i.e. its fields should use owned types like `String` or
+/// `OwnedRoomId` rather than references, so it can be stored across `.await` points or sent to
+/// other threads. `Clone` and `Debug` are derived for it. Other derives can be added with
+/// `#[request(derive(PartialEq, Eq))]`, which is useful to compare requests in tests.
 ///
 /// The generated code expects a `METADATA` constant of type [`Metadata`] to be in scope,
 /// alongside a `Response` type that implements [`OutgoingResponse`] (for
@@ -116,15 +169,24 @@ macro_rules! metadata {
 ///   headers on the request. The value must implement `Display`. Generally this is a `String`.
 ///   The attribute value shown above as `HEADER_NAME` must be a `const` expression of the type
 ///   `http::header::HeaderName`, like one of the constants from `http::header`, e.g.
-///   `CONTENT_TYPE`.
+///   `CONTENT_TYPE`, or a string literal with the raw name of the header, e.g.
+///   `"X-Custom-Name"`. If the field is not an `Option`, deserialization fails when the header
+///   is missing.
 /// * `#[ruma_api(path)]`: Fields with this attribute will be inserted into the matching path
 ///   component of the request URL. If there are multiple of these fields, the order in which
-///   they are declared must match the order in which they occur in the request path.
+///   they are declared must match the order in which they occur in the request path. Trailing
+///   path arguments can be made optional with a `?` suffix, like `:filename?`, in which case
+///   their field must be an `Option`. When it is `None`, the segment is omitted from the URL.
+///   The values are percent-encoded in outgoing requests, and must be percent-decoded in the
+///   `path_args` of incoming requests.
 /// * `#[ruma_api(query)]`: Fields with this attribute will be inserting into the URL's query
 ///   string.
-/// * `#[ruma_api(query_map)]`: Instead of individual query fields, one query_map field, of any
-///   type that implements `IntoIterator<Item = (String, String)>` (e.g. `HashMap<String,
-///   String>`, can be used for cases where an endpoint supports arbitrary query parameters.
+/// * `#[ruma_api(query_map)]`: One query_map field, of any type that implements
+///   `IntoIterator<Item = (String, String)>` (e.g. `HashMap<String, String>`), can be used for
+///   cases where an endpoint supports arbitrary query parameters. It can be combined with
+///   `query` fields, in which case its entries are appended to the query string and it
+///   collects all query parameters that don't match a `query` field. The type must then also
+///   implement `FromIterator<(String, String)>`. It can't be combined with a `body` field.
 /// * No attribute: Fields without an attribute are part of the body. They can use `#[serde]`
 ///   attributes to customize (de)serialization.
 /// * `#[ruma_api(body)]`: Use this if multiple endpoints should share a request body type, or
@@ -135,6 +197,50 @@ macro_rules! metadata {
 ///   entire request body, but this attribute is for endpoints where the body can be anything,
 ///   not just JSON. The field type must be `Vec<u8>`.
 ///
+/// Body and query fields can also use `#[ruma_api(default)]` to fall back to the `Default`
+/// value of their type when they are missing from an incoming request, or `#[ruma_api(default
+/// = "path::to::fn")]` to call the given function instead. For query fields, it can be written
+/// after the kind of the field, as `#[ruma_api(query, default)]`, or in a separate
+/// `#[ruma_api(default)]` attribute; the order doesn't matter. The value in the query string
+/// always takes precedence over the default. Values that are equal to the default, or to the
+/// value returned by the function, are not sent, which requires the type to implement
+/// `PartialEq`, unless the field has its own `#[serde(skip_serializing_if)]` attribute. If all
+/// the fields of the request have a default value, `Default` is implemented for the `Request`.
+///
+/// For example, a `#[ruma_api(query, default)]` field of type `bool` is a flag that only
+/// appears in the query string as `flag=true`, and that is `false` when it is missing. If
+/// `false` must be sent explicitly, use a `#[ruma_api(query)]` field of type `Option<bool>`
+/// instead, which is omitted when it is `None`.
+///
+/// ## Body format
+///
+/// The body of the request is JSON by default. If the `cbor` feature is enabled, the
+/// `#[request(body_format = "cbor")]` attribute can be used to serialize it as CBOR instead,
+/// with the `application/cbor` content type. This doesn't affect `raw_body` fields. Types that
+/// rely on JSON, like [`Raw`](crate::serde::Raw), can't be used in a CBOR body.
+///
+/// ## Deprecation
+///
+/// Deprecated endpoints should set the versions of their history in the attribute, like
+/// `#[request(deprecated = 1.3, removed = 1.6, note = "Use the new endpoint instead.")]`,
+/// where `removed` and `note` are optional. The `Request` type is then marked as
+/// `#[deprecated]`, and compilation fails if the versions don't match the history of the
+/// `METADATA`. See the [`metadata!`](crate::metadata) macro for an example.
+///
+/// ## Error type
+///
+/// By default, the error returned by the endpoint is a [`MatrixError`](error::MatrixError). A
+/// different type can be used with `#[request(error = MyError)]`, and the same attribute on
+/// the `Response`. The type must implement [`EndpointError`], whose `from_http_response`
+/// method receives every response with an error status code.
+///
+/// Specific status codes can be mapped to variants of the error type in the `#[response]`
+/// attribute, like `#[response(error = MyError { 429 => RateLimited, 404 => NotFound })]`.
+/// Each variant must be a tuple variant with a single field that implements [`EndpointError`],
+/// like [`MatrixError`](error::MatrixError), which is constructed from the response. The
+/// responses with other status codes fall back to the `EndpointError` implementation of the
+/// error type.
+///
 /// ## Examples
 ///
 /// ```
@@ -217,6 +323,14 @@ pub use ruma_macros::request;
 ///
 /// The generated code expects a `METADATA` constant of type [`Metadata`] to be in scope.
 ///
+/// `Clone` and `Debug` are derived for the `Response` type. Other derives can be added with
+/// `#[response(derive(PartialEq, Eq))]`.
+///
+/// Like for requests, the body of the response can be serialized as CBOR instead of JSON with
+/// `#[response(body_format = "cbor")]` if the `cbor` feature is enabled, and the `Response`
+/// type of a deprecated endpoint should be marked as deprecated with the same `deprecated`,
+/// `removed` and `note` arguments as the `Request`.
+///
 /// ## Attributes
 ///
 /// To declare which part of the request a field belongs to:
@@ -224,7 +338,9 @@ pub use ruma_macros::request;
 /// * `#[ruma_api(header = HEADER_NAME)]`: Fields with this attribute will be treated as HTTP
 ///   headers on the response. The value must implement `Display`. Generally this is a
 ///   `String`. The attribute value shown above as `HEADER_NAME` must be a header name constant
-///   from `http::header`, e.g. `CONTENT_TYPE`.
+///   from `http::header`, e.g. `CONTENT_TYPE`, or a string literal with the raw name of the
+///   header, e.g. `"X-Custom-Name"`. If the field is not an `Option`, deserialization fails
+///   when the header is missing.
 /// * No attribute: Fields without an attribute are part of the body. They can use `#[serde]`
 ///   attributes to customize (de)serialization.
 /// * `#[ruma_api(body)]`: Use this if multiple endpoints should share a response body type, or
@@ -234,6 +350,9 @@ pub use ruma_macros::request;
 /// * `#[ruma_api(raw_body)]`: Like `body` in that the field annotated with it represents the
 ///   entire response body, but this attribute is for endpoints where the body can be anything,
 ///   not just JSON. The field type must be `Vec<u8>`.
+/// * `#[ruma_api(status)]`: The field annotated with it is the HTTP status code of the
+///   response. The field type must be `http::StatusCode`. Without it, the status code of
+///   outgoing responses is always `200 OK`.
 ///
 /// ## Examples
 ///
@@ -463,11 +582,16 @@ pub trait OutgoingResponse {
 }
 
 /// Gives users the ability to define their own serializable / deserializable errors.
+///
+/// Set the error type of an endpoint with `#[request(error = MyError)]` and
+/// `#[response(error = MyError)]`. Status codes can be mapped to variants of the error type with
+/// `#[response(error = MyError { 429 => RateLimited })]`.
 pub trait EndpointError: OutgoingResponse + StdError + Sized + Send + 'static {
     /// Tries to construct `Self` from an `http::Response`.
     ///
-    /// This will always return `Err` variant when no `error` field is defined in
-    /// the `ruma_api` macro.
+    /// This is called for every response with a status code of 400 or above, except the ones that
+    /// are mapped to a variant in the `#[response]` attribute, so the implementation can also map
+    /// specific status codes to specific errors.
     fn from_http_response<T: AsRef<[u8]>>(response: http::Response<T>) -> Self;
 }
 
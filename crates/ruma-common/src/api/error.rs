@@ -121,6 +121,11 @@ pub enum IntoHttpError {
     #[error("JSON serialization failed: {0}")]
     Json(#[from] serde_json::Error),
 
+    /// CBOR serialization failed.
+    #[cfg(feature = "cbor")]
+    #[error("CBOR serialization failed: {0}")]
+    Cbor(#[from] ciborium::ser::Error<std::io::Error>),
+
     /// Query parameter serialization failed.
     #[error("query parameter serialization failed: {0}")]
     Query(#[from] serde_html_form::ser::Error),
@@ -226,6 +231,11 @@ pub enum DeserializationError {
     #[error(transparent)]
     Json(#[from] serde_json::Error),
 
+    /// CBOR deserialization failed.
+    #[cfg(feature = "cbor")]
+    #[error(transparent)]
+    Cbor(#[from] ciborium::de::Error<std::io::Error>),
+
     /// Query parameter deserialization failed.
     #[error(transparent)]
     Query(#[from] serde_html_form::de::Error),
@@ -36,6 +36,29 @@ pub struct Metadata {
 }
 
 impl Metadata {
+    /// Creates a new `Metadata` from its parts.
+    ///
+    /// This is a `const fn`, so it can be used to build metadata in static contexts, like routing
+    /// tables. Most endpoints should use the [`metadata!`](crate::metadata) macro instead.
+    pub const fn new(
+        method: Method,
+        rate_limited: bool,
+        authentication: AuthScheme,
+        history: VersionHistory,
+    ) -> Self {
+        Self { method, rate_limited, authentication, history }
+    }
+
+    /// Returns the Matrix version that deprecated this endpoint, if any.
+    pub const fn deprecated_in(&self) -> Option<MatrixVersion> {
+        self.history.deprecated_in()
+    }
+
+    /// Returns the Matrix version that removed this endpoint, if any.
+    pub const fn removed_in(&self) -> Option<MatrixVersion> {
+        self.history.removed_in()
+    }
+
     /// Returns an empty request body for this Matrix request.
     ///
     /// For `GET` requests, it returns an entirely empty buffer, for others it returns an empty JSON
@@ -79,6 +102,10 @@ impl Metadata {
     }
 
     /// Generate the endpoint URL for this endpoint.
+    ///
+    /// Optional path parameters, declared with a trailing `?` like `:filename?`, are omitted from
+    /// the URL along with their preceding slash when `path_args` doesn't contain enough elements
+    /// for them.
     pub fn make_endpoint_url(
         &self,
         versions: &[MatrixVersion],
@@ -97,10 +124,12 @@ impl Metadata {
 
         for segment in segments {
             if segment.starts_with(':') {
-                let arg = path_args
-                    .next()
-                    .expect("number of placeholders must match number of arguments")
-                    .to_string();
+                let arg = match path_args.next() {
+                    Some(arg) => arg.to_string(),
+                    // Optional arguments are always at the end of the path.
+                    None if segment.ends_with('?') => break,
+                    None => panic!("number of placeholders must match number of arguments"),
+                };
                 let arg = utf8_percent_encode(&arg, PATH_PERCENT_ENCODE_SET);
 
                 write!(res, "/{arg}").expect("writing to a String using fmt::Write can't fail");
@@ -165,6 +194,8 @@ impl VersionHistory {
     ///
     /// Specifically, this checks the following invariants:
     /// - Path Arguments are equal (in order, amount, and argument name) in all path strings
+    /// - Optional Path Arguments (with a trailing `?`) are only followed by other optional Path
+    ///   Arguments
     /// - In stable_paths:
     ///   - matrix versions are in ascending order
     ///   - no matrix version is referenced twice
@@ -189,6 +220,21 @@ impl VersionHistory {
             });
         }
 
+        const fn check_optional_path_args_are_trailing(path: &'static str) {
+            let mut seen_optional_arg = false;
+
+            iter::for_each!(segment in string::split(path, "/") => {
+                let is_optional_arg =
+                    string::starts_with(segment, ":") && string::ends_with(segment, "?");
+
+                if is_optional_arg {
+                    seen_optional_arg = true;
+                } else if seen_optional_arg {
+                    panic!("Optional path arguments must be at the end of the path");
+                }
+            });
+        }
+
         const fn check_path_args_equal(first: &'static str, second: &'static str) {
             let mut second_iter = string::split(second, "/").next();
 
@@ -239,6 +285,7 @@ impl VersionHistory {
 
         iter::for_each!(unstable_path in slice::iter(unstable_paths) => {
             check_path_is_valid(unstable_path);
+            check_optional_path_args_are_trailing(unstable_path);
             check_path_args_equal(ref_path, unstable_path);
         });
 
@@ -246,6 +293,7 @@ impl VersionHistory {
 
         iter::for_each!(stable_path in slice::iter(stable_paths) => {
             check_path_is_valid(stable_path.1);
+            check_optional_path_args_are_trailing(stable_path.1);
             check_path_args_equal(ref_path, stable_path.1);
 
             let current_version = stable_path.0;
@@ -379,30 +427,66 @@ impl VersionHistory {
     /// Returns the *first* version this endpoint was added in.
     ///
     /// Is `None` when this endpoint is unstable/unreleased.
-    pub fn added_in(&self) -> Option<MatrixVersion> {
-        self.stable_paths.first().map(|(v, _)| *v)
+    pub const fn added_in(&self) -> Option<MatrixVersion> {
+        match self.stable_paths {
+            [(version, _), ..] => Some(*version),
+            [] => None,
+        }
     }
 
     /// Returns the Matrix version that deprecated this endpoint, if any.
-    pub fn deprecated_in(&self) -> Option<MatrixVersion> {
+    pub const fn deprecated_in(&self) -> Option<MatrixVersion> {
         self.deprecated
     }
 
     /// Returns the Matrix version that removed this endpoint, if any.
-    pub fn removed_in(&self) -> Option<MatrixVersion> {
+    pub const fn removed_in(&self) -> Option<MatrixVersion> {
         self.removed
     }
 
     /// Picks the last unstable path, if it exists.
-    pub fn unstable(&self) -> Option<&'static str> {
-        self.unstable_paths.last().copied()
+    pub const fn unstable(&self) -> Option<&'static str> {
+        match self.unstable_paths {
+            [.., path] => Some(*path),
+            [] => None,
+        }
     }
 
-    /// Returns all path variants in canon form, for use in server routers.
+    /// Returns all path variants in canon form.
+    ///
+    /// Optional path arguments keep their trailing `?`, like `:filename?`. Use
+    /// [`router_paths()`](Self::router_paths) to get paths for server routers.
     pub fn all_paths(&self) -> impl Iterator<Item = &'static str> {
         self.unstable_paths().chain(self.stable_paths().map(|(_, path)| path))
     }
 
+    /// Returns all path variants in canon form, for use in server routers.
+    ///
+    /// Paths with optional path arguments are expanded to one path for every number of optional
+    /// arguments that are present, from the shortest to the longest. For example,
+    /// `/download/:media_id/:filename?` gives `/download/:media_id` and
+    /// `/download/:media_id/:filename`.
+    pub fn router_paths(&self) -> impl Iterator<Item = String> {
+        self.all_paths().flat_map(|path| {
+            let segments: Vec<_> = path
+                .split('/')
+                .map(|segment| segment.strip_suffix('?').unwrap_or(segment))
+                .collect();
+            let optional_count = path
+                .split('/')
+                .rev()
+                .take_while(|segment| segment.starts_with(':') && segment.ends_with('?'))
+                .count();
+
+            (segments.len() - optional_count..=segments.len()).map(move |len| {
+                match segments[..len].join("/") {
+                    path if path.is_empty() => "/".to_owned(),
+                    path => path,
+                }
+            })
+        })
+    }
+
     /// Returns all unstable path variants in canon form.
     pub fn unstable_paths(&self) -> impl Iterator<Item = &'static str> {
         self.unstable_paths.iter().copied()
@@ -770,6 +854,22 @@ mod tests {
         assert_eq!(url, "https://example.org/s/?foo=bar");
     }
 
+    #[test]
+    fn make_endpoint_url_with_optional_path_args() {
+        let meta = stable_only_metadata(&[(V1_0, "/s/:x/:y?")]);
+        let url = meta
+            .make_endpoint_url(&[V1_0], "https://example.org", &[&"123", &"file.png"], "")
+            .unwrap();
+        assert_eq!(url, "https://example.org/s/123/file.png");
+
+        let url = meta.make_endpoint_url(&[V1_0], "https://example.org", &[&"123"], "").unwrap();
+        assert_eq!(url, "https://example.org/s/123");
+
+        let url =
+            meta.make_endpoint_url(&[V1_0], "https://example.org", &[&"123"], "foo=bar").unwrap();
+        assert_eq!(url, "https://example.org/s/123?foo=bar");
+    }
+
     #[test]
     #[should_panic]
     fn make_endpoint_url_wrong_num_path_args() {
@@ -777,6 +877,41 @@ mod tests {
         _ = meta.make_endpoint_url(&[V1_0], "https://example.org", &[], "");
     }
 
+    #[test]
+    #[should_panic = "Optional path arguments must be at the end of the path"]
+    fn optional_path_args_not_trailing() {
+        VersionHistory::new(&["/s/:x?/y"], &[], None, None);
+    }
+
+    #[test]
+    fn router_paths_with_optional_path_args() {
+        let history = VersionHistory::new(
+            &["/unstable/:x/:y?"],
+            &[(V1_0, "/r0/:x/:y?"), (V1_1, "/v3/:x/:y?")],
+            None,
+            None,
+        );
+        assert_eq!(
+            history.router_paths().collect::<Vec<_>>(),
+            ["/unstable/:x", "/unstable/:x/:y", "/r0/:x", "/r0/:x/:y", "/v3/:x", "/v3/:x/:y"]
+        );
+
+        let history = VersionHistory::new(&["/s/:x/:y?/:z?"], &[], None, None);
+        assert_eq!(
+            history.router_paths().collect::<Vec<_>>(),
+            ["/s/:x", "/s/:x/:y", "/s/:x/:y/:z"]
+        );
+
+        let history = VersionHistory::new(&["/:x?"], &[], None, None);
+        assert_eq!(history.router_paths().collect::<Vec<_>>(), ["/", "/:x"]);
+    }
+
+    #[test]
+    fn router_paths_without_optional_path_args() {
+        let history = VersionHistory::new(&["/unstable/:x"], &[(V1_0, "/r0/:x")], None, None);
+        assert_eq!(history.router_paths().collect::<Vec<_>>(), ["/unstable/:x", "/r0/:x"]);
+    }
+
     const EMPTY: VersionHistory =
         VersionHistory { unstable_paths: &[], stable_paths: &[], deprecated: None, removed: None };
 
@@ -172,3 +172,104 @@ pub enum KeyUsage {
     #[doc(hidden)]
     _Custom(PrivOwnedStr),
 }
+
+#[cfg(test)]
+mod tests {
+    use assert_matches2::assert_matches;
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::{DeviceKeys, OneTimeKey, SignedKey};
+    use crate::{device_key_id, owned_user_id, serde::Base64, EventEncryptionAlgorithm};
+
+    #[test]
+    fn device_keys_algorithms_round_trip() {
+        let json = json!({
+            "user_id": "@alice:example.org",
+            "device_id": "JLAFKJWSCS",
+            "algorithms": [
+                "m.olm.v1.curve25519-aes-sha2",
+                "m.megolm.v1.aes-sha2",
+                "org.example.custom.v1",
+            ],
+            "keys": {
+                "curve25519:JLAFKJWSCS": "3C5BFWi2Y8MaVvjM8M22DBmh24PmgR0nPvJOIArzgyI",
+            },
+            "signatures": {},
+        });
+
+        let device_keys = from_json_value::<DeviceKeys>(json.clone()).unwrap();
+        assert_eq!(
+            device_keys.algorithms,
+            [
+                EventEncryptionAlgorithm::OlmV1Curve25519AesSha2,
+                EventEncryptionAlgorithm::MegolmV1AesSha2,
+                EventEncryptionAlgorithm::from("org.example.custom.v1"),
+            ]
+        );
+        assert_eq!(device_keys.algorithms[2].as_str(), "org.example.custom.v1");
+
+        assert_eq!(to_json_value(&device_keys).unwrap(), json);
+    }
+
+    #[test]
+    fn signed_one_time_key_round_trip() {
+        let signatures = [(
+            owned_user_id!("@alice:example.org"),
+            [(
+                device_key_id!("ed25519:JLAFKJWSCS").to_owned(),
+                "FLWxXqGbwrb8SM3Y795eB6OA8bwBcoMZFXBqnTn58AYWZSqiD45tlBVcDa2L7RwdKXebW/VzDlnfVJ+9jok1Bw".to_owned(),
+            )]
+            .into(),
+        )]
+        .into();
+        let key = OneTimeKey::SignedKey(SignedKey::new(
+            Base64::parse("zKbLg+NrIjpnagy+pIY6uPL4ZwEG2v+8F9lmgsnlZzs").unwrap(),
+            signatures,
+        ));
+
+        let json = json!({
+            "key": "zKbLg+NrIjpnagy+pIY6uPL4ZwEG2v+8F9lmgsnlZzs",
+            "signatures": {
+                "@alice:example.org": {
+                    "ed25519:JLAFKJWSCS": "FLWxXqGbwrb8SM3Y795eB6OA8bwBcoMZFXBqnTn58AYWZSqiD45tlBVcDa2L7RwdKXebW/VzDlnfVJ+9jok1Bw",
+                },
+            },
+        });
+        assert_eq!(to_json_value(&key).unwrap(), json);
+
+        assert_matches!(from_json_value::<OneTimeKey>(json).unwrap(), OneTimeKey::SignedKey(key));
+        assert_eq!(key.key.encode(), "zKbLg+NrIjpnagy+pIY6uPL4ZwEG2v+8F9lmgsnlZzs");
+        assert!(!key.fallback);
+        let alice_signatures = &key.signatures[&owned_user_id!("@alice:example.org")];
+        assert!(alice_signatures.contains_key(device_key_id!("ed25519:JLAFKJWSCS")));
+    }
+
+    #[test]
+    fn fallback_one_time_key_round_trip() {
+        let key = OneTimeKey::SignedKey(SignedKey::new_fallback(
+            Base64::parse("j3fR3HemM16M7CWhoI4Sk5ZsdmdfQHsKL1xuSft6MSw").unwrap(),
+            Default::default(),
+        ));
+
+        let json = json!({
+            "key": "j3fR3HemM16M7CWhoI4Sk5ZsdmdfQHsKL1xuSft6MSw",
+            "signatures": {},
+            "fallback": true,
+        });
+        assert_eq!(to_json_value(&key).unwrap(), json);
+
+        assert_matches!(from_json_value::<OneTimeKey>(json).unwrap(), OneTimeKey::SignedKey(key));
+        assert!(key.fallback);
+    }
+
+    #[test]
+    fn unsigned_one_time_key_round_trip() {
+        let key = OneTimeKey::Key("zKbLg+NrIjpnagy+pIY6uPL4ZwEG2v+8F9lmgsnlZzs".to_owned());
+
+        let json = json!("zKbLg+NrIjpnagy+pIY6uPL4ZwEG2v+8F9lmgsnlZzs");
+        assert_eq!(to_json_value(&key).unwrap(), json);
+
+        assert_matches!(from_json_value::<OneTimeKey>(json).unwrap(), OneTimeKey::Key(key));
+        assert_eq!(key, "zKbLg+NrIjpnagy+pIY6uPL4ZwEG2v+8F9lmgsnlZzs");
+    }
+}
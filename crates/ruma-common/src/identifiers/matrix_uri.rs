@@ -217,6 +217,15 @@ impl From<&UserId> for MatrixId {
     }
 }
 
+impl From<&RoomOrAliasId> for MatrixId {
+    fn from(room_id: &RoomOrAliasId) -> Self {
+        match <&RoomId>::try_from(room_id) {
+            Ok(room_id) => room_id.into(),
+            Err(room_alias_id) => room_alias_id.into(),
+        }
+    }
+}
+
 impl From<(OwnedRoomOrAliasId, OwnedEventId)> for MatrixId {
     fn from(ids: (OwnedRoomOrAliasId, OwnedEventId)) -> Self {
         Self::Event(ids.0, ids.1)
@@ -522,6 +531,20 @@ impl fmt::Display for MatrixUri {
     }
 }
 
+impl From<MatrixToUri> for MatrixUri {
+    /// Convert a [`matrix.to` URI] to the equivalent [`matrix:` URI], without an action.
+    ///
+    /// This can be used to handle identifiers in both forms with the same code path, for example
+    /// when parsing links pasted by a user.
+    ///
+    /// [`matrix.to` URI]: https://spec.matrix.org/latest/appendices/#matrixto-navigation
+    /// [`matrix:` URI]: https://spec.matrix.org/latest/appendices/#matrix-uri-scheme
+    fn from(uri: MatrixToUri) -> Self {
+        let MatrixToUri { id, via } = uri;
+        Self { id, via, action: None }
+    }
+}
+
 impl TryFrom<&str> for MatrixUri {
     type Error = Error;
 
@@ -549,7 +572,7 @@ mod tests {
     use super::{MatrixId, MatrixToUri, MatrixUri};
     use crate::{
         event_id, matrix_uri::UriAction, room_alias_id, room_id, server_name, user_id,
-        RoomOrAliasId,
+        RoomOrAliasId, ServerName,
     };
 
     #[test]
@@ -593,6 +616,21 @@ mod tests {
                 .to_string(),
             "https://matrix.to/#/!ruma:notareal.hs/$event:notareal.hs?via=notareal.hs"
         );
+        assert_eq!(
+            <&RoomOrAliasId>::from(room_alias_id!("#ruma:notareal.hs"))
+                .matrix_to_uri_via(Vec::<&ServerName>::new())
+                .to_string(),
+            "https://matrix.to/#/%23ruma:notareal.hs"
+        );
+        assert_eq!(
+            <&RoomOrAliasId>::from(room_id!("!ruma:notareal.hs"))
+                .matrix_to_event_uri_via(
+                    event_id!("$event:notareal.hs"),
+                    vec![server_name!("notareal.hs")]
+                )
+                .to_string(),
+            "https://matrix.to/#/!ruma:notareal.hs/$event:notareal.hs?via=notareal.hs"
+        );
     }
 
     #[test]
@@ -890,6 +928,21 @@ mod tests {
                 .to_string(),
             "matrix:roomid/ruma:notareal.hs/e/event:notareal.hs?via=notareal.hs"
         );
+        assert_eq!(
+            <&RoomOrAliasId>::from(room_alias_id!("#ruma:notareal.hs"))
+                .matrix_uri_via(Vec::<&ServerName>::new(), true)
+                .to_string(),
+            "matrix:r/ruma:notareal.hs?action=join"
+        );
+        assert_eq!(
+            <&RoomOrAliasId>::from(room_id!("!ruma:notareal.hs"))
+                .matrix_event_uri_via(
+                    event_id!("$event:notareal.hs"),
+                    vec![server_name!("notareal.hs")]
+                )
+                .to_string(),
+            "matrix:roomid/ruma:notareal.hs/e/event:notareal.hs?via=notareal.hs"
+        );
     }
 
     #[test]
@@ -1055,6 +1108,54 @@ mod tests {
         assert_eq!(matrix_uri.action(), Some(&UriAction::Join));
     }
 
+    #[test]
+    fn matrixtouri_to_matrixuri() {
+        let matrix_uri: MatrixUri =
+            MatrixToUri::parse("https://matrix.to/#/%40jplatte%3Anotareal.hs")
+                .expect("Failed to create MatrixToUri.")
+                .into();
+        assert_eq!(matrix_uri.id(), &user_id!("@jplatte:notareal.hs").into());
+        assert!(matrix_uri.via().is_empty());
+        assert_eq!(matrix_uri.action(), None);
+        assert_eq!(matrix_uri.to_string(), "matrix:u/jplatte:notareal.hs");
+
+        let matrix_uri: MatrixUri = MatrixToUri::parse("https://matrix.to/#/%23ruma%3Anotareal.hs")
+            .expect("Failed to create MatrixToUri.")
+            .into();
+        assert_eq!(matrix_uri.id(), &room_alias_id!("#ruma:notareal.hs").into());
+        assert_eq!(matrix_uri.to_string(), "matrix:r/ruma:notareal.hs");
+
+        let matrix_uri: MatrixUri = MatrixToUri::parse(
+            "https://matrix.to/#/%21ruma%3Anotareal.hs?via=notareal.hs&via=anotherunreal.hs",
+        )
+        .expect("Failed to create MatrixToUri.")
+        .into();
+        assert_eq!(matrix_uri.id(), &room_id!("!ruma:notareal.hs").into());
+        assert_eq!(
+            matrix_uri.via(),
+            &[server_name!("notareal.hs").to_owned(), server_name!("anotherunreal.hs").to_owned()]
+        );
+        assert_eq!(
+            matrix_uri.to_string(),
+            "matrix:roomid/ruma:notareal.hs?via=notareal.hs&via=anotherunreal.hs"
+        );
+
+        let matrix_uri: MatrixUri = MatrixToUri::parse(
+            "https://matrix.to/#/!ruma:notareal.hs/%24event%3Anotareal.hs?via=notareal.hs",
+        )
+        .expect("Failed to create MatrixToUri.")
+        .into();
+        assert_eq!(
+            matrix_uri.id(),
+            &(room_id!("!ruma:notareal.hs"), event_id!("$event:notareal.hs")).into()
+        );
+        assert_eq!(matrix_uri.via(), &[server_name!("notareal.hs").to_owned()]);
+        assert_eq!(
+            matrix_uri.to_string(),
+            "matrix:roomid/ruma:notareal.hs/e/event:notareal.hs?via=notareal.hs"
+        );
+    }
+
     #[test]
     fn parse_matrixuri_invalid_uri() {
         assert_eq!(
@@ -35,6 +35,11 @@ impl RoomId {
     }
 
     /// Returns the server name of the room ID.
+    ///
+    /// Older room IDs have the form `!opaque:server` and embed the name of the server that created
+    /// the room, which can be used as a routing hint over federation. Returns `None` for room IDs
+    /// that don't contain a colon, like newer fully opaque room IDs, or if the part after the
+    /// colon is not a valid server name.
     pub fn server_name(&self) -> Option<&ServerName> {
         <&RoomOrAliasId>::from(self).server_name()
     }
@@ -259,11 +264,20 @@ mod tests {
 
     #[test]
     fn valid_room_id_with_non_standard_port() {
-        assert_eq!(
-            <&RoomId>::try_from("!29fhd83h92h0:example.com:5000")
-                .expect("Failed to create RoomId."),
-            "!29fhd83h92h0:example.com:5000"
-        );
+        let room_id = <&RoomId>::try_from("!29fhd83h92h0:example.com:5000")
+            .expect("Failed to create RoomId.");
+        assert_eq!(room_id, "!29fhd83h92h0:example.com:5000");
+        assert_eq!(room_id.server_name(), Some(server_name!("example.com:5000")));
+    }
+
+    #[test]
+    fn valid_room_id_with_ip_server_name() {
+        let room_id =
+            <&RoomId>::try_from("!29fhd83h92h0:1.1.1.1:8448").expect("Failed to create RoomId.");
+        assert_eq!(room_id.server_name(), Some(server_name!("1.1.1.1:8448")));
+
+        let room_id = <&RoomId>::try_from("!29fhd83h92h0:[::1]").expect("Failed to create RoomId.");
+        assert_eq!(room_id.server_name(), Some(server_name!("[::1]")));
     }
 
     #[test]
@@ -281,6 +295,16 @@ mod tests {
         assert_eq!(room_id.server_name(), None);
     }
 
+    #[test]
+    fn opaque_room_id_without_server_name() {
+        let room_id = <&RoomId>::try_from("!31hneApxJ_1o-63DmFrpeqnkFfWppnzWso1JvH3ogLM")
+            .expect("Failed to create RoomId.");
+        assert_eq!(room_id.server_name(), None);
+
+        let owned_room_id = room_id.to_owned();
+        assert_eq!(owned_room_id.server_name(), None);
+    }
+
     #[test]
     fn invalid_room_id_host() {
         let room_id = <&RoomId>::try_from("!29fhd83h92h0:/").expect("Failed to create RoomId.");
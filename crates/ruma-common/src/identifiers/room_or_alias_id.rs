@@ -5,7 +5,10 @@ use std::hint::unreachable_unchecked;
 use ruma_macros::IdZst;
 use tracing::warn;
 
-use super::{server_name::ServerName, OwnedRoomAliasId, OwnedRoomId, RoomAliasId, RoomId};
+use super::{
+    matrix_uri::UriAction, server_name::ServerName, MatrixToUri, MatrixUri, OwnedEventId,
+    OwnedRoomAliasId, OwnedRoomId, OwnedServerName, RoomAliasId, RoomId,
+};
 
 /// A Matrix [room ID] or a Matrix [room alias ID].
 ///
@@ -59,6 +62,91 @@ impl RoomOrAliasId {
         self.variant() == Variant::RoomAliasId
     }
 
+    /// Create a `matrix.to` URI for this room ID or alias with a list of servers that should know
+    /// it.
+    ///
+    /// The list of servers is only useful for room IDs, it can be empty for room aliases. To get
+    /// the list of servers, it is recommended to use the [routing algorithm] from the spec.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ruma_common::{server_name, RoomOrAliasId};
+    ///
+    /// let room_id = <&RoomOrAliasId>::try_from("!somewhere:example.org").unwrap();
+    /// assert_eq!(
+    ///     room_id.matrix_to_uri_via([&*server_name!("example.org")]).to_string(),
+    ///     "https://matrix.to/#/!somewhere:example.org?via=example.org"
+    /// );
+    /// ```
+    ///
+    /// [routing algorithm]: https://spec.matrix.org/latest/appendices/#routing
+    pub fn matrix_to_uri_via<T>(&self, via: T) -> MatrixToUri
+    where
+        T: IntoIterator,
+        T::Item: Into<OwnedServerName>,
+    {
+        MatrixToUri::new(self.into(), via.into_iter().map(Into::into).collect())
+    }
+
+    /// Create a `matrix.to` URI for an event scoped under this room ID or alias with a list of
+    /// servers that should know it.
+    ///
+    /// The list of servers is only useful for room IDs, it can be empty for room aliases. To get
+    /// the list of servers, it is recommended to use the [routing algorithm] from the spec.
+    ///
+    /// [routing algorithm]: https://spec.matrix.org/latest/appendices/#routing
+    pub fn matrix_to_event_uri_via<T>(&self, ev_id: impl Into<OwnedEventId>, via: T) -> MatrixToUri
+    where
+        T: IntoIterator,
+        T::Item: Into<OwnedServerName>,
+    {
+        MatrixToUri::new(
+            (self.to_owned(), ev_id.into()).into(),
+            via.into_iter().map(Into::into).collect(),
+        )
+    }
+
+    /// Create a `matrix:` URI for this room ID or alias with a list of servers that should know
+    /// it.
+    ///
+    /// The list of servers is only useful for room IDs, it can be empty for room aliases. To get
+    /// the list of servers, it is recommended to use the [routing algorithm] from the spec.
+    ///
+    /// If `join` is `true`, a click on the URI should join the room.
+    ///
+    /// [routing algorithm]: https://spec.matrix.org/latest/appendices/#routing
+    pub fn matrix_uri_via<T>(&self, via: T, join: bool) -> MatrixUri
+    where
+        T: IntoIterator,
+        T::Item: Into<OwnedServerName>,
+    {
+        MatrixUri::new(
+            self.into(),
+            via.into_iter().map(Into::into).collect(),
+            Some(UriAction::Join).filter(|_| join),
+        )
+    }
+
+    /// Create a `matrix:` URI for an event scoped under this room ID or alias with a list of
+    /// servers that should know it.
+    ///
+    /// The list of servers is only useful for room IDs, it can be empty for room aliases. To get
+    /// the list of servers, it is recommended to use the [routing algorithm] from the spec.
+    ///
+    /// [routing algorithm]: https://spec.matrix.org/latest/appendices/#routing
+    pub fn matrix_event_uri_via<T>(&self, ev_id: impl Into<OwnedEventId>, via: T) -> MatrixUri
+    where
+        T: IntoIterator,
+        T::Item: Into<OwnedServerName>,
+    {
+        MatrixUri::new(
+            (self.to_owned(), ev_id.into()).into(),
+            via.into_iter().map(Into::into).collect(),
+            None,
+        )
+    }
+
     fn variant(&self) -> Variant {
         match self.as_bytes().first() {
             Some(b'!') => Variant::RoomId,
@@ -1,7 +1,8 @@
 use percent_encoding::{AsciiSet, CONTROLS};
 
 /// The [path percent-encode set] as defined in the WHATWG URL standard + `/` since
-/// we always encode single segments of the path.
+/// we always encode single segments of the path, and `%` since the segments are arbitrary
+/// values that must not be percent-decoded to something else.
 ///
 /// [path percent-encode set]: https://url.spec.whatwg.org/#path-percent-encode-set
 pub(crate) const PATH_PERCENT_ENCODE_SET: &AsciiSet = &CONTROLS
@@ -14,4 +15,5 @@ pub(crate) const PATH_PERCENT_ENCODE_SET: &AsciiSet = &CONTROLS
     .add(b'`')
     .add(b'{')
     .add(b'}')
-    .add(b'/');
+    .add(b'/')
+    .add(b'%');
@@ -42,9 +42,9 @@ impl Action {
 }
 
 /// The `set_tweak` action.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
-#[serde(from = "tweak_serde::Tweak", into = "tweak_serde::Tweak")]
+#[serde(into = "tweak_serde::Tweak")]
 pub enum Tweak {
     /// A string representing the sound to be played when this notification arrives.
     ///
@@ -59,7 +59,7 @@ pub enum Tweak {
     /// event occurred. If a `highlight` tweak is given with no value, its value is defined to be
     /// `true`. If no highlight tweak is given at all then the value of `highlight` is defined to
     /// be `false`.
-    Highlight(#[serde(default = "crate::serde::default_true")] bool),
+    Highlight(bool),
 
     /// A custom tweak
     Custom {
@@ -121,11 +121,13 @@ pub enum CustomAction {
 }
 
 mod tweak_serde {
-    use serde::{Deserialize, Serialize};
+    use serde::{Deserialize, Deserializer, Serialize};
     use serde_json::value::RawValue as RawJsonValue;
 
+    use crate::serde::from_raw_json_value;
+
     /// Values for the `set_tweak` action.
-    #[derive(Clone, Deserialize, Serialize)]
+    #[derive(Clone, Serialize)]
     #[serde(untagged)]
     pub(crate) enum Tweak {
         Sound(SoundTweak),
@@ -165,15 +167,41 @@ mod tweak_serde {
         }
     }
 
-    impl From<Tweak> for super::Tweak {
-        fn from(tweak: Tweak) -> Self {
-            use Tweak::*;
+    // Custom implementation of `Deserialize`, because an untagged enum can't contain a
+    // `RawJsonValue`, so the custom variant would never deserialize.
+    impl<'de> Deserialize<'de> for super::Tweak {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            #[derive(Deserialize)]
+            struct TweakName {
+                set_tweak: String,
+            }
 
-            match tweak {
-                Sound(SoundTweak { value }) => Self::Sound(value),
-                Highlight(HighlightTweak { value }) => Self::Highlight(value),
-                Custom { name, value } => Self::Custom { name, value },
+            #[derive(Deserialize)]
+            struct CustomTweak {
+                set_tweak: String,
+                value: Box<RawJsonValue>,
             }
+
+            let json = Box::<RawJsonValue>::deserialize(deserializer)?;
+            let TweakName { set_tweak } = from_raw_json_value(&json)?;
+
+            Ok(match set_tweak.as_str() {
+                "sound" => {
+                    let SoundTweak { value } = from_raw_json_value(&json)?;
+                    Self::Sound(value)
+                }
+                "highlight" => {
+                    let HighlightTweak { value } = from_raw_json_value(&json)?;
+                    Self::Highlight(value)
+                }
+                _ => {
+                    let CustomTweak { set_tweak, value } = from_raw_json_value(&json)?;
+                    Self::Custom { name: set_tweak, value }
+                }
+            })
         }
     }
 }
@@ -248,4 +276,44 @@ mod tests {
             Ok(Action::SetTweak(Tweak::Highlight(true)))
         );
     }
+
+    #[test]
+    fn tweak_sound_round_trip() {
+        let json_data = json!({ "set_tweak": "sound", "value": "ping.ogg" });
+
+        let action = from_json_value::<Action>(json_data.clone()).unwrap();
+        assert_eq!(action.sound(), Some("ping.ogg"));
+        assert!(!action.is_highlight());
+
+        assert_eq!(to_json_value(&action).unwrap(), json_data);
+    }
+
+    #[test]
+    fn tweak_highlight_round_trip() {
+        let json_data = json!({ "set_tweak": "highlight", "value": false });
+        let action = from_json_value::<Action>(json_data.clone()).unwrap();
+        assert_matches!(&action, Action::SetTweak(Tweak::Highlight(false)));
+        assert!(!action.is_highlight());
+        assert_eq!(to_json_value(&action).unwrap(), json_data);
+
+        // `true` is the default value, so it is omitted when serializing.
+        let action =
+            from_json_value::<Action>(json!({ "set_tweak": "highlight", "value": true })).unwrap();
+        assert!(action.is_highlight());
+        assert_eq!(to_json_value(&action).unwrap(), json!({ "set_tweak": "highlight" }));
+    }
+
+    #[test]
+    fn tweak_custom_round_trip() {
+        let json_data =
+            json!({ "set_tweak": "dev.local.vibrate", "value": { "pattern": [100, 50] } });
+
+        let action = from_json_value::<Action>(json_data.clone()).unwrap();
+        assert_matches!(&action, Action::SetTweak(Tweak::Custom { name, value }));
+        assert_eq!(name, "dev.local.vibrate");
+        assert_eq!(value.get(), r#"{"pattern":[100,50]}"#);
+        assert_eq!(action.sound(), None);
+
+        assert_eq!(to_json_value(&action).unwrap(), json_data);
+    }
 }
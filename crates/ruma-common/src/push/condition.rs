@@ -741,6 +741,82 @@ mod tests {
         assert!(!member_count_lt.applies(&event, &context));
     }
 
+    #[test]
+    fn event_match_glob_patterns_apply() {
+        let context = push_context();
+        let first_event = first_flattened_event();
+        let second_event = second_flattened_event();
+
+        let event_match = |key: &str, pattern: &str| PushCondition::EventMatch {
+            key: key.to_owned(),
+            pattern: pattern.to_owned(),
+        };
+
+        // `content.body` matches whole words, case-insensitively.
+        let body_glob = event_match("content.body", "gor*");
+        assert!(body_glob.applies(&first_event, &context));
+        assert!(!body_glob.applies(&second_event, &context));
+
+        let body_glob = event_match("content.body", "part?");
+        assert!(!body_glob.applies(&first_event, &context));
+        assert!(body_glob.applies(&second_event, &context));
+
+        let body_glob = event_match("content.body", "WARM WEL*");
+        assert!(body_glob.applies(&first_event, &context));
+
+        let body_glob = event_match("content.body", "orill*");
+        assert!(!body_glob.applies(&first_event, &context));
+
+        // Other keys must match the whole value.
+        let sender_glob = event_match("sender", "@*_whale:server.name");
+        assert!(sender_glob.applies(&first_event, &context));
+        assert!(!sender_glob.applies(&second_event, &context));
+
+        let sender_glob = event_match("sender", "@party_bot:*");
+        assert!(!sender_glob.applies(&first_event, &context));
+        assert!(sender_glob.applies(&second_event, &context));
+
+        let msgtype_glob = event_match("content.msgtype", "m.??????");
+        assert!(!msgtype_glob.applies(&first_event, &context));
+        assert!(msgtype_glob.applies(&second_event, &context));
+
+        let msgtype_glob = event_match("content.msgtype", "m.*");
+        assert!(msgtype_glob.applies(&first_event, &context));
+        assert!(msgtype_glob.applies(&second_event, &context));
+
+        let missing_key = event_match("content.format", "*");
+        assert!(!missing_key.applies(&first_event, &context));
+    }
+
+    #[test]
+    fn room_member_count_range_expressions_apply() {
+        let context = push_context();
+        let event = first_flattened_event();
+
+        let room_member_count = |is: &str| {
+            from_json_value::<PushCondition>(json!({ "kind": "room_member_count", "is": is }))
+                .unwrap()
+        };
+
+        // The room in the context has 3 members.
+        assert!(room_member_count("3").applies(&event, &context));
+        assert!(room_member_count("==3").applies(&event, &context));
+        assert!(!room_member_count("==2").applies(&event, &context));
+        assert!(room_member_count(">2").applies(&event, &context));
+        assert!(!room_member_count(">3").applies(&event, &context));
+        assert!(room_member_count(">=3").applies(&event, &context));
+        assert!(!room_member_count(">=4").applies(&event, &context));
+        assert!(room_member_count("<4").applies(&event, &context));
+        assert!(!room_member_count("<3").applies(&event, &context));
+        assert!(room_member_count("<=3").applies(&event, &context));
+        assert!(!room_member_count("<=2").applies(&event, &context));
+
+        assert!(from_json_value::<PushCondition>(
+            json!({ "kind": "room_member_count", "is": "~3" })
+        )
+        .is_err());
+    }
+
     #[test]
     fn contains_display_name_applies() {
         let context = push_context();
@@ -19,6 +19,8 @@ pub mod single_element_seq;
 mod strings;
 pub mod test;
 
+#[cfg(feature = "cbor")]
+pub use self::buf::{cbor_from_slice, cbor_to_buf};
 pub use self::{
     base64::{Base64, Base64DecodeError},
     buf::{json_to_buf, slice_to_buf},
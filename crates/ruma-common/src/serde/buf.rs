@@ -14,3 +14,26 @@ pub fn json_to_buf<B: Default + BufMut, T: Serialize>(val: &T) -> serde_json::Re
     serde_json::to_writer(&mut buf, val)?;
     Ok(buf.into_inner())
 }
+
+/// Creates a buffer and writes a serializable value to it as CBOR.
+#[cfg(feature = "cbor")]
+pub fn cbor_to_buf<B: Default + BufMut, T: Serialize>(
+    val: &T,
+) -> Result<B, ciborium::ser::Error<std::io::Error>> {
+    let mut buf = B::default().writer();
+    ciborium::into_writer(val, &mut buf)?;
+    Ok(buf.into_inner())
+}
+
+/// Deserializes a value from a CBOR byte slice.
+///
+/// An empty slice is deserialized like an empty map.
+#[cfg(feature = "cbor")]
+pub fn cbor_from_slice<T: serde::de::DeserializeOwned>(
+    s: &[u8],
+) -> Result<T, ciborium::de::Error<std::io::Error>> {
+    // The empty map in CBOR.
+    const EMPTY_MAP: &[u8] = &[0xa0];
+
+    ciborium::from_reader(if s.is_empty() { EMPTY_MAP } else { s })
+}
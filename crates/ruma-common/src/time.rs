@@ -106,9 +106,9 @@ fn f64_to_uint(val: f64) -> UInt {
 
 #[cfg(test)]
 mod tests {
-    use std::time::{Duration, UNIX_EPOCH};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-    use js_int::uint;
+    use js_int::{uint, UInt};
     use serde::{Deserialize, Serialize};
     use serde_json::json;
 
@@ -139,4 +139,33 @@ mod tests {
 
         assert_eq!(serde_json::to_value(request).unwrap(), json!({ "millis": 2000, "secs": 0 }));
     }
+
+    #[test]
+    fn system_time_round_trip() {
+        let time = UNIX_EPOCH + Duration::from_millis(1_696_412_345_678);
+
+        let millis = MilliSecondsSinceUnixEpoch::from_system_time(time).unwrap();
+        assert_eq!(millis, MilliSecondsSinceUnixEpoch(UInt::new_saturating(1_696_412_345_678)));
+        assert_eq!(millis.to_system_time(), Some(time));
+
+        let secs = SecondsSinceUnixEpoch::from_system_time(time).unwrap();
+        assert_eq!(secs, SecondsSinceUnixEpoch(uint!(1_696_412_345)));
+        assert_eq!(secs.to_system_time(), Some(UNIX_EPOCH + Duration::from_secs(1_696_412_345)));
+
+        assert_eq!(
+            MilliSecondsSinceUnixEpoch::from_system_time(UNIX_EPOCH - Duration::from_secs(1)),
+            None
+        );
+    }
+
+    #[test]
+    fn now() {
+        let before = SystemTime::now();
+        let now = MilliSecondsSinceUnixEpoch::now().to_system_time().unwrap();
+        let after = SystemTime::now();
+
+        // The timestamp is truncated to the millisecond.
+        assert!(now + Duration::from_millis(1) > before);
+        assert!(now <= after);
+    }
 }
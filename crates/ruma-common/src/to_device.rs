@@ -2,7 +2,10 @@
 //!
 //! [send-to-device]: https://spec.matrix.org/latest/client-server-api/#send-to-device-messaging
 
-use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    str::FromStr,
+};
 
 use serde::{
     de::{self, Unexpected},
@@ -51,6 +54,14 @@ impl TryFrom<&str> for DeviceIdOrAllDevices {
     }
 }
 
+impl FromStr for DeviceIdOrAllDevices {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.try_into()
+    }
+}
+
 impl Serialize for DeviceIdOrAllDevices {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -74,3 +85,44 @@ impl<'de> Deserialize<'de> for DeviceIdOrAllDevices {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::DeviceIdOrAllDevices;
+    use crate::owned_device_id;
+
+    #[test]
+    fn from_str() {
+        assert_eq!(
+            "ABCDEF".parse::<DeviceIdOrAllDevices>(),
+            Ok(DeviceIdOrAllDevices::DeviceId(owned_device_id!("ABCDEF")))
+        );
+        assert_eq!("*".parse::<DeviceIdOrAllDevices>(), Ok(DeviceIdOrAllDevices::AllDevices));
+        "".parse::<DeviceIdOrAllDevices>().unwrap_err();
+    }
+
+    #[test]
+    fn device_id_map_key_round_trip() {
+        let map = BTreeMap::from([(
+            DeviceIdOrAllDevices::DeviceId(owned_device_id!("ABCDEF")),
+            "content".to_owned(),
+        )]);
+        let json = json!({ "ABCDEF": "content" });
+
+        assert_eq!(to_json_value(&map).unwrap(), json);
+        assert_eq!(from_json_value::<BTreeMap<DeviceIdOrAllDevices, String>>(json).unwrap(), map);
+    }
+
+    #[test]
+    fn all_devices_map_key_round_trip() {
+        let map = BTreeMap::from([(DeviceIdOrAllDevices::AllDevices, "content".to_owned())]);
+        let json = json!({ "*": "content" });
+
+        assert_eq!(to_json_value(&map).unwrap(), json);
+        assert_eq!(from_json_value::<BTreeMap<DeviceIdOrAllDevices, String>>(json).unwrap(), map);
+    }
+}
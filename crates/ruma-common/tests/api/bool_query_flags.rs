@@ -0,0 +1,86 @@
+use ruma_common::api::{
+    IncomingRequest as _, MatrixVersion, OutgoingRequest as _, SendAccessToken,
+};
+
+mod flags {
+    use ruma_common::{
+        api::{request, response, Metadata},
+        metadata,
+    };
+
+    const METADATA: Metadata = metadata! {
+        method: GET,
+        rate_limited: false,
+        authentication: None,
+        history: {
+            unstable => "/_matrix/some/flags/endpoint",
+        }
+    };
+
+    /// Request type for the `flags` endpoint.
+    #[request]
+    #[derive(Default)]
+    pub struct Request {
+        /// Only sent when `true`.
+        #[ruma_api(query, default)]
+        pub full_state: bool,
+
+        /// Only sent when `Some(_)`.
+        #[ruma_api(query)]
+        pub set_presence: Option<bool>,
+    }
+
+    /// Response type for the `flags` endpoint.
+    #[response]
+    pub struct Response {}
+}
+
+fn query_string(req: flags::Request) -> Option<String> {
+    let http_req = req
+        .try_into_http_request::<Vec<u8>>(
+            "https://homeserver.tld",
+            SendAccessToken::None,
+            &[MatrixVersion::V1_1],
+        )
+        .unwrap();
+    http_req.uri().query().map(ToOwned::to_owned)
+}
+
+fn parse(query: &str) -> flags::Request {
+    let http_req = http::Request::builder()
+        .uri(format!("https://homeserver.tld/_matrix/some/flags/endpoint?{query}"))
+        .body(&[] as &[u8])
+        .unwrap();
+    flags::Request::try_from_http_request(http_req, &[] as &[String]).unwrap()
+}
+
+#[test]
+fn false_flags_are_omitted() {
+    assert_eq!(query_string(flags::Request::default()), None);
+}
+
+#[test]
+fn true_flags_are_sent() {
+    let req = flags::Request { full_state: true, ..Default::default() };
+    assert_eq!(query_string(req).as_deref(), Some("full_state=true"));
+}
+
+#[test]
+fn optional_flags_are_sent_when_set() {
+    let req = flags::Request { set_presence: Some(false), ..Default::default() };
+    assert_eq!(query_string(req).as_deref(), Some("set_presence=false"));
+
+    let req = flags::Request { full_state: true, set_presence: Some(true) };
+    assert_eq!(query_string(req).as_deref(), Some("full_state=true&set_presence=true"));
+}
+
+#[test]
+fn missing_flags_are_parsed_as_false_or_none() {
+    let req = parse("");
+    assert!(!req.full_state);
+    assert_eq!(req.set_presence, None);
+
+    let req = parse("full_state=true&set_presence=false");
+    assert!(req.full_state);
+    assert_eq!(req.set_presence, Some(false));
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "cbor")]
+#![allow(clippy::exhaustive_structs)]
+
+use http::header::CONTENT_TYPE;
+use ruma_common::api::{
+    IncomingRequest as _, IncomingResponse as _, MatrixVersion, OutgoingRequest as _,
+    OutgoingResponse as _, SendAccessToken,
+};
+
+mod send_things {
+    use ruma_common::{
+        api::{request, response, Metadata},
+        metadata,
+    };
+    use serde::{Deserialize, Serialize};
+
+    const METADATA: Metadata = metadata! {
+        method: PUT,
+        rate_limited: false,
+        authentication: None,
+        history: {
+            unstable => "/_matrix/some/cbor/endpoint/:thing_id",
+        }
+    };
+
+    #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+    pub struct Thing {
+        pub name: String,
+        pub size: u64,
+    }
+
+    /// Request type for the `send_things` endpoint.
+    #[request(body_format = "cbor")]
+    pub struct Request {
+        #[ruma_api(path)]
+        pub thing_id: String,
+
+        #[ruma_api(body)]
+        pub things: Vec<Thing>,
+    }
+
+    /// Response type for the `send_things` endpoint.
+    #[response(body_format = "cbor")]
+    pub struct Response {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub count: Option<u64>,
+    }
+}
+
+use send_things::{Request, Response, Thing};
+
+#[test]
+fn newtype_body_request_round_trip() {
+    let things = vec![
+        Thing { name: "first".to_owned(), size: 3 },
+        Thing { name: "second".to_owned(), size: 0 },
+    ];
+    let req = Request { thing_id: "abc".to_owned(), things: things.clone() };
+
+    let http_req = req
+        .try_into_http_request::<Vec<u8>>(
+            "https://homeserver.tld",
+            SendAccessToken::None,
+            &[MatrixVersion::V1_1],
+        )
+        .unwrap();
+    assert_eq!(http_req.headers().get(CONTENT_TYPE).unwrap(), "application/cbor");
+    // A CBOR array of 2 elements.
+    assert_eq!(http_req.body()[0], 0x82);
+
+    let req = Request::try_from_http_request(http_req, &["abc"]).unwrap();
+    assert_eq!(req.thing_id, "abc");
+    assert_eq!(req.things, things);
+}
+
+#[test]
+fn response_round_trip() {
+    let http_res = Response { count: Some(2) }.try_into_http_response::<Vec<u8>>().unwrap();
+    assert_eq!(http_res.headers().get(CONTENT_TYPE).unwrap(), "application/cbor");
+
+    let res = Response::try_from_http_response(http_res).unwrap();
+    assert_eq!(res.count, Some(2));
+}
+
+#[test]
+fn empty_response_body() {
+    let http_res = http::Response::builder().body(Vec::<u8>::new()).unwrap();
+
+    let res = Response::try_from_http_response(http_res).unwrap();
+    assert_eq!(res.count, None);
+}
+
+#[test]
+fn invalid_cbor_body() {
+    // A CBOR text string.
+    let http_req = http::Request::builder()
+        .method("PUT")
+        .uri("https://homeserver.tld/_matrix/some/cbor/endpoint/abc")
+        .body(b"\x63abc" as &[u8])
+        .unwrap();
+
+    Request::try_from_http_request(http_req, &["abc"]).unwrap_err();
+}
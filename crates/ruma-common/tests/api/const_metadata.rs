@@ -0,0 +1,84 @@
+//! Metadata can be built and inspected in const contexts.
+
+use http::Method;
+use ruma_common::{
+    api::{AuthScheme, MatrixVersion, Metadata, VersionHistory},
+    metadata,
+};
+
+mod get_thing {
+    use ruma_common::{
+        api::{request, response, Metadata},
+        metadata,
+    };
+
+    const METADATA: Metadata = metadata! {
+        method: GET,
+        rate_limited: false,
+        authentication: None,
+        history: {
+            1.1 => "/_matrix/client/v3/thing/:id",
+        }
+    };
+
+    #[request]
+    pub struct Request {
+        #[ruma_api(path)]
+        pub id: String,
+    }
+
+    #[response]
+    pub struct Response {}
+}
+
+const PUT_THING: Metadata = metadata! {
+    method: PUT,
+    rate_limited: true,
+    authentication: AccessToken,
+    history: {
+        unstable => "/_matrix/client/unstable/org.example/thing/:id",
+    }
+};
+
+const DELETE_THING_HISTORY: VersionHistory = VersionHistory::new(
+    &["/_matrix/client/unstable/org.example/thing/:id"],
+    &[(MatrixVersion::V1_2, "/_matrix/client/v3/thing/:id")],
+    None,
+    None,
+);
+
+const DELETE_THING: Metadata =
+    Metadata::new(Method::DELETE, false, AuthScheme::AccessToken, DELETE_THING_HISTORY);
+
+static ROUTES: [Metadata; 3] =
+    [<get_thing::Request as ruma_common::api::OutgoingRequest>::METADATA, PUT_THING, DELETE_THING];
+
+const DELETE_THING_ADDED_IN: Option<MatrixVersion> = DELETE_THING_HISTORY.added_in();
+const DELETE_THING_UNSTABLE_PATH: Option<&str> = DELETE_THING_HISTORY.unstable();
+
+#[test]
+fn static_routing_table() {
+    let routes: Vec<_> = ROUTES
+        .iter()
+        .flat_map(|metadata| metadata.history.all_paths().map(|path| (&metadata.method, path)))
+        .collect();
+
+    assert_eq!(
+        routes,
+        [
+            (&Method::GET, "/_matrix/client/v3/thing/:id"),
+            (&Method::PUT, "/_matrix/client/unstable/org.example/thing/:id"),
+            (&Method::DELETE, "/_matrix/client/unstable/org.example/thing/:id"),
+            (&Method::DELETE, "/_matrix/client/v3/thing/:id"),
+        ]
+    );
+}
+
+#[test]
+fn const_history_accessors() {
+    assert_eq!(DELETE_THING_ADDED_IN, Some(MatrixVersion::V1_2));
+    assert_eq!(DELETE_THING_UNSTABLE_PATH, Some("/_matrix/client/unstable/org.example/thing/:id"));
+    assert_eq!(DELETE_THING_HISTORY.deprecated_in(), None);
+    assert_eq!(DELETE_THING_HISTORY.removed_in(), None);
+    assert_eq!(PUT_THING.history.added_in(), None);
+}
@@ -0,0 +1,231 @@
+#![allow(clippy::exhaustive_structs)]
+
+use std::{error::Error as StdError, fmt};
+
+use assert_matches2::assert_matches;
+use bytes::BufMut;
+use http::StatusCode;
+use ruma_common::api::{
+    error::{FromHttpResponseError, IntoHttpError, MatrixError},
+    EndpointError, IncomingResponse as _, OutgoingResponse,
+};
+
+/// An error with specific variants for some status codes.
+#[derive(Debug)]
+pub enum Error {
+    RateLimited(MatrixError),
+    NotFound(MatrixError),
+    Other(MatrixError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RateLimited(error) => write!(f, "rate limited: {error}"),
+            Self::NotFound(error) => write!(f, "not found: {error}"),
+            Self::Other(error) => error.fmt(f),
+        }
+    }
+}
+
+impl StdError for Error {}
+
+impl OutgoingResponse for Error {
+    fn try_into_http_response<T: Default + BufMut>(
+        self,
+    ) -> Result<http::Response<T>, IntoHttpError> {
+        match self {
+            Self::RateLimited(error) | Self::NotFound(error) | Self::Other(error) => {
+                error.try_into_http_response()
+            }
+        }
+    }
+}
+
+impl EndpointError for Error {
+    fn from_http_response<T: AsRef<[u8]>>(response: http::Response<T>) -> Self {
+        Self::Other(MatrixError::from_http_response(response))
+    }
+}
+
+/// A generic error that wraps another error type.
+#[derive(Debug)]
+pub enum WrappedError<E> {
+    RateLimited(MatrixError),
+    Other(E),
+}
+
+impl<E: fmt::Display> fmt::Display for WrappedError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RateLimited(error) => write!(f, "rate limited: {error}"),
+            Self::Other(error) => error.fmt(f),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> StdError for WrappedError<E> {}
+
+impl<E: OutgoingResponse> OutgoingResponse for WrappedError<E> {
+    fn try_into_http_response<T: Default + BufMut>(
+        self,
+    ) -> Result<http::Response<T>, IntoHttpError> {
+        match self {
+            Self::RateLimited(error) => error.try_into_http_response(),
+            Self::Other(error) => error.try_into_http_response(),
+        }
+    }
+}
+
+impl<E: EndpointError> EndpointError for WrappedError<E> {
+    fn from_http_response<T: AsRef<[u8]>>(response: http::Response<T>) -> Self {
+        Self::Other(E::from_http_response(response))
+    }
+}
+
+mod get_thing {
+    use ruma_common::{
+        api::{request, response, Metadata},
+        metadata,
+    };
+
+    use super::Error;
+
+    const METADATA: Metadata = metadata! {
+        method: GET,
+        rate_limited: true,
+        authentication: None,
+        history: {
+            unstable => "/_matrix/some/thing",
+        }
+    };
+
+    /// Request type for the `get_thing` endpoint.
+    #[request(error = Error)]
+    pub struct Request {}
+
+    /// Response type for the `get_thing` endpoint.
+    #[response(error = Error { 429 => RateLimited, 404 => NotFound })]
+    pub struct Response {
+        pub thing: String,
+    }
+}
+
+mod get_other_thing {
+    use ruma_common::{
+        api::{request, response, Metadata},
+        metadata,
+    };
+
+    use super::Error;
+
+    const METADATA: Metadata = metadata! {
+        method: GET,
+        rate_limited: true,
+        authentication: None,
+        history: {
+            unstable => "/_matrix/some/other_thing",
+        }
+    };
+
+    /// Request type for the `get_other_thing` endpoint.
+    #[request(error = Error)]
+    pub struct Request {}
+
+    /// Response type for the `get_other_thing` endpoint.
+    #[response(error = Error)]
+    pub struct Response {}
+}
+
+mod get_wrapped_thing {
+    use ruma_common::{
+        api::{error::MatrixError, request, response, Metadata},
+        metadata,
+    };
+
+    use super::WrappedError;
+
+    const METADATA: Metadata = metadata! {
+        method: GET,
+        rate_limited: true,
+        authentication: None,
+        history: {
+            unstable => "/_matrix/some/wrapped_thing",
+        }
+    };
+
+    /// Request type for the `get_wrapped_thing` endpoint.
+    #[request(error = WrappedError<MatrixError>)]
+    pub struct Request {}
+
+    /// Response type for the `get_wrapped_thing` endpoint.
+    #[response(error = WrappedError<MatrixError> { 429 => RateLimited })]
+    pub struct Response {}
+}
+
+fn error_response(status: StatusCode, body: &'static [u8]) -> http::Response<&'static [u8]> {
+    http::Response::builder().status(status).body(body).unwrap()
+}
+
+#[test]
+fn status_is_mapped_to_error_variant() {
+    let res = get_thing::Response::try_from_http_response(error_response(
+        StatusCode::TOO_MANY_REQUESTS,
+        br#"{"errcode":"M_LIMIT_EXCEEDED","error":"Too many requests"}"#,
+    ));
+    assert_matches!(res, Err(FromHttpResponseError::Server(Error::RateLimited(error))));
+    assert_eq!(error.status_code, StatusCode::TOO_MANY_REQUESTS);
+
+    let res = get_thing::Response::try_from_http_response(error_response(
+        StatusCode::NOT_FOUND,
+        br#"{"errcode":"M_NOT_FOUND","error":"No thing"}"#,
+    ));
+    assert_matches!(res, Err(FromHttpResponseError::Server(Error::NotFound(error))));
+    assert_eq!(error.status_code, StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn status_is_not_mapped_without_mapping() {
+    let res = get_other_thing::Response::try_from_http_response(error_response(
+        StatusCode::TOO_MANY_REQUESTS,
+        br#"{"errcode":"M_LIMIT_EXCEEDED","error":"Too many requests"}"#,
+    ));
+    assert_matches!(res, Err(FromHttpResponseError::Server(Error::Other(error))));
+    assert_eq!(error.status_code, StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[test]
+fn unmapped_status_falls_back_to_matrix_error() {
+    let res = get_thing::Response::try_from_http_response(error_response(
+        StatusCode::FORBIDDEN,
+        br#"{"errcode":"M_FORBIDDEN","error":"You shall not pass"}"#,
+    ));
+    assert_matches!(res, Err(FromHttpResponseError::Server(Error::Other(error))));
+    assert_eq!(error.status_code, StatusCode::FORBIDDEN);
+}
+
+#[test]
+fn successful_response() {
+    let res = get_thing::Response::try_from_http_response(
+        http::Response::builder().body(br#"{"thing":"stuff"}"# as &[u8]).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.thing, "stuff");
+}
+
+#[test]
+fn status_is_mapped_to_generic_error_variant() {
+    let res = get_wrapped_thing::Response::try_from_http_response(error_response(
+        StatusCode::TOO_MANY_REQUESTS,
+        br#"{"errcode":"M_LIMIT_EXCEEDED","error":"Too many requests"}"#,
+    ));
+    assert_matches!(res, Err(FromHttpResponseError::Server(WrappedError::RateLimited(error))));
+    assert_eq!(error.status_code, StatusCode::TOO_MANY_REQUESTS);
+
+    let res = get_wrapped_thing::Response::try_from_http_response(error_response(
+        StatusCode::FORBIDDEN,
+        br#"{"errcode":"M_FORBIDDEN","error":"You shall not pass"}"#,
+    ));
+    assert_matches!(res, Err(FromHttpResponseError::Server(WrappedError::Other(error))));
+    assert_eq!(error.status_code, StatusCode::FORBIDDEN);
+}
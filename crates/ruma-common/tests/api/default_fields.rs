@@ -0,0 +1,118 @@
+use ruma_common::api::{
+    IncomingRequest as _, MatrixVersion, OutgoingRequest as _, SendAccessToken,
+};
+
+mod search {
+    use ruma_common::{
+        api::{request, response, Metadata},
+        metadata,
+    };
+
+    const METADATA: Metadata = metadata! {
+        method: POST,
+        rate_limited: false,
+        authentication: None,
+        history: {
+            unstable => "/_matrix/some/search/endpoint",
+        }
+    };
+
+    /// Request type for the `search` endpoint.
+    #[request]
+    pub struct Request {
+        #[ruma_api(query, default = "default_limit")]
+        pub limit: u32,
+
+        #[ruma_api(default = "default_order")]
+        pub order: String,
+
+        #[ruma_api(query, default)]
+        pub include_all: bool,
+
+        #[ruma_api(default)]
+        pub keys: Vec<String>,
+
+        #[ruma_api(default)]
+        pub term: String,
+    }
+
+    /// Response type for the `search` endpoint.
+    #[response]
+    pub struct Response {}
+
+    fn default_limit() -> u32 {
+        10
+    }
+
+    fn default_order() -> String {
+        "rank".to_owned()
+    }
+}
+
+#[test]
+fn default_request() {
+    let req = search::Request::default();
+    assert_eq!(req.limit, 10);
+    assert_eq!(req.order, "rank");
+    assert!(!req.include_all);
+    assert!(req.keys.is_empty());
+    assert_eq!(req.term, "");
+}
+
+#[test]
+fn default_values_are_not_sent() {
+    let req = search::Request { term: "ruma".to_owned(), ..Default::default() };
+
+    let http_req = req
+        .try_into_http_request::<Vec<u8>>(
+            "https://homeserver.tld",
+            SendAccessToken::None,
+            &[MatrixVersion::V1_1],
+        )
+        .unwrap();
+    assert_eq!(http_req.uri().query(), None);
+    assert_eq!(http_req.body(), br#"{"term":"ruma"}"#);
+}
+
+#[test]
+fn non_default_values_round_trip() {
+    let req = search::Request {
+        limit: 5,
+        order: "recent".to_owned(),
+        include_all: true,
+        keys: vec!["content.body".to_owned()],
+        term: "ruma".to_owned(),
+    };
+
+    let http_req = req
+        .try_into_http_request::<Vec<u8>>(
+            "https://homeserver.tld",
+            SendAccessToken::None,
+            &[MatrixVersion::V1_1],
+        )
+        .unwrap();
+    assert_eq!(http_req.uri().query(), Some("limit=5&include_all=true"));
+
+    let req = search::Request::try_from_http_request(http_req, &[] as &[String]).unwrap();
+    assert_eq!(req.limit, 5);
+    assert_eq!(req.order, "recent");
+    assert!(req.include_all);
+    assert_eq!(req.keys, ["content.body"]);
+    assert_eq!(req.term, "ruma");
+}
+
+#[test]
+fn missing_values_fall_back_to_default() {
+    let http_req = http::Request::builder()
+        .method("POST")
+        .uri("https://homeserver.tld/_matrix/some/search/endpoint")
+        .body(b"{}" as &[u8])
+        .unwrap();
+
+    let req = search::Request::try_from_http_request(http_req, &[] as &[String]).unwrap();
+    assert_eq!(req.limit, 10);
+    assert_eq!(req.order, "rank");
+    assert!(!req.include_all);
+    assert!(req.keys.is_empty());
+    assert_eq!(req.term, "");
+}
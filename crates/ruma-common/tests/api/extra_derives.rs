@@ -0,0 +1,72 @@
+#![allow(clippy::exhaustive_structs)]
+
+use std::collections::BTreeSet;
+
+use ruma_common::{
+    api::{
+        request, response, IncomingRequest as _, IncomingResponse as _, MatrixVersion, Metadata,
+        OutgoingRequest as _, OutgoingResponse as _, SendAccessToken,
+    },
+    metadata, owned_room_id, OwnedRoomId,
+};
+
+const METADATA: Metadata = metadata! {
+    method: POST,
+    rate_limited: false,
+    authentication: None,
+    history: {
+        unstable => "/_matrix/my/endpoint/:room_id",
+    }
+};
+
+/// Request type for the `extra_derives` endpoint.
+#[request(derive(PartialEq, Eq, PartialOrd, Ord, Hash))]
+pub struct Request {
+    #[ruma_api(path)]
+    pub room_id: OwnedRoomId,
+
+    #[ruma_api(query)]
+    pub limit: Option<u32>,
+
+    pub reason: String,
+}
+
+/// Response type for the `extra_derives` endpoint.
+#[response(derive(std::cmp::PartialEq, Eq))]
+pub struct Response {
+    pub count: u32,
+}
+
+#[test]
+fn request_round_trip_with_partial_eq() {
+    let req = Request {
+        room_id: owned_room_id!("!room:localhost"),
+        limit: Some(10),
+        reason: "Testing".to_owned(),
+    };
+
+    let http_req = req
+        .clone()
+        .try_into_http_request::<Vec<u8>>(
+            "https://homeserver.tld",
+            SendAccessToken::None,
+            &[MatrixVersion::V1_1],
+        )
+        .unwrap();
+    let path_args = ["!room:localhost"];
+    let parsed = Request::try_from_http_request(http_req, &path_args).unwrap();
+
+    assert_eq!(parsed, req);
+
+    let mut set = BTreeSet::new();
+    set.insert(req);
+    assert!(set.contains(&parsed));
+}
+
+#[test]
+fn response_round_trip_with_partial_eq() {
+    let res = Response { count: 3 };
+    let http_res = res.clone().try_into_http_response::<Vec<u8>>().unwrap();
+
+    assert_eq!(Response::try_from_http_response(http_res).unwrap(), res);
+}
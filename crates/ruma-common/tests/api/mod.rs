@@ -1,10 +1,22 @@
 #![cfg(feature = "api")]
 #![allow(unreachable_pub)]
 
+mod bool_query_flags;
+mod cbor;
+mod const_metadata;
 mod conversions;
+mod custom_error;
+mod default_fields;
+mod extra_derives;
 mod header_override;
 mod manual_endpoint_impl;
 mod no_fields;
 mod optional_headers;
+mod optional_path_args;
+mod owned_types;
+mod path_encoding;
+mod query_map;
+mod raw_body;
+mod raw_header_names;
 mod ruma_api;
 mod ruma_api_macros;
@@ -0,0 +1,125 @@
+use ruma_common::api::{
+    IncomingRequest as _, MatrixVersion, OutgoingRequest as _, SendAccessToken,
+};
+
+mod get_file {
+    use ruma_common::{
+        api::{request, response, Metadata},
+        metadata,
+    };
+
+    const METADATA: Metadata = metadata! {
+        method: GET,
+        rate_limited: false,
+        authentication: None,
+        history: {
+            unstable => "/_matrix/some/file/:server_name/:media_id/:filename?",
+        }
+    };
+
+    /// Request type for the `get_file` endpoint.
+    #[request]
+    pub struct Request {
+        #[ruma_api(path)]
+        pub server_name: String,
+
+        #[ruma_api(path)]
+        pub media_id: String,
+
+        #[ruma_api(path)]
+        pub filename: Option<String>,
+
+        #[ruma_api(query)]
+        pub allow_remote: Option<bool>,
+    }
+
+    /// Response type for the `get_file` endpoint.
+    #[response]
+    pub struct Response {}
+}
+
+#[test]
+fn optional_path_arg_present() {
+    use get_file::Request;
+
+    let req = Request {
+        server_name: "notareal.hs".to_owned(),
+        media_id: "abcdef".to_owned(),
+        filename: Some("my file.png".to_owned()),
+        allow_remote: None,
+    };
+
+    let http_req = req
+        .try_into_http_request::<Vec<u8>>(
+            "https://homeserver.tld",
+            SendAccessToken::None,
+            &[MatrixVersion::V1_1],
+        )
+        .unwrap();
+    assert_eq!(
+        http_req.uri().to_string(),
+        "https://homeserver.tld/_matrix/some/file/notareal.hs/abcdef/my%20file.png"
+    );
+
+    let req = Request::try_from_http_request(http_req, &["notareal.hs", "abcdef", "my file.png"])
+        .unwrap();
+    assert_eq!(req.server_name, "notareal.hs");
+    assert_eq!(req.media_id, "abcdef");
+    assert_eq!(req.filename.as_deref(), Some("my file.png"));
+}
+
+#[test]
+fn optional_path_arg_absent() {
+    use get_file::Request;
+
+    let req = Request {
+        server_name: "notareal.hs".to_owned(),
+        media_id: "abcdef".to_owned(),
+        filename: None,
+        allow_remote: Some(false),
+    };
+
+    let http_req = req
+        .try_into_http_request::<Vec<u8>>(
+            "https://homeserver.tld",
+            SendAccessToken::None,
+            &[MatrixVersion::V1_1],
+        )
+        .unwrap();
+    assert_eq!(
+        http_req.uri().to_string(),
+        "https://homeserver.tld/_matrix/some/file/notareal.hs/abcdef?allow_remote=false"
+    );
+
+    let req = Request::try_from_http_request(http_req, &["notareal.hs", "abcdef"]).unwrap();
+    assert_eq!(req.server_name, "notareal.hs");
+    assert_eq!(req.media_id, "abcdef");
+    assert_eq!(req.filename, None);
+    assert_eq!(req.allow_remote, Some(false));
+}
+
+#[test]
+fn missing_required_path_arg() {
+    use get_file::Request;
+
+    let http_req = http::Request::builder()
+        .method("GET")
+        .uri("https://homeserver.tld/_matrix/some/file/notareal.hs")
+        .body(&[] as &[u8])
+        .unwrap();
+
+    Request::try_from_http_request(http_req, &["notareal.hs"]).unwrap_err();
+}
+
+#[test]
+fn router_paths_with_and_without_optional_path_arg() {
+    let metadata = <get_file::Request as ruma_common::api::OutgoingRequest>::METADATA;
+    let paths: Vec<_> = metadata.history.router_paths().collect();
+    assert_eq!(
+        paths,
+        [
+            "/_matrix/some/file/:server_name/:media_id",
+            "/_matrix/some/file/:server_name/:media_id/:filename"
+        ]
+    );
+}
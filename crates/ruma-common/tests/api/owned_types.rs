@@ -0,0 +1,67 @@
+#![allow(clippy::exhaustive_structs)]
+
+use std::{fmt::Debug, thread};
+
+use ruma_common::{
+    api::{request, response, MatrixVersion, Metadata, OutgoingRequest as _, SendAccessToken},
+    metadata, owned_room_id, owned_user_id, OwnedRoomId, OwnedUserId,
+};
+
+const METADATA: Metadata = metadata! {
+    method: POST,
+    rate_limited: false,
+    authentication: None,
+    history: {
+        unstable => "/_matrix/my/endpoint/:room_id",
+    }
+};
+
+/// Request type for the `owned_types` endpoint.
+#[request]
+pub struct Request {
+    #[ruma_api(path)]
+    pub room_id: OwnedRoomId,
+
+    pub user_id: OwnedUserId,
+
+    pub reason: Option<String>,
+}
+
+/// Response type for the `owned_types` endpoint.
+#[response]
+pub struct Response {
+    pub user_ids: Vec<OwnedUserId>,
+}
+
+fn assert_owned<T: Clone + Debug + Send + Sync + 'static>() {}
+
+#[test]
+fn generated_types_are_owned() {
+    assert_owned::<Request>();
+    assert_owned::<Response>();
+}
+
+#[test]
+fn request_can_be_moved_to_another_thread() {
+    let req = Request {
+        room_id: owned_room_id!("!room:localhost"),
+        user_id: owned_user_id!("@user:localhost"),
+        reason: Some("Test".to_owned()),
+    };
+    let cloned = req.clone();
+
+    let http_req = thread::spawn(move || {
+        req.try_into_http_request::<Vec<u8>>(
+            "https://homeserver.tld",
+            SendAccessToken::None,
+            &[MatrixVersion::V1_1],
+        )
+        .unwrap()
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(http_req.uri().path(), "/_matrix/my/endpoint/!room:localhost");
+    assert_eq!(cloned.user_id, "@user:localhost");
+    assert!(format!("{cloned:?}").contains("!room:localhost"));
+}
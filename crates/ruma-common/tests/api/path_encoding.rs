@@ -0,0 +1,113 @@
+use percent_encoding::percent_decode_str;
+use ruma_common::api::{IncomingRequest as _, MatrixVersion, OutgoingRequest, SendAccessToken};
+
+mod get_alias {
+    use ruma_common::{
+        api::{request, response, Metadata},
+        metadata, OwnedRoomAliasId,
+    };
+
+    const METADATA: Metadata = metadata! {
+        method: GET,
+        rate_limited: false,
+        authentication: None,
+        history: {
+            unstable => "/_matrix/some/directory/room/:room_alias",
+        }
+    };
+
+    /// Request type for the `get_alias` endpoint.
+    #[request]
+    pub struct Request {
+        #[ruma_api(path)]
+        pub room_alias: OwnedRoomAliasId,
+    }
+
+    /// Response type for the `get_alias` endpoint.
+    #[response]
+    pub struct Response {}
+}
+
+mod get_media {
+    use ruma_common::{
+        api::{request, response, Metadata},
+        metadata,
+    };
+
+    const METADATA: Metadata = metadata! {
+        method: GET,
+        rate_limited: false,
+        authentication: None,
+        history: {
+            unstable => "/_matrix/some/media/download/:server_name/:media_id",
+        }
+    };
+
+    /// Request type for the `get_media` endpoint.
+    #[request]
+    pub struct Request {
+        #[ruma_api(path)]
+        pub server_name: String,
+
+        #[ruma_api(path)]
+        pub media_id: String,
+    }
+
+    /// Response type for the `get_media` endpoint.
+    #[response]
+    pub struct Response {}
+}
+
+fn uri_path<R: OutgoingRequest>(req: R) -> String {
+    let http_req = req
+        .try_into_http_request::<Vec<u8>>(
+            "https://homeserver.tld",
+            SendAccessToken::None,
+            &[MatrixVersion::V1_1],
+        )
+        .unwrap();
+    http_req.uri().path().to_owned()
+}
+
+/// Get the percent-decoded path arguments after the given prefix, like a server framework would.
+fn path_args(path: &str, prefix: &str) -> Vec<String> {
+    path.strip_prefix(prefix)
+        .unwrap()
+        .split('/')
+        .map(|segment| percent_decode_str(segment).decode_utf8().unwrap().into_owned())
+        .collect()
+}
+
+fn incoming_request(path: &str) -> http::Request<&'static [u8]> {
+    http::Request::builder().uri(format!("https://homeserver.tld{path}")).body(&[][..]).unwrap()
+}
+
+#[test]
+fn room_alias_is_percent_encoded() {
+    let room_alias = ruma_common::owned_room_alias_id!("#room/alias?:example.org");
+    let path = uri_path(get_alias::Request { room_alias: room_alias.clone() });
+    assert_eq!(path, "/_matrix/some/directory/room/%23room%2Falias%3F:example.org");
+
+    let args = path_args(&path, "/_matrix/some/directory/room/");
+    assert_eq!(args, ["#room/alias?:example.org"]);
+
+    let req = get_alias::Request::try_from_http_request(incoming_request(&path), &args).unwrap();
+    assert_eq!(req.room_alias, room_alias);
+}
+
+#[test]
+fn media_id_with_reserved_characters_is_percent_encoded() {
+    let media_id = "a b/c#d?e%2Ff".to_owned();
+    let path = uri_path(get_media::Request {
+        server_name: "example.org".to_owned(),
+        media_id: media_id.clone(),
+    });
+    assert_eq!(path, "/_matrix/some/media/download/example.org/a%20b%2Fc%23d%3Fe%252Ff");
+
+    let args = path_args(&path, "/_matrix/some/media/download/");
+    assert_eq!(args, ["example.org", media_id.as_str()]);
+
+    let req = get_media::Request::try_from_http_request(incoming_request(&path), &args).unwrap();
+    assert_eq!(req.server_name, "example.org");
+    assert_eq!(req.media_id, media_id);
+}
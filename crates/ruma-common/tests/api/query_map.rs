@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+
+use ruma_common::api::{
+    IncomingRequest as _, MatrixVersion, OutgoingRequest as _, SendAccessToken,
+};
+
+mod query_fields_and_map {
+    use std::collections::BTreeMap;
+
+    use ruma_common::{
+        api::{request, response, Metadata},
+        metadata,
+    };
+
+    const METADATA: Metadata = metadata! {
+        method: GET,
+        rate_limited: false,
+        authentication: None,
+        history: {
+            unstable => "/_matrix/some/query/map/endpoint",
+        }
+    };
+
+    /// Request type for the `query_fields_and_map` endpoint.
+    #[request]
+    pub struct Request {
+        #[ruma_api(query)]
+        pub limit: Option<u32>,
+
+        #[ruma_api(query)]
+        #[serde(rename = "dir", alias = "direction")]
+        pub direction: String,
+
+        #[ruma_api(query_map)]
+        pub extra: BTreeMap<String, String>,
+    }
+
+    /// Response type for the `query_fields_and_map` endpoint.
+    #[response]
+    pub struct Response {}
+}
+
+#[test]
+fn query_fields_and_map_round_trip() {
+    use query_fields_and_map::Request;
+
+    let req = Request {
+        limit: Some(10),
+        direction: "b".to_owned(),
+        extra: BTreeMap::from([
+            ("org.example.flag".to_owned(), "true".to_owned()),
+            ("filter".to_owned(), "abc".to_owned()),
+        ]),
+    };
+
+    let http_req = req
+        .try_into_http_request::<Vec<u8>>(
+            "https://homeserver.tld",
+            SendAccessToken::None,
+            &[MatrixVersion::V1_1],
+        )
+        .unwrap();
+    assert_eq!(http_req.uri().query(), Some("limit=10&dir=b&filter=abc&org.example.flag=true"));
+
+    let req = Request::try_from_http_request(http_req, &[] as &[String]).unwrap();
+    assert_eq!(req.limit, Some(10));
+    assert_eq!(req.direction, "b");
+    assert_eq!(req.extra.len(), 2);
+    assert_eq!(req.extra["filter"], "abc");
+    assert_eq!(req.extra["org.example.flag"], "true");
+}
+
+#[test]
+fn query_map_collects_unmatched_keys() {
+    use query_fields_and_map::Request;
+
+    let http_req = http::Request::builder()
+        .method("GET")
+        .uri("https://homeserver.tld/_matrix/some/query/map/endpoint?direction=f&foo=bar")
+        .body(&[] as &[u8])
+        .unwrap();
+
+    let req = Request::try_from_http_request(http_req, &[] as &[String]).unwrap();
+    assert_eq!(req.limit, None);
+    assert_eq!(req.direction, "f");
+    assert_eq!(req.extra, BTreeMap::from([("foo".to_owned(), "bar".to_owned())]));
+}
+
+#[test]
+fn empty_query_map_is_omitted() {
+    use query_fields_and_map::Request;
+
+    let req = Request { limit: None, direction: "b".to_owned(), extra: BTreeMap::new() };
+
+    let http_req = req
+        .try_into_http_request::<Vec<u8>>(
+            "https://homeserver.tld",
+            SendAccessToken::None,
+            &[MatrixVersion::V1_1],
+        )
+        .unwrap();
+    assert_eq!(http_req.uri().query(), Some("dir=b"));
+}
@@ -0,0 +1,68 @@
+#![allow(clippy::exhaustive_structs)]
+
+use http::header::CONTENT_TYPE;
+use ruma_common::{
+    api::{request, response, IncomingResponse as _, Metadata, OutgoingResponse as _},
+    metadata,
+};
+
+const METADATA: Metadata = metadata! {
+    method: GET,
+    rate_limited: false,
+    authentication: None,
+    history: {
+        unstable => "/_matrix/my/media/:media_id",
+    }
+};
+
+/// Request type for the `raw_body` endpoint.
+#[request]
+pub struct Request {
+    #[ruma_api(path)]
+    pub media_id: String,
+}
+
+/// Response type for the `raw_body` endpoint.
+#[response]
+pub struct Response {
+    #[ruma_api(raw_body)]
+    pub file: Vec<u8>,
+
+    #[ruma_api(header = CONTENT_TYPE)]
+    pub content_type: Option<String>,
+}
+
+#[test]
+fn binary_body_round_trip() {
+    let file = vec![0x89, b'P', b'N', b'G', 0x00, 0xff, b'{'];
+    let res = Response { file: file.clone(), content_type: Some("image/png".to_owned()) };
+
+    let http_res = res.try_into_http_response::<Vec<u8>>().unwrap();
+    assert_eq!(http_res.headers().get(CONTENT_TYPE).unwrap(), "image/png");
+    assert_eq!(http_res.body(), &file);
+
+    let res = Response::try_from_http_response(http_res).unwrap();
+    assert_eq!(res.file, file);
+    assert_eq!(res.content_type.as_deref(), Some("image/png"));
+}
+
+#[test]
+fn empty_body_deserializes_to_empty_vec() {
+    let http_res = http::Response::builder()
+        .header(CONTENT_TYPE, "application/octet-stream")
+        .body(Vec::<u8>::new())
+        .unwrap();
+
+    let res = Response::try_from_http_response(http_res).unwrap();
+    assert!(res.file.is_empty());
+    assert_eq!(res.content_type.as_deref(), Some("application/octet-stream"));
+}
+
+#[test]
+fn missing_content_type() {
+    let http_res = http::Response::builder().body(b"data".to_vec()).unwrap();
+
+    let res = Response::try_from_http_response(http_res).unwrap();
+    assert_eq!(res.file, b"data");
+    assert_eq!(res.content_type, None);
+}
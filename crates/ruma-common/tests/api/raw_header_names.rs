@@ -0,0 +1,126 @@
+use assert_matches2::assert_matches;
+use ruma_common::api::{
+    error::{
+        DeserializationError, FromHttpRequestError, FromHttpResponseError,
+        HeaderDeserializationError,
+    },
+    IncomingRequest as _, IncomingResponse as _, MatrixVersion, OutgoingRequest as _,
+    OutgoingResponse as _, SendAccessToken,
+};
+
+mod custom_headers {
+    use ruma_common::{
+        api::{request, response, Metadata},
+        metadata,
+    };
+
+    const METADATA: Metadata = metadata! {
+        method: GET,
+        rate_limited: false,
+        authentication: None,
+        history: {
+            unstable => "/_matrix/my/endpoint",
+        }
+    };
+
+    /// Request type for the `custom_headers` endpoint.
+    #[request]
+    pub struct Request {
+        #[ruma_api(header = "X-Matrix-Request-Id")]
+        pub request_id: String,
+
+        #[ruma_api(header = "X-Matrix-Origin")]
+        pub origin: Option<String>,
+    }
+
+    /// Response type for the `custom_headers` endpoint.
+    #[response]
+    pub struct Response {
+        #[ruma_api(header = "X-Matrix-Server-Version")]
+        pub server_version: String,
+
+        #[ruma_api(header = "X-Matrix-Trace-Id")]
+        pub trace_id: Option<String>,
+    }
+}
+
+#[test]
+fn request_raw_headers_round_trip() {
+    let req =
+        custom_headers::Request { request_id: "foo".to_owned(), origin: Some("bar".to_owned()) };
+
+    let http_req = req
+        .try_into_http_request::<Vec<u8>>(
+            "https://homeserver.tld",
+            SendAccessToken::None,
+            &[MatrixVersion::V1_1],
+        )
+        .unwrap();
+    assert_eq!(http_req.headers().get("x-matrix-request-id").unwrap(), "foo");
+    assert_eq!(http_req.headers().get("X-Matrix-Origin").unwrap(), "bar");
+
+    let req = custom_headers::Request::try_from_http_request(http_req, &[] as &[String]).unwrap();
+    assert_eq!(req.request_id, "foo");
+    assert_eq!(req.origin.as_deref(), Some("bar"));
+}
+
+#[test]
+fn request_optional_raw_header_absent() {
+    let http_req = http::Request::builder()
+        .method("GET")
+        .uri("https://homeserver.tld/_matrix/my/endpoint")
+        .header("X-Matrix-Request-Id", "foo")
+        .body(&[] as &[u8])
+        .unwrap();
+
+    let req = custom_headers::Request::try_from_http_request(http_req, &[] as &[String]).unwrap();
+    assert_eq!(req.request_id, "foo");
+    assert_eq!(req.origin, None);
+}
+
+#[test]
+fn request_required_raw_header_missing() {
+    let http_req = http::Request::builder()
+        .method("GET")
+        .uri("https://homeserver.tld/_matrix/my/endpoint")
+        .header("X-Matrix-Origin", "bar")
+        .body(&[] as &[u8])
+        .unwrap();
+
+    let err =
+        custom_headers::Request::try_from_http_request(http_req, &[] as &[String]).unwrap_err();
+    assert_matches!(
+        err,
+        FromHttpRequestError::Deserialization(DeserializationError::Header(
+            HeaderDeserializationError::MissingHeader(name)
+        ))
+    );
+    assert_eq!(name, "x-matrix-request-id");
+}
+
+#[test]
+fn response_raw_headers_round_trip() {
+    let res = custom_headers::Response { server_version: "baz".to_owned(), trace_id: None };
+
+    let http_res = res.try_into_http_response::<Vec<u8>>().unwrap();
+    assert_eq!(http_res.headers().get("X-Matrix-Server-Version").unwrap(), "baz");
+    assert!(!http_res.headers().contains_key("X-Matrix-Trace-Id"));
+
+    let res = custom_headers::Response::try_from_http_response(http_res).unwrap();
+    assert_eq!(res.server_version, "baz");
+    assert_eq!(res.trace_id, None);
+}
+
+#[test]
+fn response_required_raw_header_missing() {
+    let http_res = http::Response::builder().status(200).body(b"{}" as &[u8]).unwrap();
+
+    let err = custom_headers::Response::try_from_http_response(http_res).unwrap_err();
+    assert_matches!(
+        err,
+        FromHttpResponseError::Deserialization(DeserializationError::Header(
+            HeaderDeserializationError::MissingHeader(name)
+        ))
+    );
+    assert_eq!(name, "x-matrix-server-version");
+}
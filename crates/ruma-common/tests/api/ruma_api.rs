@@ -5,6 +5,22 @@ fn ui() {
     t.pass("tests/api/ui/move-value.rs");
     t.pass("tests/api/ui/request-only.rs");
     t.pass("tests/api/ui/response-only.rs");
+    t.pass("tests/api/ui/deprecated-endpoint.rs");
+    t.compile_fail("tests/api/ui/deprecated-endpoint-use.rs");
+    t.compile_fail("tests/api/ui/deprecated-version-mismatch.rs");
     t.compile_fail("tests/api/ui/deprecated-without-added.rs");
     t.compile_fail("tests/api/ui/removed-without-deprecated.rs");
+    t.compile_fail("tests/api/ui/query-map-with-newtype-body.rs");
+    t.compile_fail("tests/api/ui/multiple-query-maps.rs");
+    t.compile_fail("tests/api/ui/optional-path-field-not-last.rs");
+    t.compile_fail("tests/api/ui/default-on-path-field.rs");
+    t.compile_fail("tests/api/ui/invalid-header-name.rs");
+    t.compile_fail("tests/api/ui/invalid-extra-derives.rs");
+    t.compile_fail("tests/api/ui/invalid-error-status.rs");
+    t.compile_fail("tests/api/ui/metadata-misspelled-key.rs");
+    t.compile_fail("tests/api/ui/metadata-missing-key.rs");
+
+    // The `cbor` feature is enabled for the whole workspace when testing with `__ci`.
+    #[cfg(not(feature = "cbor"))]
+    t.compile_fail("tests/api/ui/cbor-without-feature.rs");
 }
@@ -0,0 +1,25 @@
+use ruma_common::{
+    api::{request, response, Metadata},
+    metadata,
+};
+
+const METADATA: Metadata = metadata! {
+    method: POST,
+    rate_limited: false,
+    authentication: None,
+    history: {
+        unstable => "/_matrix/some/cbor/endpoint",
+    }
+};
+
+#[request(body_format = "cbor")]
+pub struct Request {
+    pub thing: String,
+}
+
+#[response(body_format = "cbor")]
+pub struct Response {
+    pub thing: String,
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use ruma_common::{
+    api::{request, response, Metadata},
+    metadata,
+};
+
+const METADATA: Metadata = metadata! {
+    method: GET,
+    rate_limited: false,
+    authentication: None,
+    history: {
+        unstable => "/_matrix/some/endpoint/:foo",
+    }
+};
+
+#[request]
+pub struct Request {
+    #[ruma_api(path, default)]
+    pub foo: String,
+}
+
+#[response]
+pub struct Response {}
+
+fn main() {}
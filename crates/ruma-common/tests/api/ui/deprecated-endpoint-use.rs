@@ -0,0 +1,30 @@
+#![deny(deprecated)]
+
+use ruma_common::{
+    api::{request, response, Metadata},
+    metadata,
+};
+
+const METADATA: Metadata = metadata! {
+    method: POST,
+    rate_limited: false,
+    authentication: None,
+    history: {
+        1.0 => "/_matrix/some/endpoint",
+        1.3 => deprecated,
+        1.6 => removed,
+    }
+};
+
+#[request(deprecated = 1.3, removed = 1.6, note = "Use the new endpoint instead.")]
+pub struct Request {
+    pub foo: String,
+}
+
+#[response(deprecated = 1.3, removed = 1.6)]
+pub struct Response {}
+
+fn main() {
+    let _req = Request { foo: "foo".to_owned() };
+    let _res = Response {};
+}
@@ -0,0 +1,48 @@
+#![deny(warnings)]
+
+use http::header::CONTENT_TYPE;
+use ruma_common::{
+    api::{request, response, Metadata},
+    metadata,
+};
+
+const METADATA: Metadata = metadata! {
+    method: POST,
+    rate_limited: false,
+    authentication: None,
+    history: {
+        1.0 => "/_matrix/some/endpoint/:foo",
+        1.3 => deprecated,
+        1.6 => removed,
+    }
+};
+
+/// Request type for a deprecated endpoint.
+#[request(deprecated = 1.3, removed = 1.6, note = "Use the new endpoint instead.")]
+pub struct Request {
+    #[ruma_api(path)]
+    pub foo: String,
+
+    #[ruma_api(query)]
+    pub bar: Option<String>,
+
+    #[ruma_api(header = CONTENT_TYPE)]
+    pub content_type: String,
+
+    pub baz: String,
+}
+
+/// Response type for a deprecated endpoint.
+#[response(deprecated = 1.3, removed = 1.6, note = "Use the new endpoint instead.")]
+pub struct Response {
+    #[ruma_api(header = CONTENT_TYPE)]
+    pub content_type: String,
+
+    pub qux: String,
+}
+
+// The generated code doesn't use the deprecated types in a way that triggers a warning.
+fn main() {
+    assert_eq!(METADATA.deprecated_in(), Some(ruma_common::api::MatrixVersion::V1_3));
+    assert_eq!(METADATA.removed_in(), Some(ruma_common::api::MatrixVersion::V1_6));
+}
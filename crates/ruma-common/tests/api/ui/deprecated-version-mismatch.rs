@@ -0,0 +1,22 @@
+use ruma_common::{
+    api::{request, response, Metadata},
+    metadata,
+};
+
+const METADATA: Metadata = metadata! {
+    method: POST,
+    rate_limited: false,
+    authentication: None,
+    history: {
+        1.0 => "/_matrix/some/endpoint",
+        1.3 => deprecated,
+    }
+};
+
+#[request(deprecated = 1.2)]
+pub struct Request {}
+
+#[response(deprecated = 1.3, removed = 1.6)]
+pub struct Response {}
+
+fn main() {}
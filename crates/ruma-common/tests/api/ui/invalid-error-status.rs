@@ -0,0 +1,21 @@
+use ruma_common::{
+    api::{error::MatrixError, request, response, Metadata},
+    metadata,
+};
+
+const METADATA: Metadata = metadata! {
+    method: GET,
+    rate_limited: false,
+    authentication: None,
+    history: {
+        unstable => "/_matrix/some/endpoint",
+    }
+};
+
+#[request]
+pub struct Request {}
+
+#[response(error = MatrixError { 200 => Ok })]
+pub struct Response {}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use ruma_common::{
+    api::{request, response, Metadata},
+    metadata,
+};
+
+const METADATA: Metadata = metadata! {
+    method: GET,
+    rate_limited: false,
+    authentication: None,
+    history: {
+        unstable => "/_matrix/some/endpoint",
+    }
+};
+
+#[request(derive("PartialEq"))]
+pub struct Request {
+    pub foo: String,
+}
+
+#[response]
+pub struct Response {}
+
+fn main() {}
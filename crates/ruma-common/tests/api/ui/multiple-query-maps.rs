@@ -0,0 +1,29 @@
+use std::collections::BTreeMap;
+
+use ruma_common::{
+    api::{request, response, Metadata},
+    metadata,
+};
+
+const METADATA: Metadata = metadata! {
+    method: GET,
+    rate_limited: false,
+    authentication: None,
+    history: {
+        unstable => "/_matrix/some/endpoint",
+    }
+};
+
+#[request]
+pub struct Request {
+    #[ruma_api(query_map)]
+    pub fields: BTreeMap<String, String>,
+
+    #[ruma_api(query_map)]
+    pub more_fields: BTreeMap<String, String>,
+}
+
+#[response]
+pub struct Response {}
+
+fn main() {}
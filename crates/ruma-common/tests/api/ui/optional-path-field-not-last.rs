@@ -0,0 +1,27 @@
+use ruma_common::{
+    api::{request, response, Metadata},
+    metadata,
+};
+
+const METADATA: Metadata = metadata! {
+    method: GET,
+    rate_limited: false,
+    authentication: None,
+    history: {
+        unstable => "/_matrix/some/endpoint/:foo?/:bar",
+    }
+};
+
+#[request]
+pub struct Request {
+    #[ruma_api(path)]
+    pub foo: Option<String>,
+
+    #[ruma_api(path)]
+    pub bar: String,
+}
+
+#[response]
+pub struct Response {}
+
+fn main() {}
@@ -102,10 +102,15 @@ pub struct AudioDetailsContentBlock {
     /// The waveform representation of the audio content, if any.
     ///
     /// This is optional and defaults to an empty array.
+    ///
+    /// To avoid huge allocations with untrusted input, at most 1024 samples are kept when
+    /// deserializing: the samples past the first 1024 are dropped, so such a waveform doesn't
+    /// round-trip.
     #[cfg(feature = "unstable-msc3246")]
     #[serde(
         rename = "org.matrix.msc3246.waveform",
         default,
+        deserialize_with = "crate::waveform_serde::deserialize",
         skip_serializing_if = "Vec::is_empty"
     )]
     pub waveform: Vec<Amplitude>,
@@ -101,6 +101,22 @@ pub enum ShortAuthenticationString {
     _Custom(PrivOwnedStr),
 }
 
+impl ShortAuthenticationString {
+    /// Get the preferred Short Authentication String method supported by both sides of a
+    /// verification.
+    ///
+    /// The emoji method is preferred over the decimal method. Otherwise, a method supported by
+    /// both sides that isn't known by Ruma might be returned, in the order of `local`.
+    ///
+    /// Returns `None` if there is no common method.
+    pub fn negotiate(local: &[Self], remote: &[Self]) -> Option<Self> {
+        [Self::Emoji, Self::Decimal]
+            .into_iter()
+            .find(|method| local.contains(method) && remote.contains(method))
+            .or_else(|| local.iter().find(|method| remote.contains(method)).cloned())
+    }
+}
+
 /// A Short Authentication String (SAS) verification method.
 #[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/doc/string_enum.md"))]
 #[derive(Clone, PartialEq, Eq, StringEnum)]
@@ -126,11 +142,49 @@ pub enum VerificationMethod {
     _Custom(PrivOwnedStr),
 }
 
+impl VerificationMethod {
+    /// Get the preferred verification method supported by both sides of a verification, from the
+    /// `methods` they advertised.
+    ///
+    /// The SAS method is preferred over the QR code methods. A QR code method is only possible if
+    /// one side can show a QR code and the other side can scan it, and both sides support
+    /// `m.reciprocate.v1`. In that case, this returns the QR code method that the local side
+    /// should use. Otherwise, a method supported by both sides that isn't known by Ruma might be
+    /// returned, in the order of `local`.
+    ///
+    /// To pick the Short Authentication String method for SAS, use
+    /// [`ShortAuthenticationString::negotiate()`].
+    ///
+    /// Returns `None` if there is no common method.
+    pub fn negotiate(local: &[Self], remote: &[Self]) -> Option<Self> {
+        if local.contains(&Self::SasV1) && remote.contains(&Self::SasV1) {
+            return Some(Self::SasV1);
+        }
+
+        if local.contains(&Self::ReciprocateV1) && remote.contains(&Self::ReciprocateV1) {
+            if local.contains(&Self::QrCodeShowV1) && remote.contains(&Self::QrCodeScanV1) {
+                return Some(Self::QrCodeShowV1);
+            }
+            if local.contains(&Self::QrCodeScanV1) && remote.contains(&Self::QrCodeShowV1) {
+                return Some(Self::QrCodeScanV1);
+            }
+        }
+
+        local
+            .iter()
+            .find(|method| matches!(method, Self::_Custom(_)) && remote.contains(method))
+            .cloned()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::{from_value as from_json_value, json};
 
-    use super::{KeyAgreementProtocol, MessageAuthenticationCode};
+    use super::{
+        KeyAgreementProtocol, MessageAuthenticationCode, ShortAuthenticationString,
+        VerificationMethod,
+    };
 
     #[test]
     fn serialize_key_agreement() {
@@ -174,4 +228,69 @@ mod tests {
         assert_eq!(serialized, "\"hkdf-hmac-sha256.v2\"");
         assert_eq!(deserialized, MessageAuthenticationCode::HkdfHmacSha256V2);
     }
+
+    #[test]
+    fn negotiate_verification_method() {
+        use VerificationMethod::{QrCodeScanV1, QrCodeShowV1, ReciprocateV1, SasV1};
+
+        let all = [SasV1, QrCodeScanV1, QrCodeShowV1, ReciprocateV1];
+
+        // SAS is preferred.
+        assert_eq!(VerificationMethod::negotiate(&all, &all), Some(SasV1));
+        assert_eq!(VerificationMethod::negotiate(&[SasV1], &all), Some(SasV1));
+
+        // QR codes need one side to show and the other to scan.
+        assert_eq!(
+            VerificationMethod::negotiate(&all, &[QrCodeScanV1, ReciprocateV1]),
+            Some(QrCodeShowV1)
+        );
+        assert_eq!(
+            VerificationMethod::negotiate(&[QrCodeScanV1, ReciprocateV1], &all),
+            Some(QrCodeScanV1)
+        );
+        assert_eq!(
+            VerificationMethod::negotiate(
+                &[QrCodeShowV1, ReciprocateV1],
+                &[QrCodeShowV1, ReciprocateV1]
+            ),
+            None
+        );
+        assert_eq!(VerificationMethod::negotiate(&[QrCodeShowV1], &[QrCodeScanV1]), None);
+
+        // Custom methods.
+        let custom = VerificationMethod::from("org.example.custom");
+        assert_eq!(
+            VerificationMethod::negotiate(
+                &[custom.clone(), QrCodeShowV1],
+                &[SasV1, custom.clone()]
+            ),
+            Some(custom)
+        );
+
+        // No common method.
+        assert_eq!(VerificationMethod::negotiate(&[SasV1], &[QrCodeScanV1, ReciprocateV1]), None);
+        assert_eq!(VerificationMethod::negotiate(&all, &[]), None);
+    }
+
+    #[test]
+    fn negotiate_short_authentication_string() {
+        use ShortAuthenticationString::{Decimal, Emoji};
+
+        assert_eq!(
+            ShortAuthenticationString::negotiate(&[Decimal, Emoji], &[Emoji, Decimal]),
+            Some(Emoji)
+        );
+        assert_eq!(
+            ShortAuthenticationString::negotiate(&[Decimal, Emoji], &[Decimal]),
+            Some(Decimal)
+        );
+
+        let custom = ShortAuthenticationString::from("org.example.colors");
+        assert_eq!(
+            ShortAuthenticationString::negotiate(&[custom.clone()], &[Decimal, custom.clone()]),
+            Some(custom)
+        );
+
+        assert_eq!(ShortAuthenticationString::negotiate(&[Emoji], &[Decimal]), None);
+    }
 }
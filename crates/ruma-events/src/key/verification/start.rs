@@ -108,7 +108,7 @@ pub struct _CustomContent {
     pub data: BTreeMap<String, JsonValue>,
 }
 
-/// The payload of an `m.key.verification.start` event using the `m.sas.v1` method.
+/// The payload of an `m.key.verification.start` event using the `m.reciprocate.v1` method.
 #[derive(Clone, Deserialize, Serialize)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
 #[serde(rename = "m.reciprocate.v1", tag = "method")]
@@ -210,7 +210,7 @@ mod tests {
     use std::collections::BTreeMap;
 
     use assert_matches2::assert_matches;
-    use ruma_common::{event_id, serde::Base64};
+    use ruma_common::{event_id, owned_event_id, serde::Base64};
     use serde_json::{
         from_value as from_json_value, json, to_value as to_json_value, Value as JsonValue,
     };
@@ -488,4 +488,34 @@ mod tests {
         assert_matches!(content.method, StartMethod::ReciprocateV1(reciprocate));
         assert_eq!(reciprocate.secret.encode(), "c2VjcmV0Cg");
     }
+
+    #[test]
+    fn reciprocate_round_trip() {
+        let secret = Base64::new(b"This is a secret to everybody".to_vec());
+        let content = KeyVerificationStartEventContent::new(
+            "123".into(),
+            StartMethod::ReciprocateV1(ReciprocateV1Content::new(secret.clone())),
+            Reference::new(owned_event_id!("$1598361704261elfgc:localhost")),
+        );
+
+        let json = to_json_value(&content).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "from_device": "123",
+                "method": "m.reciprocate.v1",
+                "secret": secret,
+                "m.relates_to": {
+                    "rel_type": "m.reference",
+                    "event_id": "$1598361704261elfgc:localhost",
+                }
+            })
+        );
+
+        let content = from_json_value::<KeyVerificationStartEventContent>(json).unwrap();
+        assert_eq!(content.from_device, "123");
+        assert_eq!(content.relates_to.event_id, "$1598361704261elfgc:localhost");
+        assert_matches!(content.method, StartMethod::ReciprocateV1(reciprocate));
+        assert_eq!(reciprocate.secret, secret);
+    }
 }
@@ -106,7 +106,9 @@
 
 use std::{collections::BTreeSet, fmt};
 
-use ruma_common::{EventEncryptionAlgorithm, OwnedUserId, RoomVersionId};
+#[cfg(feature = "html")]
+use ruma_common::{matrix_uri::MatrixId, MatrixToUri, MatrixUri};
+use ruma_common::{EventEncryptionAlgorithm, OwnedUserId, RoomVersionId, UserId};
 use serde::{de::IgnoredAny, Deserialize, Serialize, Serializer};
 
 // Needs to be public for trybuild tests
@@ -117,6 +119,8 @@ mod enums;
 mod kinds;
 mod state_key;
 mod unsigned;
+#[cfg(any(feature = "unstable-msc3246", feature = "unstable-msc3245-v1-compat"))]
+mod waveform_serde;
 
 // So event macros work inside this crate.
 extern crate self as ruma_events;
@@ -275,12 +279,49 @@ impl Mentions {
         Self { room: true, ..Default::default() }
     }
 
+    /// Add the users mentioned with pills in the given HTML, e.g. the `formatted_body` of a
+    /// message, to this `Mentions`.
+    ///
+    /// Pills are links to the `matrix.to` or `matrix:` URI of a user. Since `user_ids` is a set,
+    /// users that are mentioned several times, or that are already in `user_ids`, are only
+    /// mentioned once.
+    #[cfg(feature = "html")]
+    pub fn with_user_pills(mut self, html: &str) -> Self {
+        self.user_ids.extend(user_pills(html));
+        self
+    }
+
+    /// Remove the given user from the mentioned users.
+    ///
+    /// This should be called with the ID of the sender of the event, to avoid notifying them about
+    /// their own message.
+    pub fn exclude_self(mut self, own_user_id: &UserId) -> Self {
+        self.user_ids.remove(own_user_id);
+        self
+    }
+
     fn add(&mut self, mentions: Self) {
         self.user_ids.extend(mentions.user_ids);
         self.room |= mentions.room;
     }
 }
 
+/// Get the IDs of the users mentioned with pills in the given HTML.
+#[cfg(feature = "html")]
+fn user_pills(html: &str) -> impl Iterator<Item = OwnedUserId> {
+    ruma_html::Html::parse(html).links().into_iter().filter_map(|href| {
+        let id = match MatrixToUri::parse(&href) {
+            Ok(uri) => uri.id().clone(),
+            Err(_) => MatrixUri::parse(&href).ok()?.id().clone(),
+        };
+
+        match id {
+            MatrixId::User(user_id) => Some(user_id),
+            _ => None,
+        }
+    })
+}
+
 // Wrapper around `Box<str>` that cannot be used in a meaningful way outside of
 // this crate. Used for string enums because their `_Custom` variant can't be
 // truly private (only `#[doc(hidden)]`).
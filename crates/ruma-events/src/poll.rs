@@ -82,6 +82,43 @@ pub fn compile_unstable_poll_results<'a>(
     aggregate_results(poll.answers.iter().map(|a| a.id.as_str()), users_selections)
 }
 
+/// An error encountered when building poll content with invalid selections settings or answers.
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum PollSelectionsError {
+    /// The maximum number of selections of a poll is `0`.
+    #[error("max_selections must be at least 1")]
+    ZeroMaxSelections,
+
+    /// A selection doesn't match the ID of any answer of the poll.
+    #[error("unknown poll answer ID: {0}")]
+    UnknownAnswer(String),
+
+    /// There are more selections than the maximum number of selections of the poll.
+    #[error("too many selections")]
+    TooManySelections,
+}
+
+/// Check that the selections of a new response are valid for a poll.
+fn check_selections<'a>(
+    answer_ids: impl Iterator<Item = &'a str>,
+    max_selections: UInt,
+    selections: &[String],
+) -> Result<(), PollSelectionsError> {
+    let answer_ids: BTreeSet<_> = answer_ids.collect();
+
+    if let Some(unknown) = selections.iter().find(|s| !answer_ids.contains(s.as_str())) {
+        return Err(PollSelectionsError::UnknownAnswer(unknown.clone()));
+    }
+
+    let max_selections: usize = max_selections.try_into().unwrap_or(usize::MAX);
+    if selections.len() > max_selections {
+        return Err(PollSelectionsError::TooManySelections);
+    }
+
+    Ok(())
+}
+
 /// Validate the selections of a response.
 fn validate_selections<'a>(
     answer_ids: &BTreeSet<&str>,
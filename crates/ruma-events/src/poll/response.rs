@@ -6,7 +6,10 @@ use ruma_common::OwnedEventId;
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
-use super::{start::PollContentBlock, validate_selections, PollResponseData};
+use super::{
+    check_selections, start::PollContentBlock, validate_selections, PollResponseData,
+    PollSelectionsError,
+};
 use crate::relation::Reference;
 
 /// The payload for a poll response event.
@@ -51,6 +54,24 @@ impl PollResponseEventContent {
             relates_to: Reference::new(poll_start_id),
         }
     }
+
+    /// Creates a new `PollResponseEventContent` that responds to the given poll, after checking
+    /// that the selections are valid for it.
+    ///
+    /// Returns an error if a selection doesn't match the ID of an answer of the poll, or if there
+    /// are more selections than the poll's `max_selections`.
+    pub fn for_poll(
+        poll: &PollContentBlock,
+        selections: SelectionsContentBlock,
+        poll_start_id: OwnedEventId,
+    ) -> Result<Self, PollSelectionsError> {
+        check_selections(
+            poll.answers.iter().map(|a| a.id.as_str()),
+            poll.max_selections,
+            &selections,
+        )?;
+        Ok(Self::new(selections, poll_start_id))
+    }
 }
 
 impl OriginalSyncPollResponseEvent {
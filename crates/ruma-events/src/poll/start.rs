@@ -16,7 +16,7 @@ use poll_answers_serde::PollAnswersDeHelper;
 use super::{
     compile_poll_results,
     end::{PollEndEventContent, PollResultsContentBlock},
-    generate_poll_end_fallback_text, PollResponseData,
+    generate_poll_end_fallback_text, PollResponseData, PollSelectionsError,
 };
 use crate::{message::TextContentBlock, room::message::Relation};
 
@@ -161,6 +161,21 @@ impl PollContentBlock {
         }
     }
 
+    /// Sets the maximum number of responses a user is able to select.
+    ///
+    /// Returns an error if `max_selections` is `0`.
+    pub fn with_max_selections(
+        mut self,
+        max_selections: UInt,
+    ) -> Result<Self, PollSelectionsError> {
+        if max_selections == uint!(0) {
+            return Err(PollSelectionsError::ZeroMaxSelections);
+        }
+
+        self.max_selections = max_selections;
+        Ok(self)
+    }
+
     pub(super) fn default_max_selections() -> UInt {
         uint!(1)
     }
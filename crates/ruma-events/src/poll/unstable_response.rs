@@ -5,7 +5,10 @@ use ruma_common::OwnedEventId;
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
-use super::{unstable_start::UnstablePollStartContentBlock, validate_selections, PollResponseData};
+use super::{
+    check_selections, unstable_start::UnstablePollStartContentBlock, validate_selections,
+    PollResponseData, PollSelectionsError,
+};
 use crate::relation::Reference;
 
 /// The payload for an unstable poll response event.
@@ -40,6 +43,24 @@ impl UnstablePollResponseEventContent {
             relates_to: Reference::new(poll_start_id),
         }
     }
+
+    /// Creates a new `UnstablePollResponseEventContent` that responds to the given poll, after
+    /// checking that the answers are valid for it.
+    ///
+    /// Returns an error if an answer doesn't match the ID of an answer of the poll, or if there
+    /// are more answers than the poll's `max_selections`.
+    pub fn for_poll(
+        poll: &UnstablePollStartContentBlock,
+        answers: Vec<String>,
+        poll_start_id: OwnedEventId,
+    ) -> Result<Self, PollSelectionsError> {
+        check_selections(
+            poll.answers.iter().map(|a| a.id.as_str()),
+            poll.max_selections,
+            &answers,
+        )?;
+        Ok(Self::new(answers, poll_start_id))
+    }
 }
 
 impl OriginalSyncUnstablePollResponseEvent {
@@ -2,7 +2,7 @@
 
 use std::ops::Deref;
 
-use js_int::UInt;
+use js_int::{uint, UInt};
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
@@ -17,7 +17,7 @@ use super::{
     compile_unstable_poll_results, generate_poll_end_fallback_text,
     start::{PollAnswers, PollAnswersError, PollContentBlock, PollKind},
     unstable_end::UnstablePollEndEventContent,
-    PollResponseData,
+    PollResponseData, PollSelectionsError,
 };
 use crate::{
     relation::Replacement, room::message::RelationWithoutReplacement, EventContent,
@@ -298,6 +298,21 @@ impl UnstablePollStartContentBlock {
             answers,
         }
     }
+
+    /// Sets the maximum number of responses a user is able to select.
+    ///
+    /// Returns an error if `max_selections` is `0`.
+    pub fn with_max_selections(
+        mut self,
+        max_selections: UInt,
+    ) -> Result<Self, PollSelectionsError> {
+        if max_selections == uint!(0) {
+            return Err(PollSelectionsError::ZeroMaxSelections);
+        }
+
+        self.max_selections = max_selections;
+        Ok(self)
+    }
 }
 
 /// An unstable poll question.
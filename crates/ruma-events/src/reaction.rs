@@ -2,6 +2,7 @@
 //!
 //! [`m.reaction`]: https://spec.matrix.org/latest/client-server-api/#mreaction
 
+use ruma_common::OwnedEventId;
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
@@ -26,6 +27,12 @@ impl ReactionEventContent {
     pub fn new(relates_to: Annotation) -> Self {
         Self { relates_to }
     }
+
+    /// Creates a new `ReactionEventContent` that annotates the event with the given ID with the
+    /// given key, e.g. an emoji.
+    pub fn annotate(event_id: OwnedEventId, key: impl Into<String>) -> Self {
+        Self::new(Annotation::new(event_id, key.into()))
+    }
 }
 
 impl From<Annotation> for ReactionEventContent {
@@ -79,4 +86,24 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn annotate_round_trip() {
+        let content = ReactionEventContent::annotate(owned_event_id!("$my_reaction"), "👍🏽");
+        let json = to_json_value(&content).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "m.relates_to": {
+                    "rel_type": "m.annotation",
+                    "event_id": "$my_reaction",
+                    "key": "👍🏽",
+                },
+            })
+        );
+
+        let content = from_json_value::<ReactionEventContent>(json).unwrap();
+        assert_eq!(content.relates_to.event_id, "$my_reaction");
+        assert_eq!(content.relates_to.key, "👍🏽");
+    }
 }
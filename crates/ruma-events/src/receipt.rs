@@ -38,6 +38,44 @@ impl ReceiptEventContent {
             Some((event_id.as_ref(), receipt))
         })
     }
+
+    /// Get the effective read receipt of the given user in the main timeline, if any.
+    ///
+    /// This considers both the public [`ReceiptType::Read`] and the private
+    /// [`ReceiptType::ReadPrivate`] receipts that are unthreaded or that apply to the main
+    /// timeline. If there are several receipts of the same type, for example both an unthreaded
+    /// and a [`ReceiptThread::Main`] receipt, the one with the most recent timestamp is used.
+    /// Since private receipts are only sent to their own user, the private receipt takes
+    /// precedence over the public one, unless the public receipt has a more recent timestamp.
+    pub fn user_read_receipt(&self, user_id: &UserId) -> Option<(&EventId, &Receipt)> {
+        let main_timeline_receipt = |receipt_type| {
+            self.iter()
+                .filter_map(|(event_id, receipts)| {
+                    let receipt = receipts.get(&receipt_type)?.get(user_id)?;
+                    matches!(receipt.thread, ReceiptThread::Unthreaded | ReceiptThread::Main)
+                        .then_some((event_id.as_ref(), receipt))
+                })
+                .max_by_key(|(_, receipt)| receipt.ts)
+        };
+
+        match (
+            main_timeline_receipt(ReceiptType::ReadPrivate),
+            main_timeline_receipt(ReceiptType::Read),
+        ) {
+            (Some(private), Some(public)) => {
+                if public
+                    .1
+                    .ts
+                    .is_some_and(|public_ts| private.1.ts.is_some_and(|ts| ts < public_ts))
+                {
+                    Some(public)
+                } else {
+                    Some(private)
+                }
+            }
+            (private, public) => private.or(public),
+        }
+    }
 }
 
 impl Deref for ReceiptEventContent {
@@ -138,6 +176,11 @@ impl Receipt {
     pub fn new(ts: MilliSecondsSinceUnixEpoch) -> Self {
         Self { ts: Some(ts), thread: ReceiptThread::Unthreaded }
     }
+
+    /// Creates a new `Receipt` with the given timestamp that applies to the given thread.
+    pub fn threaded(ts: MilliSecondsSinceUnixEpoch, thread: ReceiptThread) -> Self {
+        Self { ts: Some(ts), thread }
+    }
 }
 
 /// The [thread a receipt applies to].
@@ -212,10 +255,11 @@ where
 #[cfg(test)]
 mod tests {
     use assert_matches2::assert_matches;
-    use ruma_common::{owned_event_id, MilliSecondsSinceUnixEpoch};
+    use js_int::uint;
+    use ruma_common::{owned_event_id, user_id, MilliSecondsSinceUnixEpoch};
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
-    use super::{Receipt, ReceiptThread};
+    use super::{Receipt, ReceiptEventContent, ReceiptThread};
 
     #[test]
     fn serialize_receipt() {
@@ -272,4 +316,89 @@ mod tests {
         assert_matches!(&receipt.thread, ReceiptThread::_Custom(_));
         assert_eq!(receipt.thread.as_str().unwrap(), "io.ruma.unknown");
     }
+
+    #[test]
+    fn threaded_receipt() {
+        let ts = MilliSecondsSinceUnixEpoch(1_664_702_144_365_u64.try_into().unwrap());
+
+        let receipt = Receipt::threaded(ts, ReceiptThread::Thread(owned_event_id!("$thread")));
+        let json = to_json_value(receipt).unwrap();
+        assert_eq!(json, json!({ "ts": 1_664_702_144_365_u64, "thread_id": "$thread" }));
+        let receipt = from_json_value::<Receipt>(json).unwrap();
+        assert_eq!(receipt.ts, Some(ts));
+        assert_matches!(receipt.thread, ReceiptThread::Thread(event_id));
+        assert_eq!(event_id, "$thread");
+
+        let receipt = Receipt::threaded(ts, ReceiptThread::Main);
+        let json = to_json_value(receipt).unwrap();
+        assert_eq!(json, json!({ "ts": 1_664_702_144_365_u64, "thread_id": "main" }));
+        let receipt = from_json_value::<Receipt>(json).unwrap();
+        assert_eq!(receipt.thread, ReceiptThread::Main);
+
+        // An unthreaded receipt doesn't have a `thread_id`.
+        let receipt = Receipt::threaded(ts, ReceiptThread::Unthreaded);
+        let json = to_json_value(receipt).unwrap();
+        assert_eq!(json, json!({ "ts": 1_664_702_144_365_u64 }));
+        let receipt = from_json_value::<Receipt>(json).unwrap();
+        assert_eq!(receipt.thread, ReceiptThread::Unthreaded);
+    }
+
+    #[test]
+    fn user_read_receipt() {
+        let content = from_json_value::<ReceiptEventContent>(json!({
+            "$public": {
+                "m.read": {
+                    "@alice:localhost": { "ts": 1_000 },
+                    "@bob:localhost": { "ts": 3_000 },
+                },
+            },
+            "$private": {
+                "m.read.private": {
+                    "@alice:localhost": { "ts": 2_000 },
+                    "@bob:localhost": { "ts": 2_000 },
+                },
+            },
+            "$thread_private": {
+                "m.read.private": {
+                    "@carl:localhost": { "ts": 2_000, "thread_id": "$thread" },
+                },
+            },
+            "$carl_public": {
+                "m.read": {
+                    "@carl:localhost": { "ts": 1_000 },
+                },
+            },
+            "$dave_main": {
+                "m.read": {
+                    "@dave:localhost": { "ts": 1_000, "thread_id": "main" },
+                },
+            },
+            "$dave_unthreaded": {
+                "m.read": {
+                    "@dave:localhost": { "ts": 2_000 },
+                },
+            },
+        }))
+        .unwrap();
+
+        // The private receipt is more recent.
+        let (event_id, receipt) = content.user_read_receipt(user_id!("@alice:localhost")).unwrap();
+        assert_eq!(event_id, "$private");
+        assert_eq!(receipt.ts, Some(MilliSecondsSinceUnixEpoch(uint!(2_000))));
+
+        // The public receipt is more recent.
+        let (event_id, _) = content.user_read_receipt(user_id!("@bob:localhost")).unwrap();
+        assert_eq!(event_id, "$public");
+
+        // The private receipt is in a thread.
+        let (event_id, _) = content.user_read_receipt(user_id!("@carl:localhost")).unwrap();
+        assert_eq!(event_id, "$carl_public");
+
+        // The unthreaded receipt is more recent than the main timeline one.
+        let (event_id, receipt) = content.user_read_receipt(user_id!("@dave:localhost")).unwrap();
+        assert_eq!(event_id, "$dave_unthreaded");
+        assert_eq!(receipt.thread, ReceiptThread::Unthreaded);
+
+        assert_matches!(content.user_read_receipt(user_id!("@erin:localhost")), None);
+    }
 }
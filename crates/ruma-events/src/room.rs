@@ -104,6 +104,17 @@ pub struct ImageInfo {
     #[cfg(feature = "unstable-msc2448")]
     #[serde(rename = "xyz.amorgan.blurhash", skip_serializing_if = "Option::is_none")]
     pub blurhash: Option<String>,
+
+    /// A map from an algorithm name to a hash of the image file, encoded as unpadded base64.
+    ///
+    /// Like the `hashes` of an [`EncryptedFile`], this allows clients to check the integrity of
+    /// the downloaded media. Clients should support the SHA-256 hash, which uses the key
+    /// `sha256`.
+    ///
+    /// This uses the unstable `io.ruma.hashes` key.
+    #[cfg(feature = "unstable-media-hashes")]
+    #[serde(rename = "io.ruma.hashes", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hashes: BTreeMap<String, Base64>,
 }
 
 impl ImageInfo {
@@ -111,6 +122,14 @@ impl ImageInfo {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Checks the given image file against the SHA-256 hash in `hashes`.
+    ///
+    /// Returns `None` if there is no SHA-256 hash, otherwise whether the hash of `data` matches.
+    #[cfg(feature = "unstable-media-hashes")]
+    pub fn verify_hashes(&self, data: &[u8]) -> Option<bool> {
+        verify_sha256_hash(&self.hashes, data)
+    }
 }
 
 /// Metadata about a thumbnail.
@@ -132,6 +151,13 @@ pub struct ThumbnailInfo {
     /// The file size of the thumbnail in bytes.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub size: Option<UInt>,
+
+    /// Whether the thumbnail is animated, like an animated WebP thumbnail of a GIF.
+    ///
+    /// This uses the unstable `io.ruma.animated` key.
+    #[cfg(feature = "unstable-animated-thumbnail")]
+    #[serde(rename = "io.ruma.animated", skip_serializing_if = "Option::is_none")]
+    pub animated: Option<bool>,
 }
 
 impl ThumbnailInfo {
@@ -139,6 +165,15 @@ impl ThumbnailInfo {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Whether the thumbnail is animated.
+    ///
+    /// Thumbnails are considered static unless `animated` is set to `true`, so renderers that
+    /// prefer static previews can skip the ones for which this returns `true`.
+    #[cfg(feature = "unstable-animated-thumbnail")]
+    pub fn is_animated(&self) -> bool {
+        self.animated.unwrap_or(false)
+    }
 }
 
 /// A file sent to a room with end-to-end encryption enabled.
@@ -195,6 +230,53 @@ pub struct EncryptedFileInit {
     pub v: String,
 }
 
+#[cfg(feature = "crypto")]
+impl EncryptedFile {
+    /// Decrypts the given downloaded data of this file.
+    ///
+    /// The SHA-256 hash of `ciphertext` is checked against the `sha256` entry of the `hashes`
+    /// before decrypting it with AES-256 in CTR mode, using the `key` and `iv`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the version of the protocol is not `v2`, if the `sha256` hash is missing
+    /// or doesn't match, if the key can't be used for decryption, or if the key or the
+    /// initialization vector are invalid.
+    pub fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, DecryptError> {
+        use aes::{
+            cipher::{KeyIvInit, StreamCipher},
+            Aes256,
+        };
+        use sha2::{Digest, Sha256};
+
+        if self.v != "v2" {
+            return Err(DecryptError::UnsupportedVersion);
+        }
+
+        let expected_hash = self.hashes.get("sha256").ok_or(DecryptError::MissingHash)?;
+        if Sha256::digest(ciphertext).as_slice() != expected_hash.as_bytes() {
+            return Err(DecryptError::HashMismatch);
+        }
+
+        if self.key.kty != "oct" || self.key.alg != "A256CTR" {
+            return Err(DecryptError::UnsupportedAlgorithm);
+        }
+
+        if !self.key.key_ops.iter().any(|op| op == "decrypt") {
+            return Err(DecryptError::InvalidKeyOps);
+        }
+
+        let mut cipher =
+            ctr::Ctr128BE::<Aes256>::new_from_slices(self.key.k.as_bytes(), self.iv.as_bytes())
+                .map_err(|_| DecryptError::InvalidKeyOrIv)?;
+
+        let mut plaintext = ciphertext.to_owned();
+        cipher.apply_keystream(&mut plaintext);
+
+        Ok(plaintext)
+    }
+}
+
 impl From<EncryptedFileInit> for EncryptedFile {
     fn from(init: EncryptedFileInit) -> Self {
         let EncryptedFileInit { url, key, iv, hashes, v } = init;
@@ -202,6 +284,36 @@ impl From<EncryptedFileInit> for EncryptedFile {
     }
 }
 
+/// An error encountered when trying to decrypt an [`EncryptedFile`].
+#[cfg(feature = "crypto")]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum DecryptError {
+    /// The version of the encrypted attachments protocol is not supported.
+    #[error("unsupported version of the encrypted attachments protocol, expected `v2`")]
+    UnsupportedVersion,
+
+    /// The `sha256` hash of the ciphertext is missing.
+    #[error("missing sha256 hash of the ciphertext")]
+    MissingHash,
+
+    /// The `sha256` hash of the ciphertext doesn't match the expected value.
+    #[error("sha256 hash of the ciphertext doesn't match")]
+    HashMismatch,
+
+    /// The key type or algorithm is not supported.
+    #[error("unsupported key type or algorithm, expected `oct` and `A256CTR`")]
+    UnsupportedAlgorithm,
+
+    /// The key operations don't include `decrypt`.
+    #[error("the key operations don't include `decrypt`")]
+    InvalidKeyOps,
+
+    /// The key is not 32 bytes long, or the initialization vector is not 16 bytes long.
+    #[error("invalid key or initialization vector length")]
+    InvalidKeyOrIv,
+}
+
 /// A [JSON Web Key](https://tools.ietf.org/html/rfc7517#appendix-A.3) object.
 ///
 /// To create an instance of this type, first create a `JsonWebKeyInit` and convert it via
@@ -273,6 +385,25 @@ impl From<JsonWebKeyInit> for JsonWebKey {
     }
 }
 
+/// Checks `data` against the `sha256` entry of the given map of hashes.
+#[cfg(feature = "unstable-media-hashes")]
+pub(crate) fn verify_sha256_hash(hashes: &BTreeMap<String, Base64>, data: &[u8]) -> Option<bool> {
+    let expected = hashes.get("sha256")?;
+    Some(media_content_hash(data) == *expected)
+}
+
+/// Computes the SHA-256 hash of the given media file.
+///
+/// The hash only depends on the bytes of the file, so it can be used as a key to detect that the
+/// same file was already uploaded. It is also the value of the `sha256` entry of the `hashes` of
+/// the media info.
+#[cfg(feature = "unstable-media-hashes")]
+pub fn media_content_hash(data: &[u8]) -> Base64 {
+    use sha2::{Digest, Sha256};
+
+    Base64::new(Sha256::digest(data).to_vec())
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
@@ -333,4 +464,60 @@ mod tests {
 
         assert_matches!(msg.source, MediaSource::Encrypted(_));
     }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn decrypt_encrypted_file() {
+        use sha2::{Digest, Sha256};
+
+        use super::DecryptError;
+
+        fn hex(s: &str) -> Vec<u8> {
+            (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+        }
+
+        // CTR-AES256 test vector from NIST SP 800-38A, section F.5.5.
+        let key = hex("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4");
+        let iv = hex("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff");
+        let plaintext = hex("6bc1bee22e409f96e93d7e117393172a\
+             ae2d8a571e03ac9c9eb76fac45af8e51\
+             30c81c46a35ce411e5fbc1191a0a52ef\
+             f69f2445df4f9b17ad2b417be66c3710");
+        let ciphertext = hex("601ec313775789a5b7a7f504bbf3d228\
+             f443e3ca4d62b59aca84e990cacaf5c5\
+             2b0930daa23de94ce87017ba2d84988d\
+             dfc9c58db67aada613c2dd08457941a6");
+
+        let mut file = encrypted_file();
+        file.key.k = Base64::new(key);
+        file.iv = Base64::new(iv);
+
+        assert_eq!(file.decrypt(&ciphertext), Err(DecryptError::MissingHash));
+
+        file.hashes.insert("sha256".to_owned(), Base64::new(Sha256::digest(&ciphertext).to_vec()));
+        assert_eq!(file.decrypt(&ciphertext).unwrap(), plaintext);
+
+        // Tampered data.
+        let mut tampered = ciphertext.clone();
+        tampered[0] ^= 1;
+        assert_eq!(file.decrypt(&tampered), Err(DecryptError::HashMismatch));
+
+        // Unsupported version.
+        file.v = "v1".to_owned();
+        assert_eq!(file.decrypt(&ciphertext), Err(DecryptError::UnsupportedVersion));
+        file.v = "v2".to_owned();
+
+        // The key can't be used for decryption.
+        file.key.key_ops = vec!["encrypt".to_owned()];
+        assert_eq!(file.decrypt(&ciphertext), Err(DecryptError::InvalidKeyOps));
+        file.key.key_ops.push("decrypt".to_owned());
+
+        // Invalid initialization vector.
+        file.iv = Base64::new(vec![0; 8]);
+        assert_eq!(file.decrypt(&ciphertext), Err(DecryptError::InvalidKeyOrIv));
+
+        // Unsupported algorithm.
+        file.key.alg = "A128CTR".to_owned();
+        assert_eq!(file.decrypt(&ciphertext), Err(DecryptError::UnsupportedAlgorithm));
+    }
 }
@@ -5,7 +5,9 @@
 use std::{borrow::Cow, collections::BTreeMap};
 
 use js_int::UInt;
-use ruma_common::{serde::JsonObject, EventId, OwnedDeviceId, OwnedEventId};
+use ruma_common::{
+    serde::JsonObject, EventEncryptionAlgorithm, EventId, OwnedDeviceId, OwnedEventId,
+};
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
@@ -78,6 +80,51 @@ pub enum EncryptedEventScheme {
     MegolmV1AesSha2(MegolmV1AesSha2Content),
 }
 
+impl EncryptedEventScheme {
+    /// The algorithm of this scheme.
+    pub fn algorithm(&self) -> EventEncryptionAlgorithm {
+        match self {
+            Self::OlmV1Curve25519AesSha2(_) => EventEncryptionAlgorithm::OlmV1Curve25519AesSha2,
+            Self::MegolmV1AesSha2(_) => EventEncryptionAlgorithm::MegolmV1AesSha2,
+        }
+    }
+}
+
+/// A placeholder for the content of an `m.room.encrypted` event that could not be decrypted.
+///
+/// It keeps the metadata of the encrypted event that allows to retry the decryption later, for
+/// example when the keys of the session are received.
+///
+/// This type is never sent over the wire, it is meant to be displayed instead of the decrypted
+/// content. To create an instance of this type, convert it from a `RoomEncryptedEventContent` via
+/// `UnableToDecryptContent::from` / `.into()`.
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct UnableToDecryptContent {
+    /// The algorithm used to encrypt the event.
+    pub algorithm: EventEncryptionAlgorithm,
+
+    /// The ID of the session used to encrypt the event, if it was encrypted with
+    /// `m.megolm.v1.aes-sha2`.
+    pub session_id: Option<String>,
+
+    /// Information about related events.
+    pub relates_to: Option<Relation>,
+}
+
+impl From<RoomEncryptedEventContent> for UnableToDecryptContent {
+    fn from(content: RoomEncryptedEventContent) -> Self {
+        let RoomEncryptedEventContent { scheme, relates_to } = content;
+        let algorithm = scheme.algorithm();
+        let session_id = match scheme {
+            EncryptedEventScheme::MegolmV1AesSha2(c) => Some(c.session_id),
+            EncryptedEventScheme::OlmV1Curve25519AesSha2(_) => None,
+        };
+
+        Self { algorithm, session_id, relates_to }
+    }
+}
+
 /// Relationship information about an encrypted event.
 ///
 /// Outside of the encrypted payload to support server aggregation.
@@ -286,12 +333,12 @@ impl From<MegolmV1AesSha2ContentInit> for MegolmV1AesSha2Content {
 mod tests {
     use assert_matches2::assert_matches;
     use js_int::uint;
-    use ruma_common::{owned_event_id, serde::Raw};
+    use ruma_common::{owned_event_id, serde::Raw, EventEncryptionAlgorithm};
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
     use super::{
         EncryptedEventScheme, InReplyTo, MegolmV1AesSha2ContentInit, Relation,
-        RoomEncryptedEventContent,
+        RoomEncryptedEventContent, UnableToDecryptContent,
     };
 
     #[test]
@@ -387,4 +434,44 @@ mod tests {
         .deserialize()
         .unwrap_err();
     }
+
+    #[test]
+    fn unable_to_decrypt() {
+        let json_data = json!({
+            "algorithm": "m.megolm.v1.aes-sha2",
+            "ciphertext": "ciphertext",
+            "sender_key": "sender_key",
+            "device_id": "device_id",
+            "session_id": "session_id",
+            "m.relates_to": {
+                "m.in_reply_to": {
+                    "event_id": "$h29iv0s8:example.com"
+                }
+            },
+        });
+        let content = from_json_value::<RoomEncryptedEventContent>(json_data).unwrap();
+
+        let utd = UnableToDecryptContent::from(content);
+        assert_eq!(utd.algorithm, EventEncryptionAlgorithm::MegolmV1AesSha2);
+        assert_eq!(utd.session_id.as_deref(), Some("session_id"));
+        assert_matches!(utd.relates_to, Some(Relation::Reply { in_reply_to }));
+        assert_eq!(in_reply_to.event_id, "$h29iv0s8:example.com");
+
+        let json_data = json!({
+            "algorithm": "m.olm.v1.curve25519-aes-sha2",
+            "ciphertext": {
+                "SenderKey": {
+                    "body": "encrypted_message",
+                    "type": 0
+                }
+            },
+            "sender_key": "sender_key",
+        });
+        let content = from_json_value::<RoomEncryptedEventContent>(json_data).unwrap();
+
+        let utd = UnableToDecryptContent::from(content);
+        assert_eq!(utd.algorithm, EventEncryptionAlgorithm::OlmV1Curve25519AesSha2);
+        assert_eq!(utd.session_id, None);
+        assert_matches!(utd.relates_to, None);
+    }
 }
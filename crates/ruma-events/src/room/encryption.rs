@@ -53,3 +53,43 @@ impl RoomEncryptionEventContent {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use js_int::uint;
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::RoomEncryptionEventContent;
+    use crate::EventEncryptionAlgorithm;
+
+    #[test]
+    fn megolm_round_trip() {
+        let json = json!({
+            "algorithm": "m.megolm.v1.aes-sha2",
+            "rotation_period_ms": 604_800_000,
+            "rotation_period_msgs": 100,
+        });
+
+        let content = from_json_value::<RoomEncryptionEventContent>(json.clone()).unwrap();
+        assert_eq!(content.algorithm, EventEncryptionAlgorithm::MegolmV1AesSha2);
+        assert_eq!(content.rotation_period_ms, Some(uint!(604_800_000)));
+        assert_eq!(content.rotation_period_msgs, Some(uint!(100)));
+
+        assert_eq!(to_json_value(&content).unwrap(), json);
+        assert_eq!(
+            to_json_value(RoomEncryptionEventContent::with_recommended_defaults()).unwrap(),
+            json
+        );
+    }
+
+    #[test]
+    fn custom_algorithm_round_trip() {
+        let json = json!({ "algorithm": "org.example.megolm.v2" });
+
+        let content = from_json_value::<RoomEncryptionEventContent>(json.clone()).unwrap();
+        assert_eq!(content.algorithm.as_str(), "org.example.megolm.v2");
+        assert_eq!(content.algorithm, EventEncryptionAlgorithm::from("org.example.megolm.v2"));
+
+        assert_eq!(to_json_value(content).unwrap(), json);
+    }
+}
@@ -44,6 +44,55 @@ impl RoomJoinRulesEventContent {
     pub fn knock_restricted(allow: Vec<AllowRule>) -> Self {
         Self { join_rule: JoinRule::KnockRestricted(Restricted::new(allow)) }
     }
+
+    /// Checks whether a user that is not invited can join the room with these join rules.
+    ///
+    /// `user_memberships` is the list of rooms that the user is a member of, used to check the
+    /// allow rules of restricted rooms. Unknown allow rules are ignored.
+    ///
+    /// This only makes a pre-check on the client side, the server still decides whether the join
+    /// succeeds. Bans are not taken into account.
+    pub fn can_join(&self, user_memberships: &[OwnedRoomId]) -> JoinEligibility {
+        match &self.join_rule {
+            JoinRule::Public => JoinEligibility::Allowed,
+            JoinRule::Restricted(restricted) | JoinRule::KnockRestricted(restricted) => {
+                let is_allowed = restricted.allow.iter().any(|rule| match rule {
+                    AllowRule::RoomMembership(membership) => {
+                        user_memberships.contains(&membership.room_id)
+                    }
+                    AllowRule::_Custom(_) => false,
+                });
+
+                if is_allowed {
+                    JoinEligibility::Allowed
+                } else {
+                    JoinEligibility::RestrictedRequirementUnmet
+                }
+            }
+            JoinRule::Invite | JoinRule::Knock | JoinRule::Private | JoinRule::_Custom(_) => {
+                JoinEligibility::NeedsInvite
+            }
+        }
+    }
+}
+
+/// Whether a user can join a room, as returned by [`RoomJoinRulesEventContent::can_join()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum JoinEligibility {
+    /// The user can join the room.
+    Allowed,
+
+    /// The user needs to be invited to join the room.
+    ///
+    /// If the join rule is [`JoinRule::Knock`], the user can request an invite.
+    NeedsInvite,
+
+    /// The user is not a member of any of the rooms that allow joining this restricted room.
+    ///
+    /// The user can still join the room if they are invited. If the join rule is
+    /// [`JoinRule::KnockRestricted`], the user can request an invite.
+    RestrictedRequirementUnmet,
 }
 
 impl<'de> Deserialize<'de> for RoomJoinRulesEventContent {
@@ -249,7 +298,79 @@ mod tests {
     use assert_matches2::assert_matches;
     use ruma_common::owned_room_id;
 
-    use super::{AllowRule, JoinRule, OriginalSyncRoomJoinRulesEvent, RoomJoinRulesEventContent};
+    use super::{
+        AllowRule, JoinEligibility, JoinRule, OriginalSyncRoomJoinRulesEvent,
+        RoomJoinRulesEventContent,
+    };
+
+    #[test]
+    fn can_join_public() {
+        let content = RoomJoinRulesEventContent::new(JoinRule::Public);
+        assert_eq!(content.can_join(&[]), JoinEligibility::Allowed);
+        assert_eq!(
+            content.can_join(&[owned_room_id!("!space:example.org")]),
+            JoinEligibility::Allowed
+        );
+    }
+
+    #[test]
+    fn can_join_invite() {
+        let memberships = [owned_room_id!("!space:example.org")];
+
+        let content = RoomJoinRulesEventContent::new(JoinRule::Invite);
+        assert_eq!(content.can_join(&memberships), JoinEligibility::NeedsInvite);
+
+        let content = RoomJoinRulesEventContent::new(JoinRule::Knock);
+        assert_eq!(content.can_join(&memberships), JoinEligibility::NeedsInvite);
+
+        let content = serde_json::from_str::<RoomJoinRulesEventContent>(
+            r#"{"join_rule": "org.example.custom"}"#,
+        )
+        .unwrap();
+        assert_eq!(content.can_join(&memberships), JoinEligibility::NeedsInvite);
+    }
+
+    #[test]
+    fn can_join_restricted() {
+        let content = serde_json::from_str::<RoomJoinRulesEventContent>(
+            r#"{
+                "join_rule": "restricted",
+                "allow": [
+                    { "type": "org.example.custom", "room_id": "!other:example.org" },
+                    { "type": "m.room_membership", "room_id": "!space:example.org" }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            content.can_join(&[
+                owned_room_id!("!lobby:example.org"),
+                owned_room_id!("!space:example.org")
+            ]),
+            JoinEligibility::Allowed
+        );
+        assert_eq!(
+            content.can_join(&[owned_room_id!("!lobby:example.org")]),
+            JoinEligibility::RestrictedRequirementUnmet
+        );
+        // Custom allow rules are ignored.
+        assert_eq!(
+            content.can_join(&[owned_room_id!("!other:example.org")]),
+            JoinEligibility::RestrictedRequirementUnmet
+        );
+        assert_eq!(content.can_join(&[]), JoinEligibility::RestrictedRequirementUnmet);
+
+        let content =
+            RoomJoinRulesEventContent::knock_restricted(vec![AllowRule::room_membership(
+                owned_room_id!("!space:example.org"),
+            )]);
+        assert_eq!(
+            content.can_join(&[owned_room_id!("!space:example.org")]),
+            JoinEligibility::Allowed
+        );
+        assert_eq!(content.can_join(&[]), JoinEligibility::RestrictedRequirementUnmet);
+    }
 
     #[test]
     fn deserialize() {
@@ -13,6 +13,7 @@ use ruma_common::{
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
+use super::power_levels::RoomPowerLevels;
 use crate::{
     AnyStrippedStateEvent, BundledStateRelations, EventContent, PossiblyRedactedStateEventContent,
     PrivOwnedStr, RedactContent, RedactedStateEventContent, StateEventType,
@@ -306,6 +307,58 @@ pub enum MembershipState {
     _Custom(PrivOwnedStr),
 }
 
+impl MembershipState {
+    /// Whether the transition from the `prev` membership state of the user with the given
+    /// `state_key` to the `next` one is allowed when it is made by `sender`.
+    ///
+    /// `prev` should be `None` if the user has never been in the room before.
+    ///
+    /// This checks the membership transitions of the [authorization rules] that only depend on
+    /// the power levels. It assumes that `sender` is joined to the room when it is not the target
+    /// of the change, and it doesn't check the join rules of the room, or third-party invites.
+    ///
+    /// [authorization rules]: https://spec.matrix.org/latest/rooms/v10/#authorization-rules
+    pub fn is_valid_transition(
+        prev: Option<&MembershipState>,
+        next: &MembershipState,
+        sender: &UserId,
+        state_key: &UserId,
+        power_levels: &RoomPowerLevels,
+    ) -> bool {
+        let is_self = sender == state_key;
+        let sender_outranks_target =
+            power_levels.for_user(state_key) < power_levels.for_user(sender);
+
+        match next {
+            MembershipState::Join => is_self && prev != Some(&MembershipState::Ban),
+            MembershipState::Invite => {
+                !matches!(prev, Some(MembershipState::Ban | MembershipState::Join))
+                    && power_levels.user_can_invite(sender)
+            }
+            MembershipState::Leave if is_self => matches!(
+                prev,
+                Some(MembershipState::Invite | MembershipState::Join | MembershipState::Knock)
+            ),
+            MembershipState::Leave => {
+                (prev != Some(&MembershipState::Ban) || power_levels.user_can_ban(sender))
+                    && power_levels.user_can_kick(sender)
+                    && sender_outranks_target
+            }
+            MembershipState::Ban => power_levels.user_can_ban(sender) && sender_outranks_target,
+            MembershipState::Knock => {
+                is_self
+                    && !matches!(
+                        prev,
+                        Some(
+                            MembershipState::Ban | MembershipState::Invite | MembershipState::Join
+                        )
+                    )
+            }
+            MembershipState::_Custom(_) => false,
+        }
+    }
+}
+
 /// Information about a third party invitation.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
@@ -578,7 +631,7 @@ impl CanBeEmpty for RoomMemberUnsigned {
 #[cfg(test)]
 mod tests {
     use assert_matches2::assert_matches;
-    use js_int::uint;
+    use js_int::{int, uint};
     use maplit::btreemap;
     use ruma_common::{
         mxc_uri, owned_server_signing_key_id, serde::CanBeEmpty, server_name, user_id,
@@ -587,7 +640,10 @@ mod tests {
     use serde_json::{from_value as from_json_value, json};
 
     use super::{MembershipState, RoomMemberEventContent};
-    use crate::OriginalStateEvent;
+    use crate::{
+        room::power_levels::{RoomPowerLevels, RoomPowerLevelsEventContent},
+        OriginalStateEvent,
+    };
 
     #[test]
     fn serde_with_no_prev_content() {
@@ -820,4 +876,132 @@ mod tests {
             Some(user_id!("@notcarl:example.com"))
         );
     }
+
+    fn power_levels() -> RoomPowerLevels {
+        let mut content = RoomPowerLevelsEventContent::new();
+        content.users.insert(user_id!("@admin:example.com").to_owned(), int!(100));
+        content.users.insert(user_id!("@mod:example.com").to_owned(), int!(50));
+        content.into()
+    }
+
+    #[test]
+    fn membership_transition_invite_to_join() {
+        let user = user_id!("@carl:example.com");
+        let power_levels = power_levels();
+
+        assert!(MembershipState::is_valid_transition(
+            Some(&MembershipState::Invite),
+            &MembershipState::Join,
+            user,
+            user,
+            &power_levels,
+        ));
+        // Only the user can join.
+        assert!(!MembershipState::is_valid_transition(
+            Some(&MembershipState::Invite),
+            &MembershipState::Join,
+            user_id!("@admin:example.com"),
+            user,
+            &power_levels,
+        ));
+    }
+
+    #[test]
+    fn membership_transition_self_leave() {
+        let user = user_id!("@carl:example.com");
+        let power_levels = power_levels();
+
+        assert!(MembershipState::is_valid_transition(
+            Some(&MembershipState::Join),
+            &MembershipState::Leave,
+            user,
+            user,
+            &power_levels,
+        ));
+        // The user must be in the room to leave it.
+        assert!(!MembershipState::is_valid_transition(
+            None,
+            &MembershipState::Leave,
+            user,
+            user,
+            &power_levels,
+        ));
+        assert!(!MembershipState::is_valid_transition(
+            Some(&MembershipState::Ban),
+            &MembershipState::Leave,
+            user,
+            user,
+            &power_levels,
+        ));
+    }
+
+    #[test]
+    fn membership_transition_ban_to_join() {
+        let user = user_id!("@carl:example.com");
+        let power_levels = power_levels();
+
+        assert!(!MembershipState::is_valid_transition(
+            Some(&MembershipState::Ban),
+            &MembershipState::Join,
+            user,
+            user,
+            &power_levels,
+        ));
+    }
+
+    #[test]
+    fn membership_transition_kick_ban_unban() {
+        let admin = user_id!("@admin:example.com");
+        let moderator = user_id!("@mod:example.com");
+        let user = user_id!("@carl:example.com");
+        let power_levels = power_levels();
+
+        // Kick.
+        assert!(MembershipState::is_valid_transition(
+            Some(&MembershipState::Join),
+            &MembershipState::Leave,
+            moderator,
+            user,
+            &power_levels,
+        ));
+        assert!(!MembershipState::is_valid_transition(
+            Some(&MembershipState::Join),
+            &MembershipState::Leave,
+            user,
+            moderator,
+            &power_levels,
+        ));
+
+        // Ban.
+        assert!(MembershipState::is_valid_transition(
+            Some(&MembershipState::Join),
+            &MembershipState::Ban,
+            admin,
+            moderator,
+            &power_levels,
+        ));
+        assert!(!MembershipState::is_valid_transition(
+            Some(&MembershipState::Join),
+            &MembershipState::Ban,
+            moderator,
+            admin,
+            &power_levels,
+        ));
+
+        // Unban.
+        assert!(MembershipState::is_valid_transition(
+            Some(&MembershipState::Ban),
+            &MembershipState::Leave,
+            moderator,
+            user,
+            &power_levels,
+        ));
+        assert!(!MembershipState::is_valid_transition(
+            Some(&MembershipState::Ban),
+            &MembershipState::Leave,
+            user,
+            user,
+            &power_levels,
+        ));
+    }
 }
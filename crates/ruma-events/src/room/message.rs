@@ -6,10 +6,10 @@ use std::borrow::Cow;
 
 use ruma_common::{
     serde::{JsonObject, Raw, StringEnum},
-    OwnedEventId, RoomId,
+    EventId, OwnedEventId, RoomId,
 };
 #[cfg(feature = "html")]
-use ruma_html::{sanitize_html, HtmlSanitizerMode, RemoveReplyFallback};
+use ruma_html::{sanitize_html, Html, HtmlSanitizerMode, RemoveReplyFallback};
 use ruma_macros::EventContent;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value as JsonValue;
@@ -20,7 +20,8 @@ use self::reply::OriginalEventData;
 use self::sanitize::remove_plain_reply_fallback;
 use crate::{
     relation::{InReplyTo, Replacement, Thread},
-    AnySyncTimelineEvent, Mentions, PrivOwnedStr,
+    tag::{TagName, Tags},
+    AnyMessageLikeEventContent, AnySyncTimelineEvent, Mentions, PrivOwnedStr,
 };
 
 mod audio;
@@ -30,25 +31,38 @@ mod file;
 mod image;
 mod key_verification_request;
 mod location;
+mod media;
 mod notice;
 mod relation;
 pub(crate) mod relation_serde;
 mod reply;
 pub mod sanitize;
 mod server_notice;
+mod split;
 mod text;
 mod video;
 mod without_relation;
 
+#[cfg(feature = "unstable-audio-chapters")]
+pub use self::audio::AudioChapter;
 #[cfg(feature = "unstable-msc3245-v1-compat")]
 pub use self::audio::{UnstableAudioDetailsContentBlock, UnstableVoiceContentBlock};
+#[cfg(feature = "unstable-msc1767")]
+pub use self::file::UnstableFileContentBlock;
+#[cfg(feature = "unstable-media-tracks")]
+pub use self::media::{MediaTrack, MediaTrackKind};
+#[cfg(feature = "unstable-bridge-attribution")]
+pub use self::notice::BridgeAttribution;
+#[cfg(feature = "unstable-streaming-manifest")]
+pub use self::video::{StreamingManifest, StreamingManifestFormat};
 pub use self::{
     audio::{AudioInfo, AudioMessageEventContent},
     emote::EmoteMessageEventContent,
     file::{FileInfo, FileMessageEventContent},
     image::ImageMessageEventContent,
     key_verification_request::KeyVerificationRequestEventContent,
-    location::{LocationInfo, LocationMessageEventContent},
+    location::{GeoUriError, LocationInfo, LocationMessageEventContent},
+    media::MediaMessage,
     notice::NoticeMessageEventContent,
     relation::{Relation, RelationWithoutReplacement},
     relation_serde::deserialize_relation,
@@ -144,6 +158,13 @@ impl RoomMessageEventContent {
         Self::new(MessageType::emote_markdown(body))
     }
 
+    /// A constructor to create a plain text message or emote from the given user input.
+    ///
+    /// See [`MessageType::from_slash_command()`] for details.
+    pub fn from_slash_command(input: &str) -> Self {
+        Self::new(MessageType::from_slash_command(input))
+    }
+
     /// Turns `self` into a reply to the given message.
     ///
     /// Takes the `body` / `formatted_body` (if any) in `self` for the main text and prepends a
@@ -242,6 +263,17 @@ impl RoomMessageEventContent {
         self
     }
 
+    /// Turns `self` into a new message in the thread with the given root event ID.
+    ///
+    /// Unlike [`make_for_thread()`](Self::make_for_thread), this doesn't need the previous message
+    /// of the thread. The message is not a reply within the thread: `latest_event_id` should be the
+    /// ID of the latest message-like event in the thread, and is used as the reply fallback for
+    /// clients that don't support threads.
+    pub fn in_thread(mut self, thread_root: OwnedEventId, latest_event_id: OwnedEventId) -> Self {
+        self.relates_to = Some(Relation::Thread(Thread::plain(thread_root, latest_event_id)));
+        self
+    }
+
     /// Turns `self` into a [replacement] (or edit) for a given message.
     ///
     /// The first argument after `self` can be `&OriginalRoomMessageEvent` or
@@ -295,6 +327,91 @@ impl RoomMessageEventContent {
         self
     }
 
+    /// Turns `self` into a [replacement] (or edit) for a given message, if they have the same
+    /// `msgtype`.
+    ///
+    /// This is the same as [`make_replacement()`](Self::make_replacement), but returns an error if
+    /// `self` doesn't have the same `msgtype` as the message that is replaced, because clients
+    /// might not be able to render such an edit. Use `make_replacement()` to explicitly allow
+    /// changing the `msgtype`.
+    ///
+    /// The check is skipped if the `msgtype` of the message that is replaced is unknown, i.e. if
+    /// the [`ReplacementMetadata`] was created with [`ReplacementMetadata::new()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` has a `formatted_body` with a format other than HTML.
+    ///
+    /// [replacement]: https://spec.matrix.org/latest/client-server-api/#event-replacements
+    #[track_caller]
+    pub fn try_make_replacement(
+        self,
+        metadata: impl Into<ReplacementMetadata>,
+        replied_to_message: Option<&OriginalRoomMessageEvent>,
+    ) -> Result<Self, MsgtypeMismatchError> {
+        let metadata = metadata.into();
+
+        if let Some(original) = &metadata.msgtype {
+            if original != self.msgtype() {
+                return Err(MsgtypeMismatchError {
+                    original: original.clone(),
+                    replacement: self.msgtype().to_owned(),
+                });
+            }
+        }
+
+        Ok(self.make_replacement(metadata, replied_to_message))
+    }
+
+    /// Splits `self` into several messages whose `body` is at most `max_bytes` long, if it is an
+    /// `m.text` or `m.notice` message that is too long.
+    ///
+    /// The body is split between lines if possible, then between words, and finally between
+    /// characters for words that are too long. If a message ends in the middle of a fenced code
+    /// block, the code block is closed at the end of the message and reopened at the start of the
+    /// next one. The info string of the code block is not repeated if it leaves no room for the
+    /// code, and the code block is not reopened at all if even its fence is too long.
+    ///
+    /// The messages only have a plain text body: the formatted body of `self`, if any, is dropped
+    /// because HTML can't be split safely. The mentions and the relation of `self` are only kept
+    /// in the first message, except for a thread relation which is kept in all messages.
+    ///
+    /// If `self` is another message type, or if neither its `body` nor its formatted body are
+    /// longer than `max_bytes`, a clone of `self` is returned.
+    pub fn split_for_event_limit(&self, max_bytes: usize) -> Vec<RoomMessageEventContent> {
+        let (body, formatted, make_msgtype): (_, _, fn(String) -> MessageType) = match &self.msgtype
+        {
+            MessageType::Text(content) => (&content.body, &content.formatted, |body| {
+                MessageType::Text(TextMessageEventContent::plain(body))
+            }),
+            MessageType::Notice(content) => (&content.body, &content.formatted, |body| {
+                MessageType::Notice(NoticeMessageEventContent::plain(body))
+            }),
+            _ => return vec![self.clone()],
+        };
+
+        let formatted_len = formatted.as_ref().map_or(0, |formatted| formatted.body.len());
+        if body.len() <= max_bytes && formatted_len <= max_bytes {
+            return vec![self.clone()];
+        }
+
+        split::split_body(body, max_bytes)
+            .into_iter()
+            .enumerate()
+            .map(|(i, body)| {
+                let is_first = i == 0;
+                let relates_to = self
+                    .relates_to
+                    .as_ref()
+                    .filter(|relation| is_first || matches!(relation, Relation::Thread(_)))
+                    .cloned();
+                let mentions = self.mentions.clone().filter(|_| is_first);
+
+                Self { msgtype: make_msgtype(body), relates_to, mentions }
+            })
+            .collect()
+    }
+
     /// Set the [mentions] of this event.
     ///
     /// If this event is a replacement, it will update the mentions both in the `content` and the
@@ -462,6 +579,17 @@ pub enum ReplyWithinThread {
     No,
 }
 
+/// The base direction of a text, used for bidirectional rendering.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(clippy::exhaustive_enums)]
+pub enum Direction {
+    /// The text is left-to-right, like English.
+    Ltr,
+
+    /// The text is right-to-left, like Arabic or Hebrew.
+    Rtl,
+}
+
 /// The content that is specific to each message type variant.
 #[derive(Clone, Debug, Serialize)]
 #[serde(untagged)]
@@ -591,6 +719,22 @@ impl MessageType {
         Self::Emote(EmoteMessageEventContent::markdown(body))
     }
 
+    /// A constructor to create a plain text message or emote from the given user input.
+    ///
+    /// If the input starts with the `/me ` command, this creates a plain text emote with the rest
+    /// of the input. A leading `//` escapes the command, so the input minus its first slash is
+    /// used as a plain text message. Any other input, including `/me` without text, is used as is
+    /// for a plain text message.
+    pub fn from_slash_command(input: &str) -> Self {
+        if input.starts_with("//") {
+            Self::text_plain(&input[1..])
+        } else if let Some(emote) = input.strip_prefix("/me ").filter(|e| !e.trim().is_empty()) {
+            Self::emote_plain(emote)
+        } else {
+            Self::text_plain(input)
+        }
+    }
+
     /// Returns a reference to the `msgtype` string.
     pub fn msgtype(&self) -> &str {
         match self {
@@ -625,6 +769,17 @@ impl MessageType {
         }
     }
 
+    /// Returns the media file of this message, if it is a media message.
+    pub fn as_media(&self) -> Option<&dyn MediaMessage> {
+        match self {
+            Self::Audio(m) => Some(m),
+            Self::File(m) => Some(m),
+            Self::Image(m) => Some(m),
+            Self::Video(m) => Some(m),
+            _ => None,
+        }
+    }
+
     /// Returns the associated data.
     ///
     /// The returned JSON object won't contain the `msgtype` and `body` fields, use
@@ -785,6 +940,56 @@ impl From<RoomMessageEventContent> for MessageType {
     }
 }
 
+macro_rules! impl_from_msgtype_content {
+    ($($content:ident => $variant:ident,)*) => {
+        $(
+            impl From<$content> for MessageType {
+                fn from(content: $content) -> Self {
+                    Self::$variant(content)
+                }
+            }
+
+            impl From<$content> for RoomMessageEventContent {
+                fn from(content: $content) -> Self {
+                    Self::new(MessageType::$variant(content))
+                }
+            }
+
+            impl From<$content> for AnyMessageLikeEventContent {
+                fn from(content: $content) -> Self {
+                    Self::RoomMessage(content.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_msgtype_content! {
+    AudioMessageEventContent => Audio,
+    EmoteMessageEventContent => Emote,
+    FileMessageEventContent => File,
+    ImageMessageEventContent => Image,
+    LocationMessageEventContent => Location,
+    NoticeMessageEventContent => Notice,
+    ServerNoticeMessageEventContent => ServerNotice,
+    TextMessageEventContent => Text,
+    VideoMessageEventContent => Video,
+    KeyVerificationRequestEventContent => VerificationRequest,
+}
+
+/// Whether the given message is a notice sent by the system, e.g. by the homeserver, rather than
+/// by a user.
+///
+/// Clients can use this to exempt such messages from client-side rate limiting.
+///
+/// `room_tags` are the tags of the room the message was sent in, if they are known. All the
+/// messages in a room with the [`TagName::ServerNotice`] tag are sent by the homeserver.
+/// Otherwise, only messages with the `m.server_notice` msgtype are considered as system notices.
+pub fn is_system_notice(content: &RoomMessageEventContent, room_tags: Option<&Tags>) -> bool {
+    matches!(content.msgtype, MessageType::ServerNotice(_))
+        || room_tags.is_some_and(|tags| tags.contains_key(&TagName::ServerNotice))
+}
+
 /// Metadata about an event to be replaced.
 ///
 /// To be used with [`RoomMessageEventContent::make_replacement`].
@@ -792,27 +997,50 @@ impl From<RoomMessageEventContent> for MessageType {
 pub struct ReplacementMetadata {
     event_id: OwnedEventId,
     mentions: Option<Mentions>,
+    msgtype: Option<String>,
 }
 
 impl ReplacementMetadata {
     /// Creates a new `ReplacementMetadata` with the given event ID and mentions.
     pub fn new(event_id: OwnedEventId, mentions: Option<Mentions>) -> Self {
-        Self { event_id, mentions }
+        Self { event_id, mentions, msgtype: None }
+    }
+
+    fn from_content(event_id: &EventId, content: &RoomMessageEventContent) -> Self {
+        Self {
+            event_id: event_id.to_owned(),
+            mentions: content.mentions.clone(),
+            msgtype: Some(content.msgtype().to_owned()),
+        }
     }
 }
 
 impl From<&OriginalRoomMessageEvent> for ReplacementMetadata {
     fn from(value: &OriginalRoomMessageEvent) -> Self {
-        ReplacementMetadata::new(value.event_id.to_owned(), value.content.mentions.clone())
+        ReplacementMetadata::from_content(&value.event_id, &value.content)
     }
 }
 
 impl From<&OriginalSyncRoomMessageEvent> for ReplacementMetadata {
     fn from(value: &OriginalSyncRoomMessageEvent) -> Self {
-        ReplacementMetadata::new(value.event_id.to_owned(), value.content.mentions.clone())
+        ReplacementMetadata::from_content(&value.event_id, &value.content)
     }
 }
 
+/// An error returned when trying to replace a message with a message of a different `msgtype`.
+///
+/// To be used with [`RoomMessageEventContent::try_make_replacement`].
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+#[error("cannot replace a message of type `{original}` with a message of type `{replacement}`")]
+#[non_exhaustive]
+pub struct MsgtypeMismatchError {
+    /// The `msgtype` of the message that is replaced.
+    pub original: String,
+
+    /// The `msgtype` of the replacement.
+    pub replacement: String,
+}
+
 /// The format for the formatted representation of a message body.
 #[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/doc/string_enum.md"))]
 #[derive(Clone, PartialEq, Eq, StringEnum)]
@@ -853,6 +1081,14 @@ impl FormattedBody {
         parse_markdown(body.as_ref()).map(Self::html)
     }
 
+    /// The HTML of this `FormattedBody`, if its format is `MessageFormat::Html`.
+    ///
+    /// Clients should ignore formatted bodies in unknown formats and render the plain text body
+    /// instead, so this returns `None` for any other format.
+    pub fn renderable_html(&self) -> Option<&str> {
+        (self.format == MessageFormat::Html).then_some(self.body.as_str())
+    }
+
     /// Sanitize this `FormattedBody` if its format is `MessageFormat::Html`.
     ///
     /// This removes any [tags and attributes] that are not listed in the Matrix specification.
@@ -873,6 +1109,29 @@ impl FormattedBody {
             self.body = sanitize_html(&self.body, mode, remove_reply_fallback);
         }
     }
+
+    /// Truncate this `FormattedBody` to the given number of visible characters, if its format is
+    /// `MessageFormat::Html`.
+    ///
+    /// The HTML is kept well-formed: it is never cut inside a tag, an attribute or an entity, and
+    /// the elements that were open at the truncation point are closed. This is useful to show a
+    /// preview of a message, in a notification for example.
+    ///
+    /// Returns `true` if some content was removed. The body is left untouched otherwise.
+    #[cfg(feature = "html")]
+    pub fn truncate_html(&mut self, max_chars: usize) -> bool {
+        if self.format != MessageFormat::Html {
+            return false;
+        }
+
+        let mut html = Html::parse(&self.body);
+        let truncated = html.truncate(max_chars);
+        if truncated {
+            self.body = html.to_string();
+        }
+
+        truncated
+    }
 }
 
 /// The payload for a custom message event.
@@ -892,15 +1151,35 @@ pub struct CustomEventContent {
 
 #[cfg(feature = "markdown")]
 pub(crate) fn parse_markdown(text: &str) -> Option<String> {
-    use pulldown_cmark::{Event, Options, Parser, Tag};
+    use pulldown_cmark::{CodeBlockKind, CowStr, Event, Options, Parser, Tag};
 
     const OPTIONS: Options = Options::ENABLE_TABLES.union(Options::ENABLE_STRIKETHROUGH);
 
+    // Only keep the language of fenced code blocks if it is valid, to avoid generating a broken
+    // `class` attribute.
+    fn sanitize_code_block(kind: CodeBlockKind<'_>) -> CodeBlockKind<'_> {
+        match kind {
+            CodeBlockKind::Fenced(info) => {
+                let language = info.split_whitespace().next().filter(|language| {
+                    language.chars().all(|c| c.is_ascii_alphanumeric() || "-+#".contains(c))
+                });
+                CodeBlockKind::Fenced(CowStr::from(language.unwrap_or_default().to_owned()))
+            }
+            CodeBlockKind::Indented => CodeBlockKind::Indented,
+        }
+    }
+
     let mut found_first_paragraph = false;
 
     let parser_events: Vec<_> = Parser::new_ext(text, OPTIONS)
         .map(|event| match event {
             Event::SoftBreak => Event::HardBreak,
+            Event::Start(Tag::CodeBlock(kind)) => {
+                Event::Start(Tag::CodeBlock(sanitize_code_block(kind)))
+            }
+            Event::End(Tag::CodeBlock(kind)) => {
+                Event::End(Tag::CodeBlock(sanitize_code_block(kind)))
+            }
             _ => event,
         })
         .collect();
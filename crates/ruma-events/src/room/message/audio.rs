@@ -1,9 +1,13 @@
 use std::time::Duration;
 
+#[cfg(feature = "unstable-audio-loudness")]
+use js_int::Int;
 use js_int::UInt;
 use ruma_common::OwnedMxcUri;
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "unstable-media-tracks")]
+use super::MediaTrack;
 use crate::room::{EncryptedFile, MediaSource};
 
 /// The payload for an audio message.
@@ -37,6 +41,23 @@ pub struct AudioMessageEventContent {
     #[cfg(feature = "unstable-msc3245-v1-compat")]
     #[serde(rename = "org.matrix.msc3245.voice", skip_serializing_if = "Option::is_none")]
     pub voice: Option<UnstableVoiceContentBlock>,
+
+    /// The textual transcription of the audio clip, if any.
+    ///
+    /// This is mostly useful for voice messages, to make them accessible to users that can't
+    /// listen to them.
+    ///
+    /// This uses the unstable `io.ruma.transcription` key.
+    #[cfg(feature = "unstable-audio-transcription")]
+    #[serde(rename = "io.ruma.transcription", skip_serializing_if = "Option::is_none")]
+    pub transcription: Option<String>,
+
+    /// The local playback position of the audio clip, if any.
+    ///
+    /// This is meant to be used by clients to resume the playback of the audio clip. It is never
+    /// serialized nor deserialized.
+    #[serde(skip)]
+    pub playback_position: Option<Duration>,
 }
 
 impl AudioMessageEventContent {
@@ -50,6 +71,9 @@ impl AudioMessageEventContent {
             audio: None,
             #[cfg(feature = "unstable-msc3245-v1-compat")]
             voice: None,
+            #[cfg(feature = "unstable-audio-transcription")]
+            transcription: None,
+            playback_position: None,
         }
     }
 
@@ -72,6 +96,14 @@ impl AudioMessageEventContent {
     pub fn info(self, info: impl Into<Option<Box<AudioInfo>>>) -> Self {
         Self { info: info.into(), ..self }
     }
+
+    /// Returns the textual transcription of the audio clip, if any.
+    ///
+    /// Blank transcriptions are treated as missing.
+    #[cfg(feature = "unstable-audio-transcription")]
+    pub fn transcription(&self) -> Option<&str> {
+        self.transcription.as_deref().filter(|transcription| !transcription.trim().is_empty())
+    }
 }
 
 /// Metadata about an audio clip.
@@ -93,6 +125,31 @@ pub struct AudioInfo {
     /// The size of the audio clip in bytes.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub size: Option<UInt>,
+
+    /// The tracks contained in the audio file, e.g. the different audio or subtitles tracks.
+    ///
+    /// This uses the unstable `io.ruma.tracks` key.
+    #[cfg(feature = "unstable-media-tracks")]
+    #[serde(rename = "io.ruma.tracks", default, skip_serializing_if = "Vec::is_empty")]
+    pub tracks: Vec<MediaTrack>,
+
+    /// The chapters of the audio clip, e.g. for podcasts or long recordings.
+    ///
+    /// This uses the unstable `io.ruma.chapters` key.
+    #[cfg(feature = "unstable-audio-chapters")]
+    #[serde(rename = "io.ruma.chapters", default, skip_serializing_if = "Vec::is_empty")]
+    pub chapters: Vec<AudioChapter>,
+
+    /// The integrated loudness of the audio, in hundredths of LUFS, if any.
+    ///
+    /// For example, `-1650` means -16.5 LUFS. This can be used by players to normalize the
+    /// loudness of audio clips, like voice messages. An integer is used because the canonical JSON
+    /// of signed events doesn't allow floats.
+    ///
+    /// This uses the unstable `io.ruma.loudness` key.
+    #[cfg(feature = "unstable-audio-loudness")]
+    #[serde(rename = "io.ruma.loudness", skip_serializing_if = "Option::is_none")]
+    pub loudness: Option<Int>,
 }
 
 impl AudioInfo {
@@ -100,6 +157,44 @@ impl AudioInfo {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Get the chapter of the audio clip at the given playback position, if any.
+    ///
+    /// This is the chapter with the latest start position that is not after `position`, regardless
+    /// of the order of `chapters`. Returns `None` if `position` is before the first chapter or
+    /// after the end of the audio clip, when its duration is known.
+    #[cfg(feature = "unstable-audio-chapters")]
+    pub fn chapter_at(&self, position: Duration) -> Option<&AudioChapter> {
+        if self.duration.is_some_and(|duration| position > duration) {
+            return None;
+        }
+
+        self.chapters
+            .iter()
+            .filter(|chapter| chapter.position <= position)
+            .max_by_key(|chapter| chapter.position)
+    }
+}
+
+/// A chapter marker in an audio clip.
+#[cfg(feature = "unstable-audio-chapters")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct AudioChapter {
+    /// The position where the chapter starts in the audio clip, in milliseconds.
+    #[serde(rename = "position_ms", with = "ruma_common::serde::duration::ms")]
+    pub position: Duration,
+
+    /// The title of the chapter.
+    pub title: String,
+}
+
+#[cfg(feature = "unstable-audio-chapters")]
+impl AudioChapter {
+    /// Creates a new `AudioChapter` with the given start position and title.
+    pub fn new(position: Duration, title: String) -> Self {
+        Self { position, title }
+    }
 }
 
 /// Extensible event fallback data for audio messages, from the
@@ -120,15 +215,39 @@ pub struct UnstableAudioDetailsContentBlock {
     /// The waveform representation of the audio content, if any.
     ///
     /// This is optional and defaults to an empty array.
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    ///
+    /// To avoid huge allocations with untrusted input, at most 1024 samples are kept when
+    /// deserializing: the samples past the first 1024 are dropped, so such a waveform doesn't
+    /// round-trip.
+    #[serde(
+        default,
+        deserialize_with = "crate::waveform_serde::deserialize",
+        skip_serializing_if = "Vec::is_empty"
+    )]
     pub waveform: Vec<UnstableAmplitude>,
+
+    /// The integrated loudness of the audio, in hundredths of LUFS, if any.
+    ///
+    /// For example, `-1650` means -16.5 LUFS. This can be used by players to normalize the
+    /// loudness of audio clips, like voice messages. An integer is used because the canonical JSON
+    /// of signed events doesn't allow floats.
+    ///
+    /// This uses the unstable `io.ruma.loudness` key.
+    #[cfg(feature = "unstable-audio-loudness")]
+    #[serde(rename = "io.ruma.loudness", skip_serializing_if = "Option::is_none")]
+    pub loudness: Option<Int>,
 }
 
 #[cfg(feature = "unstable-msc3245-v1-compat")]
 impl UnstableAudioDetailsContentBlock {
     /// Creates a new `UnstableAudioDetailsContentBlock ` with the given duration and waveform.
     pub fn new(duration: Duration, waveform: Vec<UnstableAmplitude>) -> Self {
-        Self { duration, waveform }
+        Self {
+            duration,
+            waveform,
+            #[cfg(feature = "unstable-audio-loudness")]
+            loudness: None,
+        }
     }
 }
 
@@ -20,7 +20,7 @@ impl<'de> Deserialize<'de> for RoomMessageEventContent {
         let mut deserializer = serde_json::Deserializer::from_str(json.get());
         let relates_to = deserialize_relation(&mut deserializer).map_err(de::Error::custom)?;
 
-        let MentionsDeHelper { mentions } = from_raw_json_value(&json)?;
+        let mentions = from_raw_json_value::<MentionsDeHelper, _>(&json)?.into_mentions();
 
         Ok(Self { msgtype: from_raw_json_value(&json)?, relates_to, mentions })
     }
@@ -33,16 +33,32 @@ impl<'de> Deserialize<'de> for RoomMessageEventContentWithoutRelation {
     {
         let json = Box::<RawJsonValue>::deserialize(deserializer)?;
 
-        let MentionsDeHelper { mentions } = from_raw_json_value(&json)?;
+        let mentions = from_raw_json_value::<MentionsDeHelper, _>(&json)?.into_mentions();
 
         Ok(Self { msgtype: from_raw_json_value(&json)?, mentions })
     }
 }
 
+/// The intentional mentions can be under the stable key or under the unstable key of [MSC3952],
+/// used before the feature was added to the spec.
+///
+/// A serde alias can't be used, because some clients send both keys, which would be a duplicate
+/// field. The stable key has priority.
+///
+/// [MSC3952]: https://github.com/matrix-org/matrix-spec-proposals/pull/3952
 #[derive(Deserialize)]
 struct MentionsDeHelper {
     #[serde(rename = "m.mentions")]
     mentions: Option<Mentions>,
+
+    #[serde(rename = "org.matrix.msc3952.mentions")]
+    unstable_mentions: Option<Mentions>,
+}
+
+impl MentionsDeHelper {
+    fn into_mentions(self) -> Option<Mentions> {
+        self.mentions.or(self.unstable_mentions)
+    }
 }
 
 /// Helper struct to determine the msgtype from a `serde_json::value::RawValue`
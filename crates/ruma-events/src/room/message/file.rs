@@ -1,4 +1,9 @@
+#[cfg(feature = "unstable-media-hashes")]
+use std::collections::BTreeMap;
+
 use js_int::UInt;
+#[cfg(feature = "unstable-media-hashes")]
+use ruma_common::serde::Base64;
 use ruma_common::OwnedMxcUri;
 use serde::{Deserialize, Serialize};
 
@@ -25,12 +30,26 @@ pub struct FileMessageEventContent {
     /// Metadata about the file referred to in `source`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub info: Option<Box<FileInfo>>,
+
+    /// Extensible event fallback data for file messages, from [MSC1767].
+    ///
+    /// [MSC1767]: https://github.com/matrix-org/matrix-spec-proposals/pull/1767
+    #[cfg(feature = "unstable-msc1767")]
+    #[serde(rename = "org.matrix.msc1767.file", skip_serializing_if = "Option::is_none")]
+    pub file: Option<UnstableFileContentBlock>,
 }
 
 impl FileMessageEventContent {
     /// Creates a new `FileMessageEventContent` with the given body and source.
     pub fn new(body: String, source: MediaSource) -> Self {
-        Self { body, filename: None, source, info: None }
+        Self {
+            body,
+            filename: None,
+            source,
+            info: None,
+            #[cfg(feature = "unstable-msc1767")]
+            file: None,
+        }
     }
 
     /// Creates a new non-encrypted `FileMessageEventContent` with the given body and url.
@@ -86,6 +105,16 @@ pub struct FileInfo {
         skip_serializing_if = "Option::is_none"
     )]
     pub thumbnail_source: Option<MediaSource>,
+
+    /// A map from an algorithm name to a hash of the file, encoded as unpadded base64.
+    ///
+    /// Like the `hashes` of an [`EncryptedFile`], this allows clients to check the integrity of
+    /// the downloaded file. Clients should support the SHA-256 hash, which uses the key `sha256`.
+    ///
+    /// This uses the unstable `io.ruma.hashes` key.
+    #[cfg(feature = "unstable-media-hashes")]
+    #[serde(rename = "io.ruma.hashes", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hashes: BTreeMap<String, Base64>,
 }
 
 impl FileInfo {
@@ -93,4 +122,59 @@ impl FileInfo {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Creates a `FileInfo` with the size and the SHA-256 hash of the given file.
+    #[cfg(feature = "unstable-media-hashes")]
+    pub fn from_bytes(data: &[u8]) -> Self {
+        let mut info = Self::new();
+        info.size = UInt::try_from(data.len()).ok();
+        info.hashes.insert("sha256".to_owned(), crate::room::media_content_hash(data));
+        info
+    }
+
+    /// The SHA-256 hash of the file, if it is known.
+    ///
+    /// It can be used as a key to detect that the same file was already uploaded.
+    #[cfg(feature = "unstable-media-hashes")]
+    pub fn content_hash(&self) -> Option<&Base64> {
+        self.hashes.get("sha256")
+    }
+
+    /// Checks the given file against the SHA-256 hash in `hashes`.
+    ///
+    /// Returns `None` if there is no SHA-256 hash, otherwise whether the hash of `data` matches.
+    #[cfg(feature = "unstable-media-hashes")]
+    pub fn verify_hashes(&self, data: &[u8]) -> Option<bool> {
+        crate::room::verify_sha256_hash(&self.hashes, data)
+    }
+}
+
+/// Extensible event fallback data for file messages, from [MSC1767].
+///
+/// [MSC1767]: https://github.com/matrix-org/matrix-spec-proposals/pull/1767
+#[cfg(feature = "unstable-msc1767")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct UnstableFileContentBlock {
+    /// The URL to the file.
+    pub url: OwnedMxcUri,
+
+    /// The original filename of the uploaded file.
+    pub name: String,
+
+    /// The mimetype of the file, e.g. "application/msword".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mimetype: Option<String>,
+
+    /// The size of the file in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<UInt>,
+}
+
+#[cfg(feature = "unstable-msc1767")]
+impl UnstableFileContentBlock {
+    /// Creates a new `UnstableFileContentBlock` with the given url and name.
+    pub fn new(url: OwnedMxcUri, name: String) -> Self {
+        Self { url, name, mimetype: None, size: None }
+    }
 }
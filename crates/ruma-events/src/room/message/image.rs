@@ -48,4 +48,11 @@ impl ImageMessageEventContent {
     pub fn info(self, info: impl Into<Option<Box<ImageInfo>>>) -> Self {
         Self { info: info.into(), ..self }
     }
+
+    /// The source to use to preview this image.
+    ///
+    /// Returns the source of the thumbnail if there is one, otherwise the source of the image.
+    pub fn preview_source(&self) -> &MediaSource {
+        self.info.as_ref().and_then(|info| info.thumbnail_source.as_ref()).unwrap_or(&self.source)
+    }
 }
@@ -71,6 +71,16 @@ impl LocationMessageEventContent {
         }
     }
 
+    /// Creates a new `LocationMessageEventContent` with the given body and geo URI, after checking
+    /// that the URI is valid.
+    ///
+    /// Returns an error if `geo_uri` doesn't use the `geo` scheme or doesn't contain valid latitude
+    /// and longitude coordinates.
+    pub fn try_new(body: String, geo_uri: String) -> Result<Self, GeoUriError> {
+        parse_geo_uri_coordinates(&geo_uri)?;
+        Ok(Self::new(body, geo_uri))
+    }
+
     /// Set the asset type of this `LocationMessageEventContent`.
     #[cfg(feature = "unstable-msc3488")]
     pub fn with_asset_type(mut self, asset: AssetType) -> Self {
@@ -95,6 +105,43 @@ impl LocationMessageEventContent {
         &self.geo_uri
     }
 
+    /// Get the uncertainty of the location, in meters.
+    ///
+    /// This is the value of the `u` parameter of the [`geo:` URI] of this
+    /// `LocationMessageEventContent`, that clients can use to draw an accuracy circle around the
+    /// location.
+    ///
+    /// Returns `None` if the parameter is missing or is not a valid non-negative number.
+    ///
+    /// [`geo:` URI]: https://datatracker.ietf.org/doc/html/rfc5870#section-3.4.3
+    pub fn uncertainty(&self) -> Option<f64> {
+        let (scheme, uri) = self.geo_uri().split_once(':')?;
+        if !scheme.eq_ignore_ascii_case("geo") {
+            return None;
+        }
+
+        uri.split(';').skip(1).find_map(|param| {
+            let (name, value) = param.split_once('=')?;
+            name.trim()
+                .eq_ignore_ascii_case("u")
+                .then(|| value.trim().parse::<f64>().ok())
+                .flatten()
+                .filter(|uncertainty| uncertainty.is_finite() && *uncertainty >= 0.0)
+        })
+    }
+
+    /// Get the latitude and longitude of the location, in decimal degrees.
+    ///
+    /// These are the coordinates of the [`geo:` URI] of this `LocationMessageEventContent`. The
+    /// altitude, if any, is ignored.
+    ///
+    /// Returns `None` if the URI is not a valid `geo:` URI.
+    ///
+    /// [`geo:` URI]: https://datatracker.ietf.org/doc/html/rfc5870#section-3.4.2
+    pub fn coordinates(&self) -> Option<(f64, f64)> {
+        parse_geo_uri_coordinates(self.geo_uri()).ok()
+    }
+
     /// Get the plain text representation of this `LocationMessageEventContent`.
     pub fn plain_text_representation(&self) -> &str {
         #[cfg(feature = "unstable-msc3488")]
@@ -112,6 +159,44 @@ impl LocationMessageEventContent {
     }
 }
 
+/// Parse the latitude and longitude of the given `geo:` URI.
+fn parse_geo_uri_coordinates(uri: &str) -> Result<(f64, f64), GeoUriError> {
+    let (scheme, uri) = uri.split_once(':').ok_or(GeoUriError::InvalidScheme)?;
+    if !scheme.eq_ignore_ascii_case("geo") {
+        return Err(GeoUriError::InvalidScheme);
+    }
+
+    let coordinates = uri.split(';').next().unwrap_or_default();
+    let mut coordinates = coordinates.split(',').map(|c| c.trim().parse::<f64>());
+
+    let (Some(Ok(latitude)), Some(Ok(longitude))) = (coordinates.next(), coordinates.next()) else {
+        return Err(GeoUriError::InvalidCoordinates);
+    };
+    // An optional altitude is allowed, but nothing more.
+    if !matches!(coordinates.next(), None | Some(Ok(_))) || coordinates.next().is_some() {
+        return Err(GeoUriError::InvalidCoordinates);
+    }
+
+    if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+        return Err(GeoUriError::InvalidCoordinates);
+    }
+
+    Ok((latitude, longitude))
+}
+
+/// An error encountered when trying to parse a `geo:` URI.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum GeoUriError {
+    /// The URI doesn't use the `geo` scheme.
+    #[error("URI doesn't use the geo scheme")]
+    InvalidScheme,
+
+    /// The URI doesn't contain valid latitude and longitude coordinates.
+    #[error("invalid coordinates")]
+    InvalidCoordinates,
+}
+
 /// Thumbnail info associated with a location.
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
@@ -0,0 +1,150 @@
+use js_int::UInt;
+#[cfg(feature = "unstable-media-tracks")]
+use ruma_common::serde::StringEnum;
+#[cfg(feature = "unstable-media-tracks")]
+use serde::{Deserialize, Serialize};
+
+use super::{
+    AudioMessageEventContent, FileMessageEventContent, ImageMessageEventContent,
+    VideoMessageEventContent,
+};
+use crate::room::MediaSource;
+#[cfg(feature = "unstable-media-tracks")]
+use crate::PrivOwnedStr;
+
+/// Common accessors for the message types that contain a media file.
+///
+/// This is implemented for [`AudioMessageEventContent`], [`FileMessageEventContent`],
+/// [`ImageMessageEventContent`] and [`VideoMessageEventContent`].
+pub trait MediaMessage {
+    /// The source of the media file.
+    fn source(&self) -> &MediaSource;
+
+    /// The size of the media file in bytes, if it is known.
+    fn info_size(&self) -> Option<UInt>;
+
+    /// The mimetype of the media file, if it is known.
+    fn info_mimetype(&self) -> Option<&str>;
+
+    /// The source of the thumbnail of the media file, if any.
+    fn thumbnail_source(&self) -> Option<&MediaSource>;
+
+    /// Whether the media file might be unsafe to render inline.
+    ///
+    /// Media like `image/svg+xml` or `text/html` can embed scripts, so clients should refuse to
+    /// render them inline or sandbox them. Returns `true` if the mimetype is unknown, since the
+    /// media file could be of any type.
+    fn is_potentially_unsafe_media(&self) -> bool {
+        self.info_mimetype().map_or(true, is_potentially_unsafe_mimetype)
+    }
+}
+
+/// Mimetypes of media that can contain scripts or active content.
+const UNSAFE_MIMETYPES: &[&str] = &[
+    "image/svg+xml",
+    "text/html",
+    "application/xhtml+xml",
+    "text/xml",
+    "application/xml",
+    "text/javascript",
+    "application/javascript",
+    "application/ecmascript",
+];
+
+/// Whether the given mimetype is known to be unsafe to render inline.
+///
+/// Parameters, like `charset`, are ignored and the comparison is case-insensitive.
+fn is_potentially_unsafe_mimetype(mimetype: &str) -> bool {
+    let essence = mimetype.split(';').next().unwrap_or_default().trim();
+    UNSAFE_MIMETYPES.iter().any(|unsafe_mimetype| essence.eq_ignore_ascii_case(unsafe_mimetype))
+}
+
+impl MediaMessage for AudioMessageEventContent {
+    fn source(&self) -> &MediaSource {
+        &self.source
+    }
+
+    fn info_size(&self) -> Option<UInt> {
+        self.info.as_ref()?.size
+    }
+
+    fn info_mimetype(&self) -> Option<&str> {
+        self.info.as_ref()?.mimetype.as_deref()
+    }
+
+    fn thumbnail_source(&self) -> Option<&MediaSource> {
+        None
+    }
+}
+
+macro_rules! impl_media_message {
+    ($($ty:ident),*) => {
+        $(
+            impl MediaMessage for $ty {
+                fn source(&self) -> &MediaSource {
+                    &self.source
+                }
+
+                fn info_size(&self) -> Option<UInt> {
+                    self.info.as_ref()?.size
+                }
+
+                fn info_mimetype(&self) -> Option<&str> {
+                    self.info.as_ref()?.mimetype.as_deref()
+                }
+
+                fn thumbnail_source(&self) -> Option<&MediaSource> {
+                    self.info.as_ref()?.thumbnail_source.as_ref()
+                }
+            }
+        )*
+    };
+}
+
+impl_media_message!(FileMessageEventContent, ImageMessageEventContent, VideoMessageEventContent);
+
+/// A description of a track contained in an audio or video file.
+///
+/// This allows clients to present a track selection interface without probing the file.
+#[cfg(feature = "unstable-media-tracks")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct MediaTrack {
+    /// The identifier of the track inside the media file.
+    pub id: String,
+
+    /// The kind of content of the track.
+    pub kind: MediaTrackKind,
+
+    /// The language of the track, as a BCP 47 language tag, e.g. "en" or "pt-BR".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+#[cfg(feature = "unstable-media-tracks")]
+impl MediaTrack {
+    /// Creates a new `MediaTrack` with the given ID and kind.
+    pub fn new(id: String, kind: MediaTrackKind) -> Self {
+        Self { id, kind, language: None }
+    }
+}
+
+/// The kind of content of a [`MediaTrack`].
+#[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/doc/string_enum.md"))]
+#[cfg(feature = "unstable-media-tracks")]
+#[derive(Clone, PartialEq, Eq, StringEnum)]
+#[ruma_enum(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum MediaTrackKind {
+    /// An audio track.
+    Audio,
+
+    /// A video track.
+    Video,
+
+    /// A subtitles or captions track.
+    Subtitles,
+
+    #[doc(hidden)]
+    _Custom(PrivOwnedStr),
+}
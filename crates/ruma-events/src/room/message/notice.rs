@@ -1,3 +1,5 @@
+#[cfg(feature = "unstable-bridge-attribution")]
+use ruma_common::OwnedMxcUri;
 use serde::{Deserialize, Serialize};
 
 use super::FormattedBody;
@@ -13,19 +15,36 @@ pub struct NoticeMessageEventContent {
     /// Formatted form of the message `body`.
     #[serde(flatten)]
     pub formatted: Option<FormattedBody>,
+
+    /// The original sender of this message, if it was bridged from another network.
+    ///
+    /// This uses the unstable `io.ruma.bridge_attribution` key.
+    #[cfg(feature = "unstable-bridge-attribution")]
+    #[serde(rename = "io.ruma.bridge_attribution", skip_serializing_if = "Option::is_none")]
+    pub bridge_attribution: Option<BridgeAttribution>,
 }
 
 impl NoticeMessageEventContent {
     /// A convenience constructor to create a plain text notice.
     pub fn plain(body: impl Into<String>) -> Self {
         let body = body.into();
-        Self { body, formatted: None }
+        Self {
+            body,
+            formatted: None,
+            #[cfg(feature = "unstable-bridge-attribution")]
+            bridge_attribution: None,
+        }
     }
 
     /// A convenience constructor to create an html notice.
     pub fn html(body: impl Into<String>, html_body: impl Into<String>) -> Self {
         let body = body.into();
-        Self { body, formatted: Some(FormattedBody::html(html_body)) }
+        Self {
+            body,
+            formatted: Some(FormattedBody::html(html_body)),
+            #[cfg(feature = "unstable-bridge-attribution")]
+            bridge_attribution: None,
+        }
     }
 
     /// A convenience constructor to create a markdown notice.
@@ -41,3 +60,29 @@ impl NoticeMessageEventContent {
         }
     }
 }
+
+/// The original sender of a message bridged from another network.
+///
+/// Clients can use it to display the true origin of the message, like "Alice via Telegram".
+#[cfg(feature = "unstable-bridge-attribution")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct BridgeAttribution {
+    /// The human-readable name of the network the message was bridged from, e.g. "Telegram".
+    pub network: String,
+
+    /// The display name of the sender on the other network.
+    pub sender_name: String,
+
+    /// The avatar of the sender on the other network, uploaded to the media repository.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender_avatar_url: Option<OwnedMxcUri>,
+}
+
+#[cfg(feature = "unstable-bridge-attribution")]
+impl BridgeAttribution {
+    /// Creates a new `BridgeAttribution` with the given network and sender name.
+    pub fn new(network: String, sender_name: String) -> Self {
+        Self { network, sender_name, sender_avatar_url: None }
+    }
+}
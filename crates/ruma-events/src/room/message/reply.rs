@@ -67,7 +67,7 @@ fn get_message_quote_fallbacks(original_event: OriginalEventData<'_>) -> (String
     )
 }
 
-struct EscapeHtmlEntities<'a>(&'a str);
+pub(super) struct EscapeHtmlEntities<'a>(pub(super) &'a str);
 
 impl fmt::Display for EscapeHtmlEntities<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -0,0 +1,212 @@
+//! Split the body of a text message in several parts.
+
+/// Split the given plain text body in parts that are at most `max_bytes` long.
+///
+/// The body is split between lines if possible, then between words, and finally between
+/// characters. When a part ends in the middle of a fenced code block, the code block is closed at
+/// the end of the part and reopened at the start of the next one.
+pub(super) fn split_body(body: &str, max_bytes: usize) -> Vec<String> {
+    let mut splitter = Splitter::new(max_bytes);
+
+    for line in body.split('\n') {
+        splitter.push_line(line);
+    }
+
+    splitter.finish()
+}
+
+/// A fenced code block opened in a previous line.
+#[derive(Clone, Copy)]
+struct CodeFence<'a> {
+    /// The line that opens the code block, including the info string.
+    opening: &'a str,
+
+    /// The characters of the fence, that are also used to close the code block.
+    marker: &'a str,
+}
+
+impl<'a> CodeFence<'a> {
+    /// Parse the given line as the start of a fenced code block.
+    fn parse_opening(line: &'a str) -> Option<Self> {
+        let trimmed = line.trim_start();
+        let fence_char = trimmed.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+        let marker_len = trimmed.len() - trimmed.trim_start_matches(fence_char).len();
+
+        (marker_len >= 3).then(|| Self { opening: line, marker: &trimmed[..marker_len] })
+    }
+
+    /// Whether the given line closes this code block.
+    fn is_closed_by(&self, line: &str) -> bool {
+        let trimmed = line.trim();
+        let fence_char = self.marker.chars().next().unwrap_or('`');
+
+        trimmed.starts_with(self.marker) && trimmed.trim_start_matches(fence_char).is_empty()
+    }
+
+    /// The line used to reopen this code block at the start of a new part.
+    ///
+    /// The opening line is reused if there is enough room left for at least one byte of content
+    /// and the closing fence, otherwise the info string is dropped. Returns `None` if even the
+    /// bare marker doesn't fit, in which case the code block is not closed and reopened between
+    /// parts.
+    fn reopening(&self, max_bytes: usize) -> Option<&'a str> {
+        // The reopening line, a line with one byte of content, and the closing fence.
+        let fits = |line: &str| line.len() + self.marker.len() + 3 <= max_bytes;
+
+        [self.opening, self.marker].into_iter().find(|line| fits(line))
+    }
+}
+
+struct Splitter<'a> {
+    max_bytes: usize,
+    parts: Vec<String>,
+    current: String,
+    /// The length of the reopened code fence at the start of `current`.
+    prefix_len: usize,
+    /// Whether `current` contains at least one line, even if it is empty.
+    has_lines: bool,
+    /// Whether the next text pushed to `current` starts a new line.
+    at_line_start: bool,
+    fence: Option<CodeFence<'a>>,
+}
+
+impl<'a> Splitter<'a> {
+    fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            parts: Vec::new(),
+            current: String::new(),
+            prefix_len: 0,
+            has_lines: false,
+            at_line_start: true,
+            fence: None,
+        }
+    }
+
+    fn push_line(&mut self, line: &'a str) {
+        let fence_after_line = match self.fence {
+            Some(fence) if fence.is_closed_by(line) => None,
+            Some(fence) => Some(fence),
+            None => CodeFence::parse_opening(line),
+        };
+        // Keep enough room to close the code block if it is still open after this line.
+        let reserved = fence_after_line
+            .filter(|fence| fence.reopening(self.max_bytes).is_some())
+            .map_or(0, |fence| fence.marker.len() + 1);
+
+        self.at_line_start = true;
+        if !self.try_push(line, reserved) {
+            for word in line.split_inclusive(char::is_whitespace) {
+                if !self.try_push(word, reserved) {
+                    self.push_chars(word, reserved);
+                }
+            }
+        }
+
+        self.fence = fence_after_line;
+    }
+
+    /// Push the given text to the current part, or to a new part if it doesn't fit.
+    ///
+    /// Returns `false` if the text doesn't fit in a part.
+    fn try_push(&mut self, text: &str, reserved: usize) -> bool {
+        if self.fits(text, reserved) {
+            self.push(text);
+            return true;
+        }
+
+        if self.has_content() {
+            self.finish_part();
+
+            if self.fits(text, reserved) {
+                self.push(text);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Push the given text that is too long for a single part, splitting it at character
+    /// boundaries.
+    fn push_chars(&mut self, mut text: &str, reserved: usize) {
+        while !text.is_empty() {
+            let separator_len = self.separator().len();
+            let available =
+                self.max_bytes.saturating_sub(self.current.len() + separator_len + reserved);
+
+            let mut end = text
+                .char_indices()
+                .map(|(i, c)| i + c.len_utf8())
+                .take_while(|&end| end <= available)
+                .last()
+                .unwrap_or_default();
+
+            if end == 0 {
+                if self.has_content() {
+                    self.finish_part();
+                    continue;
+                }
+
+                // Always make progress, even if the limit is too small for a single character.
+                end = text.chars().next().map_or(text.len(), char::len_utf8);
+            }
+
+            let (chunk, rest) = text.split_at(end);
+            self.push(chunk);
+            text = rest;
+        }
+    }
+
+    fn separator(&self) -> &'static str {
+        if self.at_line_start && self.has_lines {
+            "\n"
+        } else {
+            ""
+        }
+    }
+
+    fn fits(&self, text: &str, reserved: usize) -> bool {
+        self.current.len() + self.separator().len() + text.len() + reserved <= self.max_bytes
+    }
+
+    fn push(&mut self, text: &str) {
+        self.current.push_str(self.separator());
+        self.current.push_str(text);
+        self.has_lines = true;
+        self.at_line_start = false;
+    }
+
+    fn has_content(&self) -> bool {
+        self.current.len() > self.prefix_len
+    }
+
+    fn finish_part(&mut self) {
+        let mut part = std::mem::take(&mut self.current);
+
+        if let Some((fence, reopening)) =
+            self.fence.and_then(|fence| Some((fence, fence.reopening(self.max_bytes)?)))
+        {
+            part.push('\n');
+            part.push_str(fence.marker);
+
+            self.current.push_str(reopening);
+            self.prefix_len = reopening.len();
+            self.has_lines = true;
+        } else {
+            self.prefix_len = 0;
+            self.has_lines = false;
+        }
+
+        self.parts.push(part);
+        self.at_line_start = true;
+    }
+
+    fn finish(mut self) -> Vec<String> {
+        if self.has_content() || self.parts.is_empty() {
+            self.parts.push(self.current);
+        }
+
+        self.parts
+    }
+}
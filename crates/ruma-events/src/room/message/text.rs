@@ -1,6 +1,10 @@
+use std::{collections::BTreeMap, ops::Range};
+
+use ruma_common::{RoomAliasId, UserId};
 use serde::{Deserialize, Serialize};
+use url::Url;
 
-use super::FormattedBody;
+use super::{reply::EscapeHtmlEntities, Direction, FormattedBody, MessageFormat};
 
 /// The payload for a text message.
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -40,4 +44,575 @@ impl TextMessageEventContent {
             Self::plain(body)
         }
     }
+
+    /// A convenience constructor to create a message quoting the given text.
+    ///
+    /// In the plain text body, every line of `text` is prefixed with `> `. In the HTML body, the
+    /// text is wrapped in a `<blockquote>` element.
+    ///
+    /// Lines of `text` that are already quoted, because they start with `>`, are put in nested
+    /// `<blockquote>` elements.
+    pub fn quote(text: impl Into<String>) -> Self {
+        let text = text.into();
+        let mut body = String::new();
+        let mut html_body = String::new();
+        let mut depth = 0;
+        let mut is_block_start = true;
+
+        for (i, line) in text.split('\n').enumerate() {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+
+            if i > 0 {
+                body.push('\n');
+            }
+            if line.is_empty() {
+                body.push('>');
+            } else {
+                body.push_str("> ");
+                body.push_str(line);
+            }
+
+            let (line_depth, content) = quote_depth(line);
+            let line_depth = line_depth + 1;
+
+            if line_depth != depth {
+                while depth < line_depth {
+                    html_body.push_str("<blockquote>");
+                    depth += 1;
+                }
+                while depth > line_depth {
+                    html_body.push_str("</blockquote>");
+                    depth -= 1;
+                }
+                is_block_start = true;
+            }
+
+            if !is_block_start {
+                html_body.push_str("<br>");
+            }
+            html_body.push_str(&EscapeHtmlEntities(content).to_string());
+            is_block_start = false;
+        }
+
+        for _ in 0..depth {
+            html_body.push_str("</blockquote>");
+        }
+
+        Self::html(body, html_body)
+    }
+
+    /// Marks the first occurrence of `text` in this message as a [spoiler].
+    ///
+    /// In the HTML body, `text` is wrapped in a `<span data-mx-spoiler>` element with the given
+    /// `reason`, if any. If this message doesn't have a formatted body yet, it is generated from
+    /// the plain text body.
+    ///
+    /// In the plain text body, `text` is replaced by a fallback that doesn't contain it, so it is
+    /// not revealed in notifications: `[Spoiler]`, or `[Spoiler for {reason}]` if a reason is
+    /// given.
+    ///
+    /// Returns the message unchanged if `text` is empty or is not found in both bodies, or if the
+    /// message has a formatted body with a format other than HTML.
+    ///
+    /// [spoiler]: https://spec.matrix.org/latest/client-server-api/#spoiler-messages
+    pub fn with_spoiler(mut self, text: &str, reason: Option<&str>) -> Self {
+        if text.is_empty() {
+            return self;
+        }
+        let Some(plain_idx) = self.body.find(text) else {
+            return self;
+        };
+
+        let escaped_text = EscapeHtmlEntities(text).to_string();
+        let spoiler = match reason {
+            Some(reason) => format!(
+                "<span data-mx-spoiler=\"{}\">{escaped_text}</span>",
+                EscapeHtmlEntities(reason)
+            ),
+            None => format!("<span data-mx-spoiler>{escaped_text}</span>"),
+        };
+
+        let html_body = match &self.formatted {
+            Some(formatted) if formatted.format == MessageFormat::Html => {
+                let Some(html_idx) = find_in_html_text(&formatted.body, &escaped_text) else {
+                    return self;
+                };
+
+                let (before, after) = formatted.body.split_at(html_idx);
+                format!("{before}{spoiler}{}", &after[escaped_text.len()..])
+            }
+            Some(_) => return self,
+            None => {
+                let (before, after) = self.body.split_at(plain_idx);
+                format!(
+                    "{}{spoiler}{}",
+                    EscapeHtmlEntities(before),
+                    EscapeHtmlEntities(&after[text.len()..])
+                )
+            }
+        };
+
+        let fallback = match reason {
+            Some(reason) => format!("[Spoiler for {reason}]"),
+            None => "[Spoiler]".to_owned(),
+        };
+        self.body.replace_range(plain_idx..plain_idx + text.len(), &fallback);
+        self.formatted = Some(FormattedBody::html(html_body));
+
+        self
+    }
+
+    /// The base direction of the plain text body of this message.
+    ///
+    /// This uses the rule of the Unicode Bidirectional Algorithm to find the paragraph level: the
+    /// direction is given by the first character with a strong direction, skipping characters
+    /// inside isolates. Neutral characters and numbers, like the ones at the start of `"123 שלום"`,
+    /// are ignored. If the body doesn't contain any strongly directional character, the direction
+    /// is [`Direction::Ltr`].
+    ///
+    /// The classification of characters is a close approximation that doesn't rely on the full
+    /// Unicode character database: alphabetic characters in the blocks of right-to-left scripts,
+    /// like Hebrew or Arabic, are right-to-left and all other alphabetic characters are
+    /// left-to-right.
+    pub fn base_direction(&self) -> Direction {
+        let mut isolate_depth = 0_usize;
+
+        for c in self.body.chars() {
+            match c {
+                // LRI, RLI and FSI.
+                '\u{2066}'..='\u{2068}' => isolate_depth += 1,
+                // PDI.
+                '\u{2069}' => isolate_depth = isolate_depth.saturating_sub(1),
+                _ if isolate_depth > 0 => {}
+                // LRM.
+                '\u{200E}' => return Direction::Ltr,
+                // RLM and ALM.
+                '\u{200F}' | '\u{061C}' => return Direction::Rtl,
+                c if c.is_alphabetic() => {
+                    return if is_rtl_script_char(c) { Direction::Rtl } else { Direction::Ltr };
+                }
+                _ => {}
+            }
+        }
+
+        Direction::Ltr
+    }
+
+    /// Generates an HTML formatted body with links for the bare URLs and Matrix identifiers in the
+    /// plain text body.
+    ///
+    /// `http` and `https` URLs are wrapped in `<a>` elements. User IDs and room aliases, like
+    /// `@alice:example.org` or `#room:example.org`, are linked to their `matrix.to` URI so they
+    /// are rendered as pills by clients. Punctuation at the end of a link, like a period or a
+    /// closing parenthesis without a matching opening one, is not included in the link. The rest
+    /// of the body is escaped.
+    ///
+    /// Returns the message unchanged if it already has a formatted body or if there is nothing to
+    /// link in the body.
+    pub fn with_autolinks(self) -> Self {
+        if self.formatted.is_some() {
+            return self;
+        }
+
+        let links = find_links(&self.body);
+        if links.is_empty() {
+            return self;
+        }
+
+        let mut html_body = String::new();
+        let mut last_end = 0;
+
+        for (range, href) in links {
+            html_body.push_str(&EscapeHtmlEntities(&self.body[last_end..range.start]).to_string());
+            html_body.push_str(&format!(
+                "<a href=\"{}\">{}</a>",
+                EscapeHtmlEntities(&href),
+                EscapeHtmlEntities(&self.body[range.clone()])
+            ));
+            last_end = range.end;
+        }
+        html_body.push_str(&EscapeHtmlEntities(&self.body[last_end..]).to_string());
+
+        Self::html(self.body, html_body)
+    }
+
+    /// Replaces the emoji shortcodes, like `:smile:`, in this message with their value in the
+    /// given map.
+    ///
+    /// The keys of the map are the names of the shortcodes, without the surrounding colons, e.g.
+    /// `smile`. Shortcodes that are not in the map are left as-is. Shortcodes that are part of a
+    /// URL, or inside a code span or block, are not expanded.
+    ///
+    /// If this message has an HTML formatted body, the shortcodes are also expanded in it, except
+    /// in the attributes of the elements and inside `<code>` and `<pre>` elements.
+    pub fn with_expanded_shortcodes(mut self, shortcodes: &BTreeMap<String, String>) -> Self {
+        self.body = expand_shortcodes_in_markdown(&self.body, shortcodes);
+
+        if let Some(formatted) = &mut self.formatted {
+            if formatted.format == MessageFormat::Html {
+                formatted.body = expand_shortcodes_in_html(&formatted.body, shortcodes);
+            }
+        }
+
+        self
+    }
+
+    /// Whether the plain text body of this message contains the legacy `@room` mention.
+    ///
+    /// The `@room` token must be separated from other words, so `@roommate` or `hi@room` don't
+    /// match. The match is case-insensitive, like the `.m.rule.roomnotif` push rule. A word
+    /// boundary is defined as the start or end of the body, or any character not in
+    /// the sets `[A-Z]`, `[a-z]`, `[0-9]` or `_`.
+    ///
+    /// This only detects the mention, it doesn't check whether the sender is allowed to notify the
+    /// whole room. New clients should use intentional [`Mentions`] instead.
+    ///
+    /// [`Mentions`]: crate::Mentions
+    pub fn mentions_room(&self) -> bool {
+        const ROOM_MENTION: &str = "@room";
+        let is_word_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+
+        // Converting only ASCII characters keeps the byte indices identical.
+        let body = self.body.to_ascii_lowercase();
+        body.match_indices(ROOM_MENTION).any(|(start, _)| {
+            let end = start + ROOM_MENTION.len();
+            !body[..start].chars().next_back().is_some_and(is_word_char)
+                && !body[end..].chars().next().is_some_and(is_word_char)
+        })
+    }
+
+    /// Wraps this message in a collapsible block with the given summary.
+    ///
+    /// In the HTML body, the message is wrapped in a `<details>` element, with the summary in a
+    /// `<summary>` element. If this message doesn't have an HTML formatted body, it is generated
+    /// from the plain text body. Calling this method on a message that is already collapsible
+    /// creates nested collapsible blocks.
+    ///
+    /// In the plain text body, the summary is added on its own line before the message.
+    ///
+    /// If `summary` is empty, the `<summary>` element is omitted and clients will usually display
+    /// a default summary instead.
+    pub fn collapsible(self, summary: &str) -> Self {
+        let html_body = match self.formatted {
+            Some(formatted) if formatted.format == MessageFormat::Html => formatted.body,
+            _ => EscapeHtmlEntities(&self.body).to_string().replace('\n', "<br>"),
+        };
+
+        if summary.is_empty() {
+            return Self::html(self.body, format!("<details>{html_body}</details>"));
+        }
+
+        Self::html(
+            format!("{summary}\n{}", self.body),
+            format!(
+                "<details><summary>{}</summary>{html_body}</details>",
+                EscapeHtmlEntities(summary)
+            ),
+        )
+    }
+}
+
+/// Get the number of `>` quote markers at the start of the given line, and the rest of the line.
+fn quote_depth(mut line: &str) -> (usize, &str) {
+    let mut depth = 0;
+
+    while let Some(rest) = line.strip_prefix('>') {
+        line = rest.strip_prefix(' ').unwrap_or(rest);
+        depth += 1;
+    }
+
+    (depth, line)
+}
+
+/// Finds the bare URLs and Matrix identifiers in the given plain text.
+///
+/// Returns the range of each link in the text and its target.
+fn find_links(text: &str) -> Vec<(Range<usize>, String)> {
+    let is_separator = |c: char| c.is_whitespace() || matches!(c, '<' | '>' | '"');
+
+    let mut links = Vec::new();
+    let mut word_start = None;
+
+    for (i, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        if !is_separator(c) {
+            word_start.get_or_insert(i);
+            continue;
+        }
+
+        let Some(start) = word_start.take() else {
+            continue;
+        };
+        let word = &text[start..i];
+
+        if let Some((range, href)) = find_url(word).or_else(|| find_matrix_identifier(word)) {
+            links.push((start + range.start..start + range.end, href));
+        }
+    }
+
+    links
+}
+
+/// Finds an `http` or `https` URL in the given word.
+fn find_url(word: &str) -> Option<(Range<usize>, String)> {
+    let start = ["https://", "http://"]
+        .into_iter()
+        .filter_map(|scheme| word.match_indices(scheme).map(|(i, _)| i).next())
+        .min()?;
+
+    // The URL must not be preceded by a letter or a digit, like in `nothttps://`.
+    if word[..start].chars().next_back().is_some_and(char::is_alphanumeric) {
+        return None;
+    }
+
+    let url = trim_trailing_punctuation(&word[start..]);
+    Url::parse(url).ok().filter(Url::has_host)?;
+
+    Some((start..start + url.len(), url.to_owned()))
+}
+
+/// Finds a user ID or a room alias at the start of the given word, possibly after an opening
+/// parenthesis or bracket.
+fn find_matrix_identifier(word: &str) -> Option<(Range<usize>, String)> {
+    let start = word.len() - word.trim_start_matches(['(', '[']).len();
+    let identifier = trim_trailing_punctuation(&word[start..]);
+
+    let href = match identifier.chars().next()? {
+        '@' => UserId::parse(identifier).ok()?.matrix_to_uri().to_string(),
+        '#' => <&RoomAliasId>::try_from(identifier).ok()?.matrix_to_uri().to_string(),
+        _ => return None,
+    };
+
+    Some((start..start + identifier.len(), href))
+}
+
+/// Removes the punctuation at the end of the given link, that is most likely part of the
+/// surrounding sentence.
+fn trim_trailing_punctuation(mut link: &str) -> &str {
+    loop {
+        let Some(last) = link.chars().next_back() else {
+            return link;
+        };
+
+        let is_trailing = match last {
+            '.' | ',' | ':' | ';' | '!' | '?' | '\'' | '*' => true,
+            ')' => link.matches(')').count() > link.matches('(').count(),
+            ']' => link.matches(']').count() > link.matches('[').count(),
+            _ => false,
+        };
+
+        if !is_trailing {
+            return link;
+        }
+
+        link = &link[..link.len() - last.len_utf8()];
+    }
+}
+
+/// Expands the emoji shortcodes in the given plain text or Markdown, outside of code spans and
+/// blocks.
+fn expand_shortcodes_in_markdown(text: &str, shortcodes: &BTreeMap<String, String>) -> String {
+    let mut expanded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('`') {
+        expand_shortcodes_in_words(&rest[..start], shortcodes, false, &mut expanded);
+        rest = &rest[start..];
+
+        // A code span or block is closed by a run of backticks of the same length.
+        let fence_len = rest.len() - rest.trim_start_matches('`').len();
+        let code_len = find_backtick_run(&rest[fence_len..], fence_len)
+            .map_or(fence_len, |end| fence_len + end + fence_len);
+
+        expanded.push_str(&rest[..code_len]);
+        rest = &rest[code_len..];
+    }
+
+    expand_shortcodes_in_words(rest, shortcodes, false, &mut expanded);
+    expanded
+}
+
+/// Finds the start of the first run of exactly `len` backticks in the given text.
+fn find_backtick_run(text: &str, len: usize) -> Option<usize> {
+    let mut offset = 0;
+
+    while let Some(start) = text[offset..].find('`') {
+        let start = offset + start;
+        let run_len = text[start..].len() - text[start..].trim_start_matches('`').len();
+
+        if run_len == len {
+            return Some(start);
+        }
+
+        offset = start + run_len;
+    }
+
+    None
+}
+
+/// Finds the first occurrence of `escaped_text` in the text of the given HTML.
+///
+/// Tags, including their attributes, are skipped, and a match can't start inside a character
+/// reference.
+fn find_in_html_text(html: &str, escaped_text: &str) -> Option<usize> {
+    let mut idx = 0;
+
+    while idx < html.len() {
+        let rest = &html[idx..];
+
+        if rest.starts_with('<') {
+            // There is no text after an unclosed tag.
+            idx += html_tag_len(rest)?;
+            continue;
+        }
+
+        if rest.starts_with(escaped_text) {
+            return Some(idx);
+        }
+
+        idx += html_character_reference_len(rest)
+            .unwrap_or_else(|| rest.chars().next().map_or(1, char::len_utf8));
+    }
+
+    None
+}
+
+/// Returns the length of the HTML tag at the start of `html`, including the final `>`.
+///
+/// A `>` inside a quoted attribute value doesn't end the tag. Returns `None` if the tag is not
+/// closed.
+fn html_tag_len(html: &str) -> Option<usize> {
+    let mut quote = None;
+
+    for (idx, c) in html.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '>' => return Some(idx + 1),
+            None => {}
+        }
+    }
+
+    None
+}
+
+/// Returns the length of the HTML character reference at the start of `html`, like `&amp;` or
+/// `&#38;`, if there is one.
+fn html_character_reference_len(html: &str) -> Option<usize> {
+    let name = html.strip_prefix('&')?;
+    let end = name.find(';')?;
+    let is_valid = end > 0 && name[..end].chars().all(|c| c.is_ascii_alphanumeric() || c == '#');
+
+    is_valid.then_some(end + 2)
+}
+
+/// Expands the emoji shortcodes in the given HTML, outside of tags and `<code>` and `<pre>`
+/// elements.
+fn expand_shortcodes_in_html(html: &str, shortcodes: &BTreeMap<String, String>) -> String {
+    let mut expanded = String::with_capacity(html.len());
+    let mut code_depth = 0_usize;
+    let mut rest = html;
+
+    while let Some(tag_start) = rest.find('<') {
+        let text = &rest[..tag_start];
+        if code_depth == 0 {
+            expand_shortcodes_in_words(text, shortcodes, true, &mut expanded);
+        } else {
+            expanded.push_str(text);
+        }
+        rest = &rest[tag_start..];
+
+        let Some(tag_len) = html_tag_len(rest) else {
+            break;
+        };
+        let tag = &rest[..tag_len];
+
+        let (is_closing, name) = match tag[1..].strip_prefix('/') {
+            Some(name) => (true, name),
+            None => (false, &tag[1..]),
+        };
+        let name_len = name.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(name.len());
+        if ["code", "pre"].iter().any(|code_tag| name[..name_len].eq_ignore_ascii_case(code_tag)) {
+            if is_closing {
+                code_depth = code_depth.saturating_sub(1);
+            } else if !tag.ends_with("/>") {
+                code_depth += 1;
+            }
+        }
+
+        expanded.push_str(tag);
+        rest = &rest[tag.len()..];
+    }
+
+    if code_depth == 0 {
+        expand_shortcodes_in_words(rest, shortcodes, true, &mut expanded);
+    } else {
+        expanded.push_str(rest);
+    }
+
+    expanded
+}
+
+/// Expands the emoji shortcodes in the words of the given text that are not URLs, and pushes
+/// the result to `expanded`.
+///
+/// If `is_html` is `true`, the values of the shortcodes are escaped.
+fn expand_shortcodes_in_words(
+    text: &str,
+    shortcodes: &BTreeMap<String, String>,
+    is_html: bool,
+    expanded: &mut String,
+) {
+    let is_shortcode_char = |c: char| c.is_alphanumeric() || matches!(c, '_' | '-' | '+');
+
+    for word in text.split_inclusive(char::is_whitespace) {
+        if word.contains("://") {
+            expanded.push_str(word);
+            continue;
+        }
+
+        let mut rest = word;
+        while let Some(start) = rest.find(':') {
+            expanded.push_str(&rest[..start]);
+            let after_colon = &rest[start + 1..];
+
+            let name_len = after_colon.find(|c| !is_shortcode_char(c)).unwrap_or(after_colon.len());
+            let value = (name_len > 0 && after_colon[name_len..].starts_with(':'))
+                .then(|| shortcodes.get(&after_colon[..name_len]))
+                .flatten();
+
+            match value {
+                Some(value) => {
+                    if is_html {
+                        expanded.push_str(&EscapeHtmlEntities(value).to_string());
+                    } else {
+                        expanded.push_str(value);
+                    }
+                    rest = &after_colon[name_len + 1..];
+                }
+                None => {
+                    // The closing colon of an unknown shortcode can start another one.
+                    expanded.push(':');
+                    rest = after_colon;
+                }
+            }
+        }
+
+        expanded.push_str(rest);
+    }
+}
+
+/// Whether the given character is in a block of a right-to-left script.
+fn is_rtl_script_char(c: char) -> bool {
+    matches!(
+        c,
+        // Hebrew, Arabic, Syriac, Thaana, NKo, Samaritan, Mandaic and their extensions.
+        '\u{0590}'..='\u{08FF}'
+        // Hebrew and Arabic presentation forms.
+        | '\u{FB1D}'..='\u{FDFF}'
+        | '\u{FE70}'..='\u{FEFF}'
+        // Historic right-to-left scripts, Adlam and Arabic mathematical symbols.
+        | '\u{10800}'..='\u{10FFF}'
+        | '\u{1E800}'..='\u{1EFFF}'
+    )
 }
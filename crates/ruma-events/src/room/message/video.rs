@@ -1,10 +1,20 @@
+#[cfg(feature = "unstable-media-hashes")]
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use js_int::UInt;
+#[cfg(feature = "unstable-media-hashes")]
+use ruma_common::serde::Base64;
+#[cfg(feature = "unstable-streaming-manifest")]
+use ruma_common::serde::StringEnum;
 use ruma_common::OwnedMxcUri;
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "unstable-media-tracks")]
+use super::MediaTrack;
 use crate::room::{EncryptedFile, MediaSource, ThumbnailInfo};
+#[cfg(feature = "unstable-streaming-manifest")]
+use crate::PrivOwnedStr;
 
 /// The payload for a video message.
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -49,6 +59,21 @@ impl VideoMessageEventContent {
     pub fn info(self, info: impl Into<Option<Box<VideoInfo>>>) -> Self {
         Self { info: info.into(), ..self }
     }
+
+    /// The source to use to preview this video.
+    ///
+    /// Returns the source of the thumbnail if there is one, otherwise the source of the video.
+    pub fn preview_source(&self) -> &MediaSource {
+        self.info.as_ref().and_then(|info| info.thumbnail_source.as_ref()).unwrap_or(&self.source)
+    }
+
+    /// The manifest to use for adaptive streaming of this video, if any.
+    ///
+    /// Clients that don't support the format of the manifest should play the video from `source`.
+    #[cfg(feature = "unstable-streaming-manifest")]
+    pub fn streaming_manifest(&self) -> Option<&StreamingManifest> {
+        self.info.as_ref()?.streaming_manifest.as_ref()
+    }
 }
 
 /// Metadata about a video.
@@ -98,6 +123,31 @@ pub struct VideoInfo {
     #[cfg(feature = "unstable-msc2448")]
     #[serde(rename = "xyz.amorgan.blurhash", skip_serializing_if = "Option::is_none")]
     pub blurhash: Option<String>,
+
+    /// A map from an algorithm name to a hash of the video file, encoded as unpadded base64.
+    ///
+    /// Like the `hashes` of an [`EncryptedFile`], this allows clients to check the integrity of
+    /// the downloaded media. Clients should support the SHA-256 hash, which uses the key
+    /// `sha256`.
+    ///
+    /// This uses the unstable `io.ruma.hashes` key.
+    #[cfg(feature = "unstable-media-hashes")]
+    #[serde(rename = "io.ruma.hashes", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hashes: BTreeMap<String, Base64>,
+
+    /// The tracks contained in the video file, e.g. the different audio or subtitles tracks.
+    ///
+    /// This uses the unstable `io.ruma.tracks` key.
+    #[cfg(feature = "unstable-media-tracks")]
+    #[serde(rename = "io.ruma.tracks", default, skip_serializing_if = "Vec::is_empty")]
+    pub tracks: Vec<MediaTrack>,
+
+    /// A manifest for adaptive streaming of the video, e.g. with HLS or DASH.
+    ///
+    /// This uses the unstable `io.ruma.streaming_manifest` key.
+    #[cfg(feature = "unstable-streaming-manifest")]
+    #[serde(rename = "io.ruma.streaming_manifest", skip_serializing_if = "Option::is_none")]
+    pub streaming_manifest: Option<StreamingManifest>,
 }
 
 impl VideoInfo {
@@ -105,4 +155,51 @@ impl VideoInfo {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Checks the given video file against the SHA-256 hash in `hashes`.
+    ///
+    /// Returns `None` if there is no SHA-256 hash, otherwise whether the hash of `data` matches.
+    #[cfg(feature = "unstable-media-hashes")]
+    pub fn verify_hashes(&self, data: &[u8]) -> Option<bool> {
+        crate::room::verify_sha256_hash(&self.hashes, data)
+    }
+}
+
+/// A reference to a manifest for adaptive streaming of a video.
+#[cfg(feature = "unstable-streaming-manifest")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct StreamingManifest {
+    /// The format of the manifest.
+    pub format: StreamingManifestFormat,
+
+    /// The source of the manifest file.
+    #[serde(flatten)]
+    pub source: MediaSource,
+}
+
+#[cfg(feature = "unstable-streaming-manifest")]
+impl StreamingManifest {
+    /// Creates a new `StreamingManifest` with the given format and source.
+    pub fn new(format: StreamingManifestFormat, source: MediaSource) -> Self {
+        Self { format, source }
+    }
+}
+
+/// The format of a [`StreamingManifest`].
+#[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/doc/string_enum.md"))]
+#[cfg(feature = "unstable-streaming-manifest")]
+#[derive(Clone, PartialEq, Eq, StringEnum)]
+#[ruma_enum(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum StreamingManifestFormat {
+    /// An HTTP Live Streaming playlist, usually with the `.m3u8` extension.
+    Hls,
+
+    /// A Dynamic Adaptive Streaming over HTTP media presentation description, usually with the
+    /// `.mpd` extension.
+    Dash,
+
+    #[doc(hidden)]
+    _Custom(PrivOwnedStr),
 }
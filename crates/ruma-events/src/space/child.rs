@@ -2,6 +2,8 @@
 //!
 //! [`m.space.child`]: https://spec.matrix.org/latest/client-server-api/#mspacechild
 
+use std::{fmt, str::FromStr};
+
 use ruma_common::{MilliSecondsSinceUnixEpoch, OwnedRoomId, OwnedServerName, OwnedUserId};
 use ruma_macros::{Event, EventContent};
 use serde::{Deserialize, Serialize};
@@ -49,6 +51,91 @@ impl SpaceChildEventContent {
     pub fn new(via: Vec<OwnedServerName>) -> Self {
         Self { via, order: None, suggested: false }
     }
+
+    /// Get the `order` of this child, if it is valid.
+    ///
+    /// Returns `None` if `order` is missing or invalid, since invalid values should be ignored.
+    pub fn order(&self) -> Option<SpaceChildOrder> {
+        self.order.as_deref()?.parse().ok()
+    }
+}
+
+/// A valid `order` of an `m.space.child` event.
+///
+/// It consists of at most 50 ASCII characters in the range `\x20` (space) to `\x7E` (`~`).
+///
+/// Orders are compared with a lexicographic ordering of their characters, as required by the
+/// specification.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SpaceChildOrder(String);
+
+impl SpaceChildOrder {
+    /// The maximum number of characters of a `SpaceChildOrder`.
+    pub const MAX_LENGTH: usize = 50;
+
+    /// Creates a new `SpaceChildOrder` after checking that the given string is valid.
+    pub fn new(order: String) -> Result<Self, SpaceChildOrderError> {
+        if order.len() > Self::MAX_LENGTH {
+            Err(SpaceChildOrderError::TooLong)
+        } else if !order.bytes().all(|b| (0x20..=0x7E).contains(&b)) {
+            Err(SpaceChildOrderError::InvalidCharacter)
+        } else {
+            Ok(Self(order))
+        }
+    }
+
+    /// Get the string representation of this `SpaceChildOrder`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for SpaceChildOrder {
+    type Error = SpaceChildOrderError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl FromStr for SpaceChildOrder {
+    type Err = SpaceChildOrderError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s.to_owned())
+    }
+}
+
+impl From<SpaceChildOrder> for String {
+    fn from(value: SpaceChildOrder) -> Self {
+        value.0
+    }
+}
+
+impl AsRef<str> for SpaceChildOrder {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for SpaceChildOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An error encountered when trying to convert to a `SpaceChildOrder`.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum SpaceChildOrderError {
+    /// The value is longer than [`SpaceChildOrder::MAX_LENGTH`].
+    #[error("value too long")]
+    TooLong,
+
+    /// The value contains a character outside of the printable ASCII range.
+    #[error("value contains a character outside of the printable ASCII range")]
+    InvalidCharacter,
 }
 
 /// An `m.space.child` event represented as a Stripped State Event with an added `origin_server_ts`
@@ -75,7 +162,9 @@ mod tests {
     use ruma_common::{server_name, MilliSecondsSinceUnixEpoch};
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
-    use super::{HierarchySpaceChildEvent, SpaceChildEventContent};
+    use super::{
+        HierarchySpaceChildEvent, SpaceChildEventContent, SpaceChildOrder, SpaceChildOrderError,
+    };
 
     #[test]
     fn space_child_serialization() {
@@ -102,6 +191,49 @@ mod tests {
         assert_eq!(to_json_value(&content).unwrap(), json);
     }
 
+    #[test]
+    fn space_child_order_validation() {
+        assert_eq!("".parse::<SpaceChildOrder>().unwrap().as_str(), "");
+        assert_eq!(" a~Z!".parse::<SpaceChildOrder>().unwrap().as_str(), " a~Z!");
+        assert_eq!("a".repeat(50).parse::<SpaceChildOrder>().unwrap().as_str().len(), 50);
+
+        assert_eq!("a".repeat(51).parse::<SpaceChildOrder>(), Err(SpaceChildOrderError::TooLong));
+        assert_eq!("tab\t".parse::<SpaceChildOrder>(), Err(SpaceChildOrderError::InvalidCharacter));
+        assert_eq!("\x7F".parse::<SpaceChildOrder>(), Err(SpaceChildOrderError::InvalidCharacter));
+        assert_eq!("é".parse::<SpaceChildOrder>(), Err(SpaceChildOrderError::InvalidCharacter));
+
+        from_json_value::<SpaceChildOrder>(json!("ok")).unwrap();
+        from_json_value::<SpaceChildOrder>(json!("not\nok")).unwrap_err();
+        assert_eq!(
+            to_json_value(SpaceChildOrder::new("ok".to_owned()).unwrap()).unwrap(),
+            json!("ok")
+        );
+    }
+
+    #[test]
+    fn space_child_order_comparison() {
+        let mut orders: Vec<SpaceChildOrder> =
+            ["b", "a", "B", "aa", " ", "~", "a0"].iter().map(|o| o.parse().unwrap()).collect();
+        orders.sort();
+
+        assert_eq!(
+            orders.iter().map(SpaceChildOrder::as_str).collect::<Vec<_>>(),
+            [" ", "B", "a", "a0", "aa", "b", "~"]
+        );
+    }
+
+    #[test]
+    fn space_child_content_order() {
+        let mut content = SpaceChildEventContent::new(vec![]);
+        assert_eq!(content.order(), None);
+
+        content.order = Some("abc".to_owned());
+        assert_eq!(content.order().unwrap().as_str(), "abc");
+
+        content.order = Some("\u{1F980}".to_owned());
+        assert_eq!(content.order(), None);
+    }
+
     #[test]
     fn hierarchy_space_child_deserialization() {
         let json = json!({
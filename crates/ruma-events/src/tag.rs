@@ -2,7 +2,7 @@
 //!
 //! [`m.tag`]: https://spec.matrix.org/latest/client-server-api/#mtag
 
-use std::{collections::BTreeMap, error::Error, fmt, str::FromStr};
+use std::{cmp::Ordering, collections::BTreeMap, error::Error, fmt, str::FromStr};
 
 #[cfg(feature = "compat-tag-info")]
 use ruma_common::serde::deserialize_as_optional_f64_or_string;
@@ -115,6 +115,40 @@ impl TagName {
             _ => &self.as_ref()[2..],
         }
     }
+
+    /// Returns the rooms that have this tag, sorted by their tag order.
+    ///
+    /// `rooms` is a list of room keys associated with the content of the `m.tag` event of the
+    /// room. The room key is used as a secondary sort key, to be able to sort rooms with the same
+    /// order, or without order, predictably. It can be the room ID or a tuple with the display
+    /// name of the room and its room ID, for example.
+    ///
+    /// Rooms are sorted by ascending `order`, and rooms without `order` are sorted after all the
+    /// rooms with an `order`, [as recommended in the Matrix specification]. Rooms that don't have
+    /// this tag are ignored.
+    ///
+    /// [as recommended in the Matrix specification]: https://spec.matrix.org/latest/client-server-api/#room-tagging
+    pub fn sort_rooms<'a, K: Ord>(
+        &self,
+        rooms: impl IntoIterator<Item = (K, &'a TagEventContent)>,
+    ) -> Vec<K> {
+        let mut tagged_rooms: Vec<_> = rooms
+            .into_iter()
+            .filter_map(|(key, content)| Some((content.tags.get(self)?.order, key)))
+            .collect();
+
+        tagged_rooms.sort_by(|(order_a, key_a), (order_b, key_b)| {
+            let order = match (order_a, order_b) {
+                (Some(a), Some(b)) => a.total_cmp(b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            };
+            order.then_with(|| key_a.cmp(key_b))
+        });
+
+        tagged_rooms.into_iter().map(|(_, key)| key).collect()
+    }
 }
 
 impl AsRef<str> for TagName {
@@ -261,4 +295,41 @@ mod tests {
         assert_eq!(TagName::from("rs.conduit.rules").display_name(), "rules");
         assert_eq!(TagName::from("Play").display_name(), "Play");
     }
+
+    #[test]
+    fn sort_rooms() {
+        let tags = |tags: &[(TagName, Option<f64>)]| {
+            TagEventContent::new(
+                tags.iter()
+                    .map(|(name, order)| (name.clone(), TagInfo { order: *order }))
+                    .collect(),
+            )
+        };
+
+        let work = TagName::from("u.work");
+        let rooms = [
+            ("!d:example.org", tags(&[(TagName::Favorite, None)])),
+            ("!b:example.org", tags(&[(TagName::Favorite, Some(0.5))])),
+            ("!e:example.org", tags(&[(TagName::Favorite, Some(0.2)), (work.clone(), Some(0.1))])),
+            ("!a:example.org", tags(&[(TagName::Favorite, None), (work.clone(), None)])),
+            ("!c:example.org", tags(&[(TagName::Favorite, Some(0.5))])),
+            ("!f:example.org", tags(&[(TagName::LowPriority, Some(0.0))])),
+            ("!g:example.org", tags(&[])),
+        ];
+        let rooms = || rooms.iter().map(|(room_id, content)| (*room_id, content));
+
+        assert_eq!(
+            TagName::Favorite.sort_rooms(rooms()),
+            [
+                "!e:example.org",
+                "!b:example.org",
+                "!c:example.org",
+                "!a:example.org",
+                "!d:example.org"
+            ]
+        );
+        assert_eq!(work.sort_rooms(rooms()), ["!e:example.org", "!a:example.org"]);
+        assert_eq!(TagName::LowPriority.sort_rooms(rooms()), ["!f:example.org"]);
+        assert!(TagName::ServerNotice.sort_rooms(rooms()).is_empty());
+    }
 }
@@ -0,0 +1,56 @@
+//! Bounded `Deserialize` implementation for audio waveforms.
+
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{IgnoredAny, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+
+/// The maximum number of samples of a waveform that are kept during deserialization.
+///
+/// The MSCs recommend between 30 and 120 samples, so this is very generous. Any sample past this
+/// limit is ignored to avoid huge allocations with untrusted input.
+pub(crate) const MAX_WAVEFORM_LEN: usize = 1024;
+
+/// Deserialize a waveform, ignoring any sample past [`MAX_WAVEFORM_LEN`].
+pub(crate) fn deserialize<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    struct WaveformVisitor<T>(PhantomData<T>);
+
+    impl<'de, T> Visitor<'de> for WaveformVisitor<T>
+    where
+        T: Deserialize<'de>,
+    {
+        type Value = Vec<T>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a sequence of amplitudes")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let capacity = seq.size_hint().unwrap_or(0).min(MAX_WAVEFORM_LEN);
+            let mut waveform = Vec::with_capacity(capacity);
+
+            while waveform.len() < MAX_WAVEFORM_LEN {
+                match seq.next_element()? {
+                    Some(amplitude) => waveform.push(amplitude),
+                    None => return Ok(waveform),
+                }
+            }
+
+            // Consume the remaining samples without storing them.
+            while seq.next_element::<IgnoredAny>()?.is_some() {}
+
+            Ok(waveform)
+        }
+    }
+
+    deserializer.deserialize_seq(WaveformVisitor(PhantomData))
+}
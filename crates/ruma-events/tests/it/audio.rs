@@ -229,6 +229,106 @@ fn plain_content_deserialization() {
     assert_eq!(audio_details.waveform.len(), 52);
 }
 
+#[cfg(feature = "unstable-audio-loudness")]
+#[test]
+fn loudness_round_trip() {
+    use js_int::int;
+    use ruma_events::room::message::{AudioInfo, AudioMessageEventContent};
+
+    let mut info = AudioInfo::new();
+    info.loudness = Some(int!(-1650));
+    let content = AudioMessageEventContent::plain(
+        "Voice message".to_owned(),
+        mxc_uri!("mxc://notareal.hs/abcdef").to_owned(),
+    )
+    .info(Box::new(info));
+
+    let json = to_json_value(&content).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "body": "Voice message",
+            "msgtype": "m.audio",
+            "url": "mxc://notareal.hs/abcdef",
+            "info": {
+                "io.ruma.loudness": -1650,
+            },
+        })
+    );
+
+    let content = from_json_value::<AudioMessageEventContent>(json).unwrap();
+    assert_eq!(content.info.unwrap().loudness, Some(int!(-1650)));
+
+    // Floats are not allowed in canonical JSON.
+    from_json_value::<AudioInfo>(json!({ "io.ruma.loudness": -16.5 })).unwrap_err();
+
+    let info = from_json_value::<AudioInfo>(json!({})).unwrap();
+    assert_eq!(info.loudness, None);
+}
+
+#[cfg(all(feature = "unstable-audio-loudness", feature = "unstable-msc3245-v1-compat"))]
+#[test]
+fn unstable_loudness_round_trip() {
+    use js_int::int;
+    use ruma_events::room::message::UnstableAudioDetailsContentBlock;
+
+    let mut audio = UnstableAudioDetailsContentBlock::new(Duration::from_millis(14_000), vec![]);
+    audio.loudness = Some(int!(-2300));
+
+    let json = to_json_value(&audio).unwrap();
+    assert_eq!(json, json!({ "duration": 14_000, "io.ruma.loudness": -2300 }));
+
+    let audio = from_json_value::<UnstableAudioDetailsContentBlock>(json).unwrap();
+    assert_eq!(audio.duration, Duration::from_millis(14_000));
+    assert_eq!(audio.loudness, Some(int!(-2300)));
+}
+
+#[cfg(feature = "unstable-msc3246")]
+#[test]
+fn huge_waveform_deserialization() {
+    let json_data = json!({
+        "org.matrix.msc1767.text": [
+            { "body": "Upload: my_new_song.webm" },
+        ],
+        "org.matrix.msc1767.file": {
+            "url": "mxc://notareal.hs/abcdef",
+            "name": "my_new_song.webm",
+        },
+        "org.matrix.msc1767.audio_details": {
+            "duration": 14,
+            "org.matrix.msc3246.waveform": vec![json!(2000); 100_000],
+        },
+    });
+
+    let content = from_json_value::<AudioEventContent>(json_data).unwrap();
+    let audio_details = content.audio_details.unwrap();
+    assert_eq!(audio_details.waveform.len(), 1024);
+    assert!(audio_details
+        .waveform
+        .iter()
+        .all(|amplitude| amplitude.get() == Amplitude::MAX.into()));
+}
+
+#[cfg(feature = "unstable-msc3246")]
+#[test]
+fn invalid_waveform_deserialization() {
+    let json_data = json!({
+        "org.matrix.msc1767.text": [
+            { "body": "Upload: my_new_song.webm" },
+        ],
+        "org.matrix.msc1767.file": {
+            "url": "mxc://notareal.hs/abcdef",
+            "name": "my_new_song.webm",
+        },
+        "org.matrix.msc1767.audio_details": {
+            "duration": 14,
+            "org.matrix.msc3246.waveform": [13, -34, "loud"],
+        },
+    });
+
+    from_json_value::<AudioEventContent>(json_data).unwrap_err();
+}
+
 #[test]
 fn encrypted_content_deserialization() {
     let json_data = json!({
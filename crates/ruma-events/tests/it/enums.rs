@@ -1,6 +1,10 @@
 use assert_matches2::assert_matches;
-use js_int::int;
-use ruma_common::{room_alias_id, serde::test::serde_json_eq};
+use js_int::{int, uint};
+use ruma_common::{
+    room_alias_id,
+    serde::{test::serde_json_eq, Raw},
+    MilliSecondsSinceUnixEpoch,
+};
 use ruma_events::{
     room::{
         aliases::RoomAliasesEventContent,
@@ -253,6 +257,78 @@ fn alias_event_field_access() {
     assert_eq!(deser.event_type().to_string(), "m.room.aliases");
 }
 
+#[test]
+fn timeline_events_mixed_deserialization() {
+    let custom_message_like_event = json!({
+        "content": {
+            "foo": "bar",
+        },
+        "event_id": "$custom_message:localhost",
+        "origin_server_ts": 2,
+        "sender": "@other:localhost",
+        "room_id": "!room:room.com",
+        "type": "dev.ruma.custom",
+    });
+    let custom_state_event = json!({
+        "content": {
+            "foo": "bar",
+        },
+        "event_id": "$custom_state:localhost",
+        "origin_server_ts": 3,
+        "sender": "@example:localhost",
+        "state_key": "",
+        "room_id": "!room:room.com",
+        "type": "dev.ruma.custom_state",
+    });
+    let invalid_message_event = json!({
+        "content": {
+            "msgtype": "m.text",
+        },
+        "event_id": "$invalid:localhost",
+        "origin_server_ts": 4,
+        "sender": "@example:localhost",
+        "room_id": "!room:room.com",
+        "type": "m.room.message",
+    });
+
+    let timeline: Vec<Raw<AnyTimelineEvent>> = from_json_value(json!([
+        message_event(),
+        aliases_event(),
+        custom_message_like_event,
+        custom_state_event,
+        invalid_message_event,
+    ]))
+    .unwrap();
+    let events = timeline.iter().map(|raw| raw.deserialize()).collect::<Vec<_>>();
+
+    assert_matches!(
+        &events[0],
+        Ok(AnyTimelineEvent::MessageLike(AnyMessageLikeEvent::RoomMessage(_)))
+    );
+    assert_matches!(&events[1], Ok(AnyTimelineEvent::State(AnyStateEvent::RoomAliases(_))));
+
+    assert_matches!(&events[2], Ok(event @ AnyTimelineEvent::MessageLike(_)));
+    assert_eq!(event.event_type().to_string(), "dev.ruma.custom");
+    assert_eq!(event.event_id(), "$custom_message:localhost");
+    assert_eq!(event.sender(), "@other:localhost");
+    assert_eq!(event.origin_server_ts(), MilliSecondsSinceUnixEpoch(uint!(2)));
+
+    assert_matches!(&events[3], Ok(event @ AnyTimelineEvent::State(_)));
+    assert_eq!(event.event_type().to_string(), "dev.ruma.custom_state");
+    assert_eq!(event.event_id(), "$custom_state:localhost");
+    assert_eq!(event.sender(), "@example:localhost");
+    assert_eq!(event.origin_server_ts(), MilliSecondsSinceUnixEpoch(uint!(3)));
+
+    // An invalid known event doesn't prevent the other events from being deserialized, and its
+    // fields can still be accessed from the raw JSON.
+    events[4].as_ref().unwrap_err();
+    assert_eq!(timeline[4].get_field::<String>("type").unwrap().as_deref(), Some("m.room.message"));
+    assert_eq!(
+        timeline[4].get_field::<String>("event_id").unwrap().as_deref(),
+        Some("$invalid:localhost")
+    );
+}
+
 #[test]
 fn ephemeral_event_deserialization() {
     let json_data = json!({
@@ -1,11 +1,13 @@
 use assert_matches2::assert_matches;
 use js_int::uint;
 use maplit::btreemap;
-use ruma_common::{event_id, owned_event_id, owned_user_id, user_id, MilliSecondsSinceUnixEpoch};
+use ruma_common::{
+    event_id, owned_event_id, owned_user_id, serde::Raw, user_id, MilliSecondsSinceUnixEpoch,
+};
 use ruma_events::{
     receipt::{Receipt, ReceiptEventContent, ReceiptType},
     typing::TypingEventContent,
-    AnyEphemeralRoomEvent,
+    AnyEphemeralRoomEvent, AnySyncEphemeralRoomEvent, EphemeralRoomEventType,
 };
 use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
@@ -94,3 +96,53 @@ fn deserialize_ephemeral_receipt() {
     let user_receipt = type_receipts.get(user_id).unwrap();
     assert_eq!(user_receipt.ts, Some(MilliSecondsSinceUnixEpoch(uint!(1))));
 }
+
+#[test]
+fn sync_ephemeral_events_round_trip() {
+    let json_data = json!([
+        {
+            "content": {
+                "user_ids": ["@alice:example.com", "@bob:example.com"],
+            },
+            "type": "m.typing",
+        },
+        {
+            "content": {
+                "$h29iv0s8:example.com": {
+                    "m.read": {
+                        "@carl:example.com": { "ts": 1 },
+                    },
+                },
+            },
+            "type": "m.receipt",
+        },
+        {
+            "content": {
+                "status": "thinking",
+            },
+            "type": "dev.ruma.custom",
+        },
+    ]);
+
+    let raw_events =
+        from_json_value::<Vec<Raw<AnySyncEphemeralRoomEvent>>>(json_data.clone()).unwrap();
+    let events = raw_events.iter().map(|raw| raw.deserialize().unwrap()).collect::<Vec<_>>();
+
+    assert_eq!(
+        events.iter().map(AnySyncEphemeralRoomEvent::event_type).collect::<Vec<_>>(),
+        [EphemeralRoomEventType::Typing, EphemeralRoomEventType::Receipt, "dev.ruma.custom".into(),]
+    );
+
+    assert_matches!(&events[0], AnySyncEphemeralRoomEvent::Typing(typing_event));
+    assert_eq!(typing_event.content.user_ids, ["@alice:example.com", "@bob:example.com"]);
+
+    assert_matches!(&events[1], AnySyncEphemeralRoomEvent::Receipt(receipt_event));
+    let (event_id, receipt) = receipt_event
+        .content
+        .user_receipt(user_id!("@carl:example.com"), ReceiptType::Read)
+        .unwrap();
+    assert_eq!(event_id, "$h29iv0s8:example.com");
+    assert_eq!(receipt.ts, Some(MilliSecondsSinceUnixEpoch(uint!(1))));
+
+    assert_eq!(to_json_value(&raw_events).unwrap(), json_data);
+}
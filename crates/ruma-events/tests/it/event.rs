@@ -7,4 +7,5 @@ fn ui() {
     t.pass("tests/it/ui/04-event-sanity-check.rs");
     t.compile_fail("tests/it/ui/05-named-fields.rs");
     t.compile_fail("tests/it/ui/06-no-content-field.rs");
+    t.compile_fail("tests/it/ui/14-wrong-state-key-type.rs");
 }
@@ -24,6 +24,7 @@ use ruma_events::{
             ReplacementUnstablePollStartEventContent, UnstablePollAnswer,
             UnstablePollStartContentBlock, UnstablePollStartEventContent,
         },
+        PollSelectionsError,
     },
     relation::Reference,
     room::message::{Relation, RelationWithoutReplacement},
@@ -254,6 +255,56 @@ fn response_content_serialization() {
     );
 }
 
+#[test]
+fn response_content_for_poll() {
+    let poll = PollContentBlock::new(
+        TextContentBlock::plain("How's the weather?"),
+        vec![
+            PollAnswer::new("not-bad".to_owned(), TextContentBlock::plain("Not bad…")),
+            PollAnswer::new("fine".to_owned(), TextContentBlock::plain("Fine.")),
+            PollAnswer::new("amazing".to_owned(), TextContentBlock::plain("Amazing!")),
+        ]
+        .try_into()
+        .unwrap(),
+    );
+    assert_eq!(
+        poll.clone().with_max_selections(uint!(0)).unwrap_err(),
+        PollSelectionsError::ZeroMaxSelections
+    );
+    let poll = poll.with_max_selections(uint!(2)).unwrap();
+    assert_eq!(poll.max_selections, uint!(2));
+
+    let poll_start_id = owned_event_id!("$related_event:notareal.hs");
+
+    let content = PollResponseEventContent::for_poll(
+        &poll,
+        vec!["fine".to_owned(), "amazing".to_owned()].into(),
+        poll_start_id.clone(),
+    )
+    .unwrap();
+    assert_eq!(*content.selections, ["fine", "amazing"]);
+    assert_eq!(content.relates_to.event_id, poll_start_id);
+
+    assert_eq!(
+        PollResponseEventContent::for_poll(
+            &poll,
+            vec!["fine".to_owned(), "terrible".to_owned()].into(),
+            poll_start_id.clone(),
+        )
+        .unwrap_err(),
+        PollSelectionsError::UnknownAnswer("terrible".to_owned())
+    );
+    assert_eq!(
+        PollResponseEventContent::for_poll(
+            &poll,
+            vec!["not-bad".to_owned(), "fine".to_owned(), "amazing".to_owned()].into(),
+            poll_start_id,
+        )
+        .unwrap_err(),
+        PollSelectionsError::TooManySelections
+    );
+}
+
 #[test]
 fn response_content_other_serialization() {
     let content = PollResponseEventContent::new(
@@ -610,6 +661,52 @@ fn unstable_response_content_serialization() {
     );
 }
 
+#[test]
+fn unstable_response_content_for_poll() {
+    let poll = UnstablePollStartContentBlock::new(
+        "How's the weather?",
+        vec![
+            UnstablePollAnswer::new("not-bad", "Not bad…"),
+            UnstablePollAnswer::new("fine", "Fine."),
+        ]
+        .try_into()
+        .unwrap(),
+    );
+    assert_eq!(
+        poll.clone().with_max_selections(uint!(0)).unwrap_err(),
+        PollSelectionsError::ZeroMaxSelections
+    );
+
+    let poll_start_id = owned_event_id!("$related_event:notareal.hs");
+
+    let content = UnstablePollResponseEventContent::for_poll(
+        &poll,
+        vec!["fine".to_owned()],
+        poll_start_id.clone(),
+    )
+    .unwrap();
+    assert_eq!(content.poll_response.answers, ["fine"]);
+
+    assert_eq!(
+        UnstablePollResponseEventContent::for_poll(
+            &poll,
+            vec!["unknown".to_owned()],
+            poll_start_id.clone(),
+        )
+        .unwrap_err(),
+        PollSelectionsError::UnknownAnswer("unknown".to_owned())
+    );
+    assert_eq!(
+        UnstablePollResponseEventContent::for_poll(
+            &poll,
+            vec!["not-bad".to_owned(), "fine".to_owned()],
+            poll_start_id,
+        )
+        .unwrap_err(),
+        PollSelectionsError::TooManySelections
+    );
+}
+
 #[test]
 fn unstable_response_event_deserialization() {
     let json_data = json!({
@@ -248,6 +248,65 @@ fn thread_unstable_deserialize() {
     assert!(!thread.is_falling_back);
 }
 
+#[test]
+fn thread_unstable_show_reply_deserialize() {
+    let json = json!({
+        "msgtype": "m.text",
+        "body": "<text msg>",
+        "m.relates_to": {
+            "rel_type": "io.element.thread",
+            "event_id": "$1598361704261elfgc",
+            "io.element.show_reply": true,
+            "m.in_reply_to": {
+                "event_id": "$latesteventid",
+            },
+        },
+    });
+
+    assert_matches!(
+        from_json_value::<RoomMessageEventContent>(json),
+        Ok(RoomMessageEventContent {
+            msgtype: MessageType::Text(_),
+            relates_to: Some(Relation::Thread(thread)),
+            ..
+        })
+    );
+    assert_eq!(thread.event_id, "$1598361704261elfgc");
+    assert_eq!(thread.in_reply_to.unwrap().event_id, "$latesteventid");
+    assert!(thread.is_falling_back);
+}
+
+#[test]
+fn in_thread_round_trip() {
+    let content = RoomMessageEventContent::text_plain("<text msg>")
+        .in_thread(owned_event_id!("$1598361704261elfgc"), owned_event_id!("$latesteventid"));
+
+    let json = to_json_value(&content).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "msgtype": "m.text",
+            "body": "<text msg>",
+            "m.relates_to": {
+                "rel_type": "m.thread",
+                "event_id": "$1598361704261elfgc",
+                "is_falling_back": true,
+                "m.in_reply_to": {
+                    "event_id": "$latesteventid",
+                },
+            },
+        })
+    );
+
+    assert_matches!(
+        from_json_value::<RoomMessageEventContent>(json),
+        Ok(RoomMessageEventContent { relates_to: Some(Relation::Thread(thread)), .. })
+    );
+    assert_eq!(thread.event_id, "$1598361704261elfgc");
+    assert_eq!(thread.in_reply_to.unwrap().event_id, "$latesteventid");
+    assert!(thread.is_falling_back);
+}
+
 #[test]
 fn custom_deserialize() {
     let json = json!({
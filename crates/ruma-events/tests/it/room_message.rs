@@ -1,4 +1,8 @@
-use std::{borrow::Cow, collections::BTreeSet};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    time::Duration,
+};
 
 use assert_matches2::assert_matches;
 use js_int::uint;
@@ -9,19 +13,25 @@ use ruma_common::{
 };
 use ruma_events::{
     key::verification::VerificationMethod,
+    relation::InReplyTo,
     room::{
         message::{
-            AddMentions, AudioMessageEventContent, EmoteMessageEventContent,
-            FileMessageEventContent, ForwardThread, ImageMessageEventContent,
-            KeyVerificationRequestEventContent, MessageType, OriginalRoomMessageEvent,
-            OriginalSyncRoomMessageEvent, Relation, ReplyWithinThread, RoomMessageEventContent,
-            TextMessageEventContent, VideoMessageEventContent,
+            is_system_notice, AddMentions, AudioInfo, AudioMessageEventContent, Direction,
+            EmoteMessageEventContent, FileInfo, FileMessageEventContent, ForwardThread,
+            ImageMessageEventContent, KeyVerificationRequestEventContent, MediaMessage,
+            MessageFormat, MessageType, OriginalRoomMessageEvent, OriginalSyncRoomMessageEvent,
+            Relation, ReplacementMetadata, ReplyWithinThread, RoomMessageEventContent,
+            RoomMessageEventContentWithoutRelation, ServerNoticeMessageEventContent,
+            ServerNoticeType, TextMessageEventContent, VideoInfo, VideoMessageEventContent,
         },
-        EncryptedFileInit, JsonWebKeyInit, MediaSource,
+        EncryptedFileInit, ImageInfo, JsonWebKeyInit, MediaSource,
     },
+    tag::{TagInfo, TagName, Tags},
     AnySyncTimelineEvent, Mentions, MessageLikeUnsigned,
 };
-use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+use serde_json::{
+    from_value as from_json_value, json, to_value as to_json_value, Value as JsonValue,
+};
 
 macro_rules! json_object {
     ( $($tt:tt)+ ) => {
@@ -228,11 +238,238 @@ fn markdown_options() {
         </table>\n"
     );
 
+    // Tables with alignment
+    let formatted_body = FormattedBody::markdown(
+        "|left|center|right|\n\
+        |:---|:---:|---:|\n\
+        |1|2|3|\
+        ",
+    );
+    assert_eq!(
+        formatted_body.unwrap().body,
+        "<table>\
+            <thead><tr>\
+                <th style=\"text-align: left\">left</th>\
+                <th style=\"text-align: center\">center</th>\
+                <th style=\"text-align: right\">right</th>\
+            </tr></thead>\
+            <tbody>\n<tr>\
+                <td style=\"text-align: left\">1</td>\
+                <td style=\"text-align: center\">2</td>\
+                <td style=\"text-align: right\">3</td>\
+            </tr>\n</tbody>\
+        </table>\n"
+    );
+
+    // Malformed tables
+    let formatted_body = FormattedBody::markdown(
+        "|head1|head2|\n\
+        |body1|body2|\
+        ",
+    );
+    assert_matches!(formatted_body, None);
+    let formatted_body = FormattedBody::markdown(
+        "|head1|head2|\n\
+        |---|---|\n\
+        |body1|body2|body3|\n\
+        |body4|\
+        ",
+    );
+    assert_eq!(
+        formatted_body.unwrap().body,
+        "<table>\
+            <thead><tr><th>head1</th><th>head2</th></tr></thead>\
+            <tbody>\n\
+                <tr><td>body1</td><td>body2</td></tr>\n\
+                <tr><td>body4</td><td></td></tr>\n\
+            </tbody>\
+        </table>\n"
+    );
+
     // Strikethrough
     let formatted_body = FormattedBody::markdown("A message with a ~~strike~~");
     assert_eq!(formatted_body.unwrap().body, "<p>A message with a <del>strike</del></p>\n");
 }
 
+#[test]
+#[cfg(feature = "markdown")]
+fn markdown_code_block_language() {
+    use ruma_events::room::message::FormattedBody;
+
+    fn code_block_html(markdown: &str) -> String {
+        FormattedBody::markdown(markdown).unwrap().body
+    }
+
+    // Valid languages are kept.
+    assert_eq!(
+        code_block_html("```rust\nfn main() {}\n```"),
+        "<pre><code class=\"language-rust\">fn main() {}\n</code></pre>\n"
+    );
+    assert_eq!(
+        code_block_html("```c++\nint x;\n```"),
+        "<pre><code class=\"language-c++\">int x;\n</code></pre>\n"
+    );
+    assert_eq!(
+        code_block_html("```c#\nint x;\n```"),
+        "<pre><code class=\"language-c#\">int x;\n</code></pre>\n"
+    );
+    assert_eq!(
+        code_block_html("```objective-c\nint x;\n```"),
+        "<pre><code class=\"language-objective-c\">int x;\n</code></pre>\n"
+    );
+
+    // Only the first word of the info string is used.
+    assert_eq!(
+        code_block_html("```python title=\"main.py\"\nprint()\n```"),
+        "<pre><code class=\"language-python\">print()\n</code></pre>\n"
+    );
+
+    // No language.
+    assert_eq!(code_block_html("```\nplain\n```"), "<pre><code>plain\n</code></pre>\n");
+
+    // Invalid languages are dropped.
+    assert_eq!(
+        code_block_html("```\"onclick=\"alert()\nplain\n```"),
+        "<pre><code>plain\n</code></pre>\n"
+    );
+    assert_eq!(code_block_html("~~~<b>\nplain\n~~~"), "<pre><code>plain\n</code></pre>\n");
+    assert_eq!(code_block_html("```rust{1,3}\nplain\n```"), "<pre><code>plain\n</code></pre>\n");
+}
+
+fn split_bodies(content: &RoomMessageEventContent, max_bytes: usize) -> Vec<String> {
+    content
+        .split_for_event_limit(max_bytes)
+        .into_iter()
+        .map(|part| {
+            assert_matches!(part.msgtype, MessageType::Text(text));
+            assert_matches!(text.formatted, None);
+            assert!(text.body.len() <= max_bytes, "{:?} is longer than {max_bytes}", text.body);
+            text.body
+        })
+        .collect()
+}
+
+#[test]
+fn split_for_event_limit_short_message() {
+    let content = RoomMessageEventContent::text_html("Some *text*", "Some <em>text</em>");
+
+    let parts = content.split_for_event_limit(100);
+    assert_eq!(parts.len(), 1);
+    assert_eq!(to_json_value(&parts[0]).unwrap(), to_json_value(&content).unwrap());
+
+    // Other message types are never split.
+    let content = RoomMessageEventContent::emote_plain("waves at everyone in the room");
+    let parts = content.split_for_event_limit(10);
+    assert_eq!(parts.len(), 1);
+    assert_matches!(&parts[0].msgtype, MessageType::Emote(emote));
+    assert_eq!(emote.body, "waves at everyone in the room");
+}
+
+#[test]
+fn split_for_event_limit_lines_and_words() {
+    // Split between lines.
+    let content = RoomMessageEventContent::text_plain("first line\nsecond line\nthird line");
+    assert_eq!(split_bodies(&content, 24), ["first line\nsecond line", "third line"]);
+
+    // Empty lines are kept.
+    let content = RoomMessageEventContent::text_plain("\nfirst line\n\nsecond line\n");
+    assert_eq!(split_bodies(&content, 13), ["\nfirst line\n", "second line\n"]);
+
+    // A single line that is too long is split between words.
+    let content =
+        RoomMessageEventContent::text_plain("The quick brown fox jumps over the lazy dog");
+    assert_eq!(split_bodies(&content, 16), ["The quick brown ", "fox jumps over ", "the lazy dog"]);
+
+    // A word that is too long is split between characters.
+    let content = RoomMessageEventContent::text_plain("Look: ééééé");
+    assert_eq!(split_bodies(&content, 6), ["Look: ", "ééé", "éé"]);
+}
+
+#[test]
+fn split_for_event_limit_code_block() {
+    let content =
+        RoomMessageEventContent::text_plain("Look:\n```rust\nlet a = 1;\nlet b = 2;\n```\nDone");
+    assert_eq!(
+        split_bodies(&content, 30),
+        ["Look:\n```rust\nlet a = 1;\n```", "```rust\nlet b = 2;\n```\nDone"]
+    );
+
+    // The fence is closed with the same marker.
+    let content = RoomMessageEventContent::text_plain("~~~~\nlet a = 1;\nlet b = 2;\n~~~~");
+    assert_eq!(split_bodies(&content, 20), ["~~~~\nlet a = 1;\n~~~~", "~~~~\nlet b = 2;\n~~~~"]);
+
+    // The info string is dropped when the opening line doesn't fit in a part with the code.
+    let content =
+        RoomMessageEventContent::text_plain("```rust,ignore,no_run\nlet a = 1;\nlet b = 2;\n```");
+    assert_eq!(
+        split_bodies(&content, 20),
+        ["```rust,ignore,n", "o_run\nlet a = 1;\n```", "```\nlet b = 2;\n```"]
+    );
+
+    // The code block is not reopened when even the marker doesn't fit.
+    let content = RoomMessageEventContent::text_plain("```\nlet a = 1;\nlet b = 2;\n```");
+    assert_eq!(split_bodies(&content, 8), ["```", "let a = ", "1;", "let b = ", "2;\n```"]);
+}
+
+#[test]
+fn split_for_event_limit_metadata() {
+    let mut content = RoomMessageEventContent::notice_plain("first line\nsecond line")
+        .set_mentions(Mentions::with_user_ids([owned_user_id!("@alice:localhost")]));
+    content.relates_to =
+        Some(Relation::Reply { in_reply_to: InReplyTo::new(owned_event_id!("$replied")) });
+
+    let parts = content.split_for_event_limit(12);
+    assert_eq!(parts.len(), 2);
+    assert_matches!(&parts[0].msgtype, MessageType::Notice(notice));
+    assert_eq!(notice.body, "first line");
+    assert_matches!(&parts[0].relates_to, Some(Relation::Reply { .. }));
+    assert!(parts[0].mentions.is_some());
+    assert_matches!(&parts[1].msgtype, MessageType::Notice(notice));
+    assert_eq!(notice.body, "second line");
+    assert_matches!(&parts[1].relates_to, None);
+    assert_matches!(&parts[1].mentions, None);
+
+    // A thread relation is kept in all parts.
+    let content = RoomMessageEventContent::text_plain("first line\nsecond line")
+        .in_thread(owned_event_id!("$root"), owned_event_id!("$latest"));
+    let parts = content.split_for_event_limit(12);
+    assert_eq!(parts.len(), 2);
+    for part in parts {
+        assert_matches!(part.relates_to, Some(Relation::Thread(thread)));
+        assert_eq!(thread.event_id, "$root");
+    }
+}
+
+#[test]
+fn from_slash_command() {
+    let content = RoomMessageEventContent::from_slash_command("/me waves at everyone");
+    assert_matches!(content.msgtype, MessageType::Emote(emote));
+    assert_eq!(emote.body, "waves at everyone");
+    assert_matches!(emote.formatted, None);
+
+    let content = RoomMessageEventContent::from_slash_command("Just a message");
+    assert_matches!(content.msgtype, MessageType::Text(text));
+    assert_eq!(text.body, "Just a message");
+
+    // No text after the command.
+    let content = RoomMessageEventContent::from_slash_command("/me");
+    assert_matches!(content.msgtype, MessageType::Text(text));
+    assert_eq!(text.body, "/me");
+    let content = RoomMessageEventContent::from_slash_command("/me   ");
+    assert_matches!(content.msgtype, MessageType::Text(text));
+    assert_eq!(text.body, "/me   ");
+
+    // Not the command.
+    let content = RoomMessageEventContent::from_slash_command("/meet me there");
+    assert_matches!(content.msgtype, MessageType::Text(text));
+    assert_eq!(text.body, "/meet me there");
+
+    // Escaped command.
+    let content = RoomMessageEventContent::from_slash_command("//me is a command");
+    assert_matches!(content.msgtype, MessageType::Text(text));
+    assert_eq!(text.body, "/me is a command");
+}
+
 #[test]
 fn verification_request_msgtype_deserialization() {
     let user_id = user_id!("@example2:localhost");
@@ -348,6 +585,72 @@ fn escape_tags_in_plain_reply_body() {
     );
 }
 
+#[test]
+#[cfg(feature = "html")]
+fn reply_to_multiline_emote_reply() {
+    let mut content = RoomMessageEventContent::emote_html(
+        "> <@bob:example.org> Who wants <cake>?\n\nraises a hand\nand a fork",
+        "<mx-reply><blockquote>\
+            <a href=\"https://matrix.to/#/!testroomid:example.org/$bob_event:example.org\">In reply to</a> \
+            <a href=\"https://matrix.to/#/@bob:example.org\">@bob:example.org</a>\
+            <br>Who wants &lt;cake&gt;?\
+        </blockquote></mx-reply>\
+        raises a hand<br>and a <em>fork</em>",
+    );
+    content.relates_to = Some(Relation::Reply {
+        in_reply_to: InReplyTo::new(owned_event_id!("$bob_event:example.org")),
+    });
+    let first_message = OriginalRoomMessageEvent {
+        content,
+        event_id: owned_event_id!("$143273582443PhrSn:example.org"),
+        origin_server_ts: MilliSecondsSinceUnixEpoch(uint!(10_000)),
+        room_id: owned_room_id!("!testroomid:example.org"),
+        sender: owned_user_id!("@user:example.org"),
+        unsigned: MessageLikeUnsigned::default(),
+    };
+
+    let second_message = RoomMessageEventContent::text_plain("Enjoy & share!").make_reply_to(
+        &first_message,
+        ForwardThread::Yes,
+        AddMentions::No,
+    );
+
+    assert_matches!(
+        second_message.msgtype,
+        MessageType::Text(TextMessageEventContent { body, formatted, .. })
+    );
+    // The nested fallback is removed and each line of the original is quoted.
+    assert_eq!(
+        body,
+        "\
+        > * <@user:example.org> raises a hand\n\
+        > and a fork\n\
+        \n\
+        Enjoy & share!\
+        "
+    );
+    let formatted = formatted.unwrap();
+    assert_eq!(
+        formatted.body,
+        "\
+        <mx-reply>\
+            <blockquote>\
+                <a href=\"https://matrix.to/#/!testroomid:example.org/$143273582443PhrSn:example.org\">In reply to</a> \
+                * <a href=\"https://matrix.to/#/@user:example.org\">@user:example.org</a>\
+                <br>\
+                raises a hand<br>and a <em>fork</em>\
+            </blockquote>\
+        </mx-reply>\
+        Enjoy &amp; share!\
+        "
+    );
+    assert_matches!(
+        second_message.relates_to,
+        Some(Relation::Reply { in_reply_to: InReplyTo { event_id, .. } })
+    );
+    assert_eq!(event_id, "$143273582443PhrSn:example.org");
+}
+
 #[test]
 #[cfg(feature = "html")]
 fn reply_sanitize() {
@@ -761,6 +1064,96 @@ fn make_replacement_no_reply() {
     assert_matches!(content.mentions, None);
 }
 
+#[test]
+fn try_make_replacement_round_trip() {
+    let original_message: OriginalSyncRoomMessageEvent = from_json_value(json!({
+        "content": {
+            "body": "Hello, World!",
+            "msgtype": "m.text",
+        },
+        "event_id": "$143273582443PhrSn",
+        "origin_server_ts": 134_829_848,
+        "room_id": "!roomid:notareal.hs",
+        "sender": "@user:notareal.hs",
+        "type": "m.room.message",
+    }))
+    .unwrap();
+
+    let content = RoomMessageEventContent::text_html(
+        "Hello, _edited_ World!",
+        "Hello, <em>edited</em> World!",
+    )
+    .try_make_replacement(&original_message, None)
+    .unwrap();
+    let json = to_json_value(&content).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "body": "* Hello, _edited_ World!",
+            "format": "org.matrix.custom.html",
+            "formatted_body": "* Hello, <em>edited</em> World!",
+            "msgtype": "m.text",
+            "m.new_content": {
+                "body": "Hello, _edited_ World!",
+                "format": "org.matrix.custom.html",
+                "formatted_body": "Hello, <em>edited</em> World!",
+                "msgtype": "m.text",
+            },
+            "m.relates_to": {
+                "rel_type": "m.replace",
+                "event_id": "$143273582443PhrSn",
+            },
+        })
+    );
+
+    // The fallback and the new content can be deserialized independently.
+    let content = from_json_value::<RoomMessageEventContent>(json.clone()).unwrap();
+    assert_matches!(content.msgtype, MessageType::Text(fallback));
+    assert_eq!(fallback.body, "* Hello, _edited_ World!");
+    assert_eq!(fallback.formatted.unwrap().body, "* Hello, <em>edited</em> World!");
+    assert_matches!(content.relates_to, Some(Relation::Replacement(replacement)));
+    assert_eq!(replacement.event_id, "$143273582443PhrSn");
+    assert_matches!(replacement.new_content.msgtype, MessageType::Text(new_content));
+    assert_eq!(new_content.body, "Hello, _edited_ World!");
+
+    let new_content =
+        from_json_value::<RoomMessageEventContentWithoutRelation>(json["m.new_content"].clone())
+            .unwrap();
+    assert_matches!(new_content.msgtype, MessageType::Text(new_content));
+    assert_eq!(new_content.body, "Hello, _edited_ World!");
+}
+
+#[test]
+fn try_make_replacement_msgtype_mismatch() {
+    let original_message: OriginalSyncRoomMessageEvent = from_json_value(json!({
+        "content": {
+            "body": "Hello, World!",
+            "msgtype": "m.text",
+        },
+        "event_id": "$143273582443PhrSn",
+        "origin_server_ts": 134_829_848,
+        "room_id": "!roomid:notareal.hs",
+        "sender": "@user:notareal.hs",
+        "type": "m.room.message",
+    }))
+    .unwrap();
+    let content = RoomMessageEventContent::notice_plain("Hello, edited World!");
+
+    let error = content.clone().try_make_replacement(&original_message, None).unwrap_err();
+    assert_eq!(error.original, "m.text");
+    assert_eq!(error.replacement, "m.notice");
+
+    // Changing the msgtype is allowed with `make_replacement`.
+    let replacement = content.clone().make_replacement(&original_message, None);
+    assert_matches!(replacement.msgtype, MessageType::Notice(fallback));
+    assert_eq!(fallback.body, "* Hello, edited World!");
+
+    // The check is skipped when the msgtype of the original message is unknown.
+    let metadata = ReplacementMetadata::new(owned_event_id!("$143273582443PhrSn"), None);
+    let replacement = content.try_make_replacement(metadata, None).unwrap();
+    assert_matches!(replacement.relates_to, Some(Relation::Replacement(_)));
+}
+
 #[test]
 fn make_replacement_with_reply() {
     let replied_to_message = OriginalRoomMessageEvent {
@@ -860,98 +1253,293 @@ fn audio_msgtype_deserialization() {
 }
 
 #[test]
-fn file_msgtype_plain_content_serialization() {
-    let message_event_content =
-        RoomMessageEventContent::new(MessageType::File(FileMessageEventContent::plain(
-            "Upload: my_file.txt".to_owned(),
-            mxc_uri!("mxc://notareal.hs/file").to_owned(),
-        )));
+fn audio_msgtype_playback_position_not_serialized() {
+    let mut content = AudioMessageEventContent::plain(
+        "Upload: my_song.mp3".to_owned(),
+        mxc_uri!("mxc://notareal.hs/file").to_owned(),
+    );
+    content.playback_position = Some(Duration::from_secs(42));
+    let message_event_content = RoomMessageEventContent::new(MessageType::Audio(content));
 
+    let json = to_json_value(&message_event_content).unwrap();
     assert_eq!(
-        to_json_value(&message_event_content).unwrap(),
+        json,
         json!({
-            "body": "Upload: my_file.txt",
+            "body": "Upload: my_song.mp3",
             "url": "mxc://notareal.hs/file",
-            "msgtype": "m.file",
+            "msgtype": "m.audio",
         })
     );
+
+    let mut json_with_position = json;
+    json_with_position["playback_position"] = json!(42);
+    let event_content = from_json_value::<RoomMessageEventContent>(json_with_position).unwrap();
+    assert_matches!(event_content.msgtype, MessageType::Audio(content));
+    assert_eq!(content.playback_position, None);
 }
 
 #[test]
-fn file_msgtype_encrypted_content_serialization() {
-    let message_event_content =
-        RoomMessageEventContent::new(MessageType::File(FileMessageEventContent::encrypted(
-            "Upload: my_file.txt".to_owned(),
-            EncryptedFileInit {
-                url: mxc_uri!("mxc://notareal.hs/file").to_owned(),
-                key: JsonWebKeyInit {
-                    kty: "oct".to_owned(),
-                    key_ops: vec!["encrypt".to_owned(), "decrypt".to_owned()],
-                    alg: "A256CTR".to_owned(),
-                    k: Base64::parse("TLlG_OpX807zzQuuwv4QZGJ21_u7weemFGYJFszMn9A").unwrap(),
-                    ext: true,
-                }
-                .into(),
-                iv: Base64::parse("S22dq3NAX8wAAAAAAAAAAA").unwrap(),
-                hashes: [(
-                    "sha256".to_owned(),
-                    Base64::parse("aWOHudBnDkJ9IwaR1Nd8XKoI7DOrqDTwt6xDPfVGN6Q").unwrap(),
-                )]
-                .into(),
-                v: "v2".to_owned(),
-            }
-            .into(),
-        )));
+#[cfg(feature = "unstable-audio-transcription")]
+fn audio_msgtype_transcription_round_trip() {
+    let mut content = AudioMessageEventContent::plain(
+        "Voice message".to_owned(),
+        mxc_uri!("mxc://notareal.hs/file").to_owned(),
+    );
+    assert_eq!(content.transcription(), None);
+
+    content.transcription = Some("Hello there!".to_owned());
+    let message_event_content = RoomMessageEventContent::new(MessageType::Audio(content));
 
+    let json = to_json_value(&message_event_content).unwrap();
     assert_eq!(
-        to_json_value(&message_event_content).unwrap(),
+        json,
         json!({
-            "body": "Upload: my_file.txt",
-            "file": {
-                "url": "mxc://notareal.hs/file",
-                "key": {
-                    "kty": "oct",
-                    "key_ops": ["encrypt", "decrypt"],
-                    "alg": "A256CTR",
-                    "k": "TLlG_OpX807zzQuuwv4QZGJ21_u7weemFGYJFszMn9A",
-                    "ext": true
-                },
-                "iv": "S22dq3NAX8wAAAAAAAAAAA",
-                "hashes": {
-                    "sha256": "aWOHudBnDkJ9IwaR1Nd8XKoI7DOrqDTwt6xDPfVGN6Q"
-                },
-                "v": "v2",
-            },
-            "msgtype": "m.file",
+            "body": "Voice message",
+            "url": "mxc://notareal.hs/file",
+            "msgtype": "m.audio",
+            "io.ruma.transcription": "Hello there!",
         })
     );
+
+    let event_content = from_json_value::<RoomMessageEventContent>(json).unwrap();
+    assert_matches!(event_content.msgtype, MessageType::Audio(mut content));
+    assert_eq!(content.transcription(), Some("Hello there!"));
+
+    content.transcription = Some("  ".to_owned());
+    assert_eq!(content.transcription(), None);
 }
 
 #[test]
-fn file_msgtype_plain_content_deserialization() {
+#[cfg(feature = "unstable-msc3245-v1-compat")]
+fn audio_msgtype_huge_waveform_deserialization() {
     let json_data = json!({
-        "body": "Upload: my_file.txt",
+        "body": "Voice message",
         "url": "mxc://notareal.hs/file",
-        "msgtype": "m.file",
+        "msgtype": "m.audio",
+        "org.matrix.msc1767.audio": {
+            "duration": 5_300,
+            "waveform": vec![json!(512); 100_000],
+        },
+        "org.matrix.msc3245.voice": {},
     });
 
     let event_content = from_json_value::<RoomMessageEventContent>(json_data).unwrap();
-    assert_matches!(event_content.msgtype, MessageType::File(content));
-    assert_eq!(content.body, "Upload: my_file.txt");
-    assert_matches!(content.source, MediaSource::Plain(url));
-    assert_eq!(url, "mxc://notareal.hs/file");
+    assert_matches!(event_content.msgtype, MessageType::Audio(content));
+    let audio = content.audio.unwrap();
+    assert_eq!(audio.waveform.len(), 1024);
+    assert!(audio.waveform.iter().all(|amplitude| amplitude.get() == uint!(512)));
 }
 
 #[test]
-fn file_msgtype_encrypted_content_deserialization() {
+fn audio_msgtype_invalid_deserialization() {
     let json_data = json!({
-        "body": "Upload: my_file.txt",
-        "file": {
-            "url": "mxc://notareal.hs/file",
-            "key": {
-                "kty": "oct",
-                "key_ops": ["encrypt", "decrypt"],
-                "alg": "A256CTR",
+        "body": "Upload: my_song.mp3",
+        "url": ["mxc://notareal.hs/file"],
+        "msgtype": "m.audio",
+    });
+    from_json_value::<RoomMessageEventContent>(json_data).unwrap_err();
+
+    let json_data = json!({
+        "body": "Upload: my_song.mp3",
+        "msgtype": "m.audio",
+    });
+    from_json_value::<RoomMessageEventContent>(json_data).unwrap_err();
+
+    let json_data = json!({
+        "body": "Upload: my_song.mp3",
+        "url": "mxc://notareal.hs/file",
+        "msgtype": "m.audio",
+        "info": {
+            "duration": -1,
+        },
+        "m.relates_to": {
+            "m.in_reply_to": "$event",
+        },
+    });
+    from_json_value::<RoomMessageEventContent>(json_data).unwrap_err();
+}
+
+#[test]
+fn deeply_nested_content_deserialization() {
+    fn nested_reply_content(depth: usize) -> JsonValue {
+        let mut nested = json!({ "event_id": "$deep:example.org" });
+        for _ in 1..depth {
+            nested = json!({ "event_id": "$deep:example.org", "m.in_reply_to": nested });
+        }
+
+        json!({
+            "body": "Deep",
+            "msgtype": "m.text",
+            "m.relates_to": { "m.in_reply_to": nested },
+        })
+    }
+
+    // Unknown nested replies are ignored.
+    let content = from_json_value::<RoomMessageEventContent>(nested_reply_content(100)).unwrap();
+    assert_matches!(content.relates_to, Some(Relation::Reply { in_reply_to }));
+    assert_eq!(in_reply_to.event_id, "$deep:example.org");
+
+    // Deeply nested custom keys are fine too.
+    let mut custom = json!("Dive!");
+    for _ in 0..100 {
+        custom = json!({ "org.example.nested": custom });
+    }
+    let mut json_data = nested_reply_content(1);
+    json_data["org.example.custom"] = custom;
+    from_json_value::<RoomMessageEventContent>(json_data).unwrap();
+
+    // The recursion limit of serde_json rejects contents that are nested too deeply.
+    let err = from_json_value::<RoomMessageEventContent>(nested_reply_content(200)).unwrap_err();
+    assert!(err.to_string().starts_with("recursion limit exceeded"), "{err}");
+}
+
+#[test]
+fn reply_to_deeply_nested_reply() {
+    let nested_plain = "> <@user:example.org> Quote\n".repeat(1_000);
+    let nested_html = "<mx-reply><blockquote>".repeat(1_000);
+    let mut content = RoomMessageEventContent::text_html(
+        format!("{nested_plain}\nThis is the first message"),
+        format!("{nested_html}This is the first message"),
+    );
+    content.relates_to =
+        Some(Relation::Reply { in_reply_to: InReplyTo::new(owned_event_id!("$deep:example.org")) });
+    let first_message = OriginalRoomMessageEvent {
+        content,
+        event_id: owned_event_id!("$143273582443PhrSn:example.org"),
+        origin_server_ts: MilliSecondsSinceUnixEpoch(uint!(10_000)),
+        room_id: owned_room_id!("!testroomid:example.org"),
+        sender: owned_user_id!("@user:example.org"),
+        unsigned: MessageLikeUnsigned::default(),
+    };
+    let reply = RoomMessageEventContent::text_plain("This is my reply").make_reply_to(
+        &first_message,
+        ForwardThread::Yes,
+        AddMentions::No,
+    );
+
+    assert_matches!(
+        reply.msgtype,
+        MessageType::Text(TextMessageEventContent { body, formatted, .. })
+    );
+    assert_eq!(
+        body,
+        "\
+        > <@user:example.org> This is the first message\n\
+        \n\
+        This is my reply\
+        "
+    );
+    #[cfg(feature = "html")]
+    assert_eq!(
+        formatted.unwrap().body,
+        "\
+        <mx-reply>\
+            <blockquote>\
+                <a href=\"https://matrix.to/#/!testroomid:example.org/$143273582443PhrSn:example.org\">In reply to</a> \
+                <a href=\"https://matrix.to/#/@user:example.org\">@user:example.org</a>\
+                <br>\
+            </blockquote>\
+        </mx-reply>\
+        This is my reply\
+        "
+    );
+    #[cfg(not(feature = "html"))]
+    assert!(formatted.is_some());
+}
+
+#[test]
+fn file_msgtype_plain_content_serialization() {
+    let message_event_content =
+        RoomMessageEventContent::new(MessageType::File(FileMessageEventContent::plain(
+            "Upload: my_file.txt".to_owned(),
+            mxc_uri!("mxc://notareal.hs/file").to_owned(),
+        )));
+
+    assert_eq!(
+        to_json_value(&message_event_content).unwrap(),
+        json!({
+            "body": "Upload: my_file.txt",
+            "url": "mxc://notareal.hs/file",
+            "msgtype": "m.file",
+        })
+    );
+}
+
+#[test]
+fn file_msgtype_encrypted_content_serialization() {
+    let message_event_content =
+        RoomMessageEventContent::new(MessageType::File(FileMessageEventContent::encrypted(
+            "Upload: my_file.txt".to_owned(),
+            EncryptedFileInit {
+                url: mxc_uri!("mxc://notareal.hs/file").to_owned(),
+                key: JsonWebKeyInit {
+                    kty: "oct".to_owned(),
+                    key_ops: vec!["encrypt".to_owned(), "decrypt".to_owned()],
+                    alg: "A256CTR".to_owned(),
+                    k: Base64::parse("TLlG_OpX807zzQuuwv4QZGJ21_u7weemFGYJFszMn9A").unwrap(),
+                    ext: true,
+                }
+                .into(),
+                iv: Base64::parse("S22dq3NAX8wAAAAAAAAAAA").unwrap(),
+                hashes: [(
+                    "sha256".to_owned(),
+                    Base64::parse("aWOHudBnDkJ9IwaR1Nd8XKoI7DOrqDTwt6xDPfVGN6Q").unwrap(),
+                )]
+                .into(),
+                v: "v2".to_owned(),
+            }
+            .into(),
+        )));
+
+    assert_eq!(
+        to_json_value(&message_event_content).unwrap(),
+        json!({
+            "body": "Upload: my_file.txt",
+            "file": {
+                "url": "mxc://notareal.hs/file",
+                "key": {
+                    "kty": "oct",
+                    "key_ops": ["encrypt", "decrypt"],
+                    "alg": "A256CTR",
+                    "k": "TLlG_OpX807zzQuuwv4QZGJ21_u7weemFGYJFszMn9A",
+                    "ext": true
+                },
+                "iv": "S22dq3NAX8wAAAAAAAAAAA",
+                "hashes": {
+                    "sha256": "aWOHudBnDkJ9IwaR1Nd8XKoI7DOrqDTwt6xDPfVGN6Q"
+                },
+                "v": "v2",
+            },
+            "msgtype": "m.file",
+        })
+    );
+}
+
+#[test]
+fn file_msgtype_plain_content_deserialization() {
+    let json_data = json!({
+        "body": "Upload: my_file.txt",
+        "url": "mxc://notareal.hs/file",
+        "msgtype": "m.file",
+    });
+
+    let event_content = from_json_value::<RoomMessageEventContent>(json_data).unwrap();
+    assert_matches!(event_content.msgtype, MessageType::File(content));
+    assert_eq!(content.body, "Upload: my_file.txt");
+    assert_matches!(content.source, MediaSource::Plain(url));
+    assert_eq!(url, "mxc://notareal.hs/file");
+}
+
+#[test]
+fn file_msgtype_encrypted_content_deserialization() {
+    let json_data = json!({
+        "body": "Upload: my_file.txt",
+        "file": {
+            "url": "mxc://notareal.hs/file",
+            "key": {
+                "kty": "oct",
+                "key_ops": ["encrypt", "decrypt"],
+                "alg": "A256CTR",
                 "k": "TLlG_OpX807zzQuuwv4QZGJ21_u7weemFGYJFszMn9A",
                 "ext": true
             },
@@ -971,6 +1559,68 @@ fn file_msgtype_encrypted_content_deserialization() {
     assert_eq!(encrypted_file.url, "mxc://notareal.hs/file");
 }
 
+#[test]
+#[cfg(feature = "unstable-msc1767")]
+fn file_msgtype_extensible_block_round_trip() {
+    use ruma_events::room::message::{FileInfo, UnstableFileContentBlock};
+
+    let mut info = FileInfo::new();
+    info.mimetype = Some("text/plain".to_owned());
+    info.size = Some(uint!(1024));
+
+    let mut block = UnstableFileContentBlock::new(
+        mxc_uri!("mxc://notareal.hs/file").to_owned(),
+        "my_file.txt".to_owned(),
+    );
+    block.mimetype = Some("text/plain".to_owned());
+    block.size = Some(uint!(1024));
+
+    let mut content = FileMessageEventContent::plain(
+        "Upload: my_file.txt".to_owned(),
+        mxc_uri!("mxc://notareal.hs/file").to_owned(),
+    )
+    .filename("my_file.txt".to_owned())
+    .info(Box::new(info));
+    content.file = Some(block);
+
+    let json = to_json_value(RoomMessageEventContent::new(MessageType::File(content))).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "body": "Upload: my_file.txt",
+            "filename": "my_file.txt",
+            "url": "mxc://notareal.hs/file",
+            "info": {
+                "mimetype": "text/plain",
+                "size": 1024,
+            },
+            "msgtype": "m.file",
+            "org.matrix.msc1767.file": {
+                "url": "mxc://notareal.hs/file",
+                "name": "my_file.txt",
+                "mimetype": "text/plain",
+                "size": 1024,
+            },
+        })
+    );
+
+    let event_content = from_json_value::<RoomMessageEventContent>(json).unwrap();
+    assert_matches!(event_content.msgtype, MessageType::File(content));
+    assert_eq!(content.body, "Upload: my_file.txt");
+    assert_eq!(content.filename.as_deref(), Some("my_file.txt"));
+    assert_matches!(content.source, MediaSource::Plain(url));
+    assert_eq!(url, "mxc://notareal.hs/file");
+    let info = content.info.unwrap();
+    assert_eq!(info.mimetype.as_deref(), Some("text/plain"));
+    assert_eq!(info.size, Some(uint!(1024)));
+
+    let block = content.file.unwrap();
+    assert_eq!(block.url, "mxc://notareal.hs/file");
+    assert_eq!(block.name, "my_file.txt");
+    assert_eq!(block.mimetype.as_deref(), Some("text/plain"));
+    assert_eq!(block.size, Some(uint!(1024)));
+}
+
 #[test]
 fn image_msgtype_serialization() {
     let message_event_content =
@@ -1004,6 +1654,394 @@ fn image_msgtype_deserialization() {
     assert_eq!(url, "mxc://notareal.hs/file");
 }
 
+#[test]
+fn image_preview_source() {
+    let content = ImageMessageEventContent::plain(
+        "Upload: my_image.jpg".to_owned(),
+        mxc_uri!("mxc://notareal.hs/file").to_owned(),
+    );
+    assert_matches!(content.preview_source(), MediaSource::Plain(url));
+    assert_eq!(url, "mxc://notareal.hs/file");
+
+    let mut info = ImageInfo::new();
+    info.thumbnail_source =
+        Some(MediaSource::Plain(mxc_uri!("mxc://notareal.hs/thumbnail").to_owned()));
+    let content = content.info(Box::new(info));
+    assert_matches!(content.preview_source(), MediaSource::Plain(url));
+    assert_eq!(url, "mxc://notareal.hs/thumbnail");
+}
+
+#[test]
+fn image_potentially_unsafe_media() {
+    let content = ImageMessageEventContent::plain(
+        "Upload: my_image".to_owned(),
+        mxc_uri!("mxc://notareal.hs/file").to_owned(),
+    );
+    // Without a mimetype, the media could be anything.
+    assert!(content.is_potentially_unsafe_media());
+
+    let content = content.info(Box::new(ImageInfo::new()));
+    assert!(content.is_potentially_unsafe_media());
+
+    let mut info = ImageInfo::new();
+    info.mimetype = Some("image/png".to_owned());
+    let content = content.info(Box::new(info));
+    assert!(!content.is_potentially_unsafe_media());
+
+    let mut info = ImageInfo::new();
+    info.mimetype = Some("image/svg+xml".to_owned());
+    let content = content.info(Box::new(info));
+    assert!(content.is_potentially_unsafe_media());
+
+    let mut info = ImageInfo::new();
+    info.mimetype = Some("Image/SVG+XML; charset=utf-8".to_owned());
+    let content = content.info(Box::new(info));
+    assert!(content.is_potentially_unsafe_media());
+
+    let mut file_info = FileInfo::new();
+    file_info.mimetype = Some("text/html".to_owned());
+    let file = MessageType::File(
+        FileMessageEventContent::plain(
+            "Upload: page.html".to_owned(),
+            mxc_uri!("mxc://notareal.hs/file").to_owned(),
+        )
+        .info(Box::new(file_info)),
+    );
+    assert!(file.as_media().unwrap().is_potentially_unsafe_media());
+
+    let file = MessageType::File(FileMessageEventContent::plain(
+        "Upload: image.svg".to_owned(),
+        mxc_uri!("mxc://notareal.hs/file").to_owned(),
+    ));
+    assert!(file.as_media().unwrap().is_potentially_unsafe_media());
+}
+
+#[cfg(feature = "unstable-animated-thumbnail")]
+#[test]
+fn image_msgtype_animated_thumbnail_round_trip() {
+    use ruma_events::room::ThumbnailInfo;
+
+    let json_data = json!({
+        "body": "Upload: dancing.gif",
+        "url": "mxc://notareal.hs/file",
+        "info": {
+            "mimetype": "image/gif",
+            "thumbnail_url": "mxc://notareal.hs/thumbnail",
+            "thumbnail_info": {
+                "mimetype": "image/webp",
+                "io.ruma.animated": true,
+            },
+        },
+        "msgtype": "m.image",
+    });
+
+    let content = from_json_value::<ImageMessageEventContent>(json_data.clone()).unwrap();
+    let thumbnail_info = content.info.as_ref().unwrap().thumbnail_info.as_deref().unwrap();
+    assert_eq!(thumbnail_info.animated, Some(true));
+    assert!(thumbnail_info.is_animated());
+
+    assert_eq!(to_json_value(&content).unwrap(), json_data);
+
+    // Thumbnails are static by default.
+    let thumbnail_info =
+        from_json_value::<ThumbnailInfo>(json!({ "mimetype": "image/png" })).unwrap();
+    assert_eq!(thumbnail_info.animated, None);
+    assert!(!thumbnail_info.is_animated());
+    assert_eq!(to_json_value(&thumbnail_info).unwrap(), json!({ "mimetype": "image/png" }));
+}
+
+#[cfg(feature = "unstable-media-hashes")]
+#[test]
+fn image_msgtype_hashes_round_trip() {
+    let json_data = json!({
+        "body": "Upload: my_image.jpg",
+        "url": "mxc://notareal.hs/file",
+        "info": {
+            "mimetype": "image/jpeg",
+            "io.ruma.hashes": {
+                "sha256": "3/1gIbsr1bCvZ2KQgJ7DpTGR3YHH9wpLKGiKNiGCmG8",
+            },
+        },
+        "msgtype": "m.image",
+    });
+
+    let event_content = from_json_value::<RoomMessageEventContent>(json_data.clone()).unwrap();
+    assert_matches!(&event_content.msgtype, MessageType::Image(content));
+    let info = content.info.as_deref().unwrap();
+    assert_eq!(info.hashes.len(), 1);
+    assert_eq!(info.verify_hashes(b"Hello, World!"), Some(true));
+    assert_eq!(info.verify_hashes(b"Hello, World?"), Some(false));
+
+    assert_eq!(to_json_value(&event_content).unwrap(), json_data);
+
+    // Without hashes, the data can't be verified and the field is not serialized.
+    let info = ImageInfo::new();
+    assert_eq!(info.verify_hashes(b"Hello, World!"), None);
+    assert_eq!(to_json_value(&info).unwrap(), json!({}));
+}
+
+#[cfg(feature = "unstable-media-hashes")]
+#[test]
+fn file_info_from_bytes() {
+    use ruma_events::room::media_content_hash;
+
+    let info = FileInfo::from_bytes(b"Hello, World!");
+    assert_eq!(info.size, Some(uint!(13)));
+    assert_eq!(
+        info.content_hash().unwrap().encode(),
+        "3/1gIbsr1bCvZ2KQgJ7DpTGR3YHH9wpLKGiKNiGCmG8"
+    );
+    assert_eq!(info.verify_hashes(b"Hello, World!"), Some(true));
+
+    // Identical bytes have the same hash, different bytes don't.
+    let data = String::from("Hello, World!");
+    let same_info = FileInfo::from_bytes(data.as_bytes());
+    assert_eq!(same_info.content_hash(), info.content_hash());
+    assert_eq!(media_content_hash(b"Hello, World!"), *info.content_hash().unwrap());
+    assert_ne!(media_content_hash(b"Hello, World?"), *info.content_hash().unwrap());
+
+    assert_eq!(
+        to_json_value(&info).unwrap(),
+        json!({
+            "size": 13,
+            "io.ruma.hashes": {
+                "sha256": "3/1gIbsr1bCvZ2KQgJ7DpTGR3YHH9wpLKGiKNiGCmG8",
+            },
+        })
+    );
+    assert_eq!(FileInfo::new().content_hash(), None);
+}
+
+#[cfg(feature = "unstable-media-hashes")]
+#[test]
+fn video_msgtype_hashes_round_trip() {
+    let mut info = VideoInfo::new();
+    info.hashes.insert(
+        "sha256".to_owned(),
+        Base64::parse("3/1gIbsr1bCvZ2KQgJ7DpTGR3YHH9wpLKGiKNiGCmG8").unwrap(),
+    );
+    let content = VideoMessageEventContent::plain(
+        "Upload: my_video.mp4".to_owned(),
+        mxc_uri!("mxc://notareal.hs/file").to_owned(),
+    )
+    .info(Box::new(info));
+
+    let json_data = json!({
+        "body": "Upload: my_video.mp4",
+        "url": "mxc://notareal.hs/file",
+        "info": {
+            "io.ruma.hashes": {
+                "sha256": "3/1gIbsr1bCvZ2KQgJ7DpTGR3YHH9wpLKGiKNiGCmG8",
+            },
+        },
+        "msgtype": "m.video",
+    });
+    assert_eq!(to_json_value(MessageType::Video(content)).unwrap(), json_data);
+
+    assert_matches!(
+        from_json_value::<MessageType>(json_data).unwrap(),
+        MessageType::Video(content)
+    );
+    let info = content.info.unwrap();
+    assert_eq!(info.verify_hashes(b"Hello, World!"), Some(true));
+    assert_eq!(info.verify_hashes(b""), Some(false));
+}
+
+#[cfg(feature = "unstable-media-tracks")]
+#[test]
+fn video_msgtype_tracks_round_trip() {
+    use ruma_events::room::message::{MediaTrack, MediaTrackKind};
+
+    let json_data = json!({
+        "body": "Upload: movie.mkv",
+        "url": "mxc://notareal.hs/file",
+        "info": {
+            "mimetype": "video/x-matroska",
+            "io.ruma.tracks": [
+                { "id": "1", "kind": "video" },
+                { "id": "2", "kind": "audio", "language": "en" },
+                { "id": "3", "kind": "audio", "language": "fr" },
+                { "id": "4", "kind": "subtitles", "language": "pt-BR" },
+                { "id": "5", "kind": "io.ruma.commentary" },
+            ],
+        },
+        "msgtype": "m.video",
+    });
+
+    assert_matches!(
+        from_json_value::<MessageType>(json_data.clone()).unwrap(),
+        MessageType::Video(content)
+    );
+    let tracks = &content.info.as_ref().unwrap().tracks;
+    assert_eq!(tracks.len(), 5);
+    assert_eq!(tracks[0].id, "1");
+    assert_eq!(tracks[0].kind, MediaTrackKind::Video);
+    assert_eq!(tracks[0].language, None);
+    assert_eq!(tracks[2].kind, MediaTrackKind::Audio);
+    assert_eq!(tracks[2].language.as_deref(), Some("fr"));
+    assert_eq!(tracks[3].kind, MediaTrackKind::Subtitles);
+    assert_eq!(tracks[4].kind.as_str(), "io.ruma.commentary");
+
+    assert_eq!(to_json_value(MessageType::Video(content)).unwrap(), json_data);
+
+    // Tracks are optional and not serialized when empty.
+    let mut info = AudioInfo::new();
+    assert_eq!(to_json_value(&info).unwrap(), json!({}));
+
+    let mut track = MediaTrack::new("0".to_owned(), MediaTrackKind::Audio);
+    track.language = Some("de".to_owned());
+    info.tracks.push(track);
+    assert_eq!(
+        to_json_value(&info).unwrap(),
+        json!({ "io.ruma.tracks": [{ "id": "0", "kind": "audio", "language": "de" }] })
+    );
+}
+
+#[cfg(feature = "unstable-streaming-manifest")]
+#[test]
+fn video_msgtype_streaming_manifest_round_trip() {
+    use ruma_events::room::message::{StreamingManifest, StreamingManifestFormat};
+
+    let json_data = json!({
+        "body": "Upload: movie.mp4",
+        "url": "mxc://notareal.hs/file",
+        "info": {
+            "mimetype": "video/mp4",
+            "io.ruma.streaming_manifest": {
+                "format": "hls",
+                "url": "mxc://notareal.hs/playlist",
+            },
+        },
+        "msgtype": "m.video",
+    });
+
+    assert_matches!(
+        from_json_value::<MessageType>(json_data.clone()).unwrap(),
+        MessageType::Video(content)
+    );
+    let manifest = content.streaming_manifest().unwrap();
+    assert_eq!(manifest.format, StreamingManifestFormat::Hls);
+    assert_matches!(&manifest.source, MediaSource::Plain(url));
+    assert_eq!(url, "mxc://notareal.hs/playlist");
+
+    assert_eq!(to_json_value(MessageType::Video(content)).unwrap(), json_data);
+
+    // The manifest is optional and not serialized when missing.
+    let mut info = VideoInfo::new();
+    assert_eq!(to_json_value(&info).unwrap(), json!({}));
+    let content = VideoMessageEventContent::plain(
+        "Upload: movie.mp4".to_owned(),
+        mxc_uri!("mxc://notareal.hs/file").to_owned(),
+    );
+    assert!(content.streaming_manifest().is_none());
+
+    info.streaming_manifest = Some(StreamingManifest::new(
+        StreamingManifestFormat::Dash,
+        MediaSource::Plain(mxc_uri!("mxc://notareal.hs/manifest").to_owned()),
+    ));
+    assert_eq!(
+        to_json_value(&info).unwrap(),
+        json!({
+            "io.ruma.streaming_manifest": {
+                "format": "dash",
+                "url": "mxc://notareal.hs/manifest",
+            },
+        })
+    );
+}
+
+#[cfg(feature = "unstable-audio-chapters")]
+#[test]
+fn audio_msgtype_chapters_round_trip() {
+    let json_data = json!({
+        "body": "Upload: episode.ogg",
+        "url": "mxc://notareal.hs/file",
+        "info": {
+            "duration": 3_600_000,
+            "io.ruma.chapters": [
+                { "position_ms": 0, "title": "Intro" },
+                { "position_ms": 90_000, "title": "News" },
+                { "position_ms": 1_200_000, "title": "Interview" },
+            ],
+        },
+        "msgtype": "m.audio",
+    });
+
+    assert_matches!(
+        from_json_value::<MessageType>(json_data.clone()).unwrap(),
+        MessageType::Audio(content)
+    );
+    let chapters = &content.info.as_ref().unwrap().chapters;
+    assert_eq!(chapters.len(), 3);
+    assert_eq!(chapters[1].position, Duration::from_secs(90));
+    assert_eq!(chapters[1].title, "News");
+
+    assert_eq!(to_json_value(MessageType::Audio(content)).unwrap(), json_data);
+
+    // Chapters are optional and not serialized when empty.
+    assert_eq!(to_json_value(AudioInfo::new()).unwrap(), json!({}));
+}
+
+#[cfg(feature = "unstable-audio-chapters")]
+#[test]
+fn audio_info_chapter_at() {
+    use ruma_events::room::message::AudioChapter;
+
+    let mut info = AudioInfo::new();
+    assert!(info.chapter_at(Duration::ZERO).is_none());
+
+    // Chapters don't need to be sorted.
+    info.chapters = vec![
+        AudioChapter::new(Duration::from_secs(600), "Outro".to_owned()),
+        AudioChapter::new(Duration::from_secs(5), "Intro".to_owned()),
+        AudioChapter::new(Duration::from_secs(60), "Main".to_owned()),
+    ];
+    fn title_at(info: &AudioInfo, secs: u64) -> Option<&str> {
+        info.chapter_at(Duration::from_secs(secs)).map(|chapter| &*chapter.title)
+    }
+
+    assert_eq!(title_at(&info, 0), None);
+    assert_eq!(title_at(&info, 5), Some("Intro"));
+    assert_eq!(title_at(&info, 59), Some("Intro"));
+    assert_eq!(title_at(&info, 60), Some("Main"));
+    assert_eq!(title_at(&info, 3_000), Some("Outro"));
+
+    info.duration = Some(Duration::from_secs(900));
+    assert_eq!(title_at(&info, 900), Some("Outro"));
+    assert_eq!(title_at(&info, 901), None);
+}
+
+#[test]
+fn formatted_body_renderable_html() {
+    let json_data = json!({
+        "body": "Hello, *world*!",
+        "format": "org.matrix.custom.html",
+        "formatted_body": "Hello, <em>world</em>!",
+        "msgtype": "m.text",
+    });
+    assert_matches!(from_json_value::<MessageType>(json_data).unwrap(), MessageType::Text(content));
+    let formatted = content.formatted.unwrap();
+    assert_eq!(formatted.format, MessageFormat::Html);
+    assert_eq!(formatted.renderable_html(), Some("Hello, <em>world</em>!"));
+
+    let json_data = json!({
+        "body": "Hello, *world*!",
+        "format": "org.example.bbcode",
+        "formatted_body": "Hello, [i]world[/i]!",
+        "msgtype": "m.text",
+    });
+    assert_matches!(
+        from_json_value::<MessageType>(json_data.clone()).unwrap(),
+        MessageType::Text(content)
+    );
+    let formatted = content.formatted.as_ref().unwrap();
+    assert_eq!(formatted.format.as_str(), "org.example.bbcode");
+    assert_eq!(formatted.renderable_html(), None);
+
+    // The unknown format is kept as-is.
+    assert_eq!(to_json_value(MessageType::Text(content)).unwrap(), json_data);
+}
+
 #[cfg(not(feature = "unstable-msc3488"))]
 #[test]
 fn location_msgtype_serialization() {
@@ -1026,17 +2064,75 @@ fn location_msgtype_serialization() {
 }
 
 #[test]
-fn location_msgtype_deserialization() {
-    let json_data = json!({
-        "body": "Alice was at geo:51.5008,0.1247;u=35",
-        "geo_uri": "geo:51.5008,0.1247;u=35",
-        "msgtype": "m.location",
-    });
+fn location_msgtype_deserialization() {
+    let json_data = json!({
+        "body": "Alice was at geo:51.5008,0.1247;u=35",
+        "geo_uri": "geo:51.5008,0.1247;u=35",
+        "msgtype": "m.location",
+    });
+
+    let event_content = from_json_value::<RoomMessageEventContent>(json_data).unwrap();
+    assert_matches!(event_content.msgtype, MessageType::Location(content));
+    assert_eq!(content.body, "Alice was at geo:51.5008,0.1247;u=35");
+    assert_eq!(content.geo_uri, "geo:51.5008,0.1247;u=35");
+}
+
+#[test]
+fn location_msgtype_uncertainty() {
+    use ruma_events::room::message::LocationMessageEventContent;
+
+    let content =
+        RoomMessageEventContent::new(MessageType::Location(LocationMessageEventContent::new(
+            "Alice was at geo:51.5008,0.1247;u=35".to_owned(),
+            "geo:51.5008,0.1247;u=35".to_owned(),
+        )));
+    let json = to_json_value(&content).unwrap();
+    assert_eq!(json["geo_uri"], "geo:51.5008,0.1247;u=35");
+
+    let content = from_json_value::<RoomMessageEventContent>(json).unwrap();
+    assert_matches!(content.msgtype, MessageType::Location(content));
+    assert_eq!(content.uncertainty(), Some(35.0));
+
+    let uncertainty = |geo_uri: &str| {
+        LocationMessageEventContent::new("Location".to_owned(), geo_uri.to_owned()).uncertainty()
+    };
+    assert_eq!(uncertainty("geo:51.5008,0.1247,10;crs=wgs84;U=12.5;foo=bar"), Some(12.5));
+    assert_eq!(uncertainty("geo:51.5008,0.1247"), None);
+    assert_eq!(uncertainty("geo:51.5008,0.1247;u=-3"), None);
+    assert_eq!(uncertainty("geo:51.5008,0.1247;u=far"), None);
+    assert_eq!(uncertainty("geo:51.5008,0.1247;u=inf"), None);
+    assert_eq!(uncertainty("https://example.org/;u=35"), None);
+}
+
+#[test]
+fn location_msgtype_coordinates() {
+    use ruma_events::room::message::{GeoUriError, LocationMessageEventContent};
 
-    let event_content = from_json_value::<RoomMessageEventContent>(json_data).unwrap();
-    assert_matches!(event_content.msgtype, MessageType::Location(content));
-    assert_eq!(content.body, "Alice was at geo:51.5008,0.1247;u=35");
-    assert_eq!(content.geo_uri, "geo:51.5008,0.1247;u=35");
+    let content = LocationMessageEventContent::try_new(
+        "Alice was at geo:51.5008,0.1247;u=35".to_owned(),
+        "geo:51.5008,0.1247;u=35".to_owned(),
+    )
+    .unwrap();
+    assert_eq!(content.coordinates(), Some((51.5008, 0.1247)));
+
+    let try_new = |geo_uri: &str| {
+        LocationMessageEventContent::try_new("Location".to_owned(), geo_uri.to_owned())
+            .map(|content| content.coordinates())
+    };
+    assert_eq!(try_new("geo:-33.8688,151.2093,58;crs=wgs84"), Ok(Some((-33.8688, 151.2093))));
+    assert_eq!(try_new("GEO:0,0"), Ok(Some((0.0, 0.0))));
+    assert_eq!(try_new("https://example.org/?q=51.5008,0.1247"), Err(GeoUriError::InvalidScheme));
+    assert_eq!(try_new("51.5008,0.1247"), Err(GeoUriError::InvalidScheme));
+    assert_eq!(try_new("geo:1,2,high"), Err(GeoUriError::InvalidCoordinates));
+    assert_eq!(try_new("geo:51.5008"), Err(GeoUriError::InvalidCoordinates));
+    assert_eq!(try_new("geo:north,west"), Err(GeoUriError::InvalidCoordinates));
+    assert_eq!(try_new("geo:1,2,3,4"), Err(GeoUriError::InvalidCoordinates));
+    assert_eq!(try_new("geo:91,0"), Err(GeoUriError::InvalidCoordinates));
+    assert_eq!(try_new("geo:0,-180.5"), Err(GeoUriError::InvalidCoordinates));
+
+    // Invalid URIs are still accepted by `new()`.
+    let content = LocationMessageEventContent::new("Location".to_owned(), "geo:".to_owned());
+    assert_eq!(content.coordinates(), None);
 }
 
 #[test]
@@ -1099,6 +2195,46 @@ fn notice_msgtype_deserialization() {
     assert_eq!(content.body, "test");
 }
 
+#[cfg(feature = "unstable-bridge-attribution")]
+#[test]
+fn notice_msgtype_bridge_attribution_round_trip() {
+    use ruma_events::room::message::{BridgeAttribution, NoticeMessageEventContent};
+
+    let mut attribution = BridgeAttribution::new("Telegram".to_owned(), "Alice".to_owned());
+    attribution.sender_avatar_url = Some(mxc_uri!("mxc://notareal.hs/avatar").to_owned());
+    let mut content = NoticeMessageEventContent::plain("The meeting starts in 5 minutes");
+    content.bridge_attribution = Some(attribution);
+
+    let json_data = json!({
+        "body": "The meeting starts in 5 minutes",
+        "io.ruma.bridge_attribution": {
+            "network": "Telegram",
+            "sender_name": "Alice",
+            "sender_avatar_url": "mxc://notareal.hs/avatar",
+        },
+        "msgtype": "m.notice",
+    });
+    assert_eq!(
+        to_json_value(RoomMessageEventContent::new(MessageType::Notice(content))).unwrap(),
+        json_data
+    );
+
+    let content = from_json_value::<RoomMessageEventContent>(json_data).unwrap();
+    assert_matches!(content.msgtype, MessageType::Notice(content));
+    let attribution = content.bridge_attribution.unwrap();
+    assert_eq!(attribution.network, "Telegram");
+    assert_eq!(attribution.sender_name, "Alice");
+    assert_eq!(attribution.sender_avatar_url.unwrap(), "mxc://notareal.hs/avatar");
+
+    // Messages that were not bridged don't have an attribution.
+    let content = from_json_value::<NoticeMessageEventContent>(json!({
+        "body": "test",
+        "msgtype": "m.notice",
+    }))
+    .unwrap();
+    assert!(content.bridge_attribution.is_none());
+}
+
 #[test]
 fn emote_msgtype_serialization() {
     let message_event_content = RoomMessageEventContent::new(MessageType::Emote(
@@ -1161,6 +2297,99 @@ fn video_msgtype_deserialization() {
     assert_eq!(url, "mxc://notareal.hs/file");
 }
 
+#[test]
+fn video_preview_source() {
+    let content = VideoMessageEventContent::plain(
+        "Upload: my_video.mp4".to_owned(),
+        mxc_uri!("mxc://notareal.hs/file").to_owned(),
+    );
+    assert_matches!(content.preview_source(), MediaSource::Plain(url));
+    assert_eq!(url, "mxc://notareal.hs/file");
+
+    let mut info = VideoInfo::new();
+    info.thumbnail_source =
+        Some(MediaSource::Plain(mxc_uri!("mxc://notareal.hs/thumbnail").to_owned()));
+    let content = content.info(Box::new(info));
+    assert_matches!(content.preview_source(), MediaSource::Plain(url));
+    assert_eq!(url, "mxc://notareal.hs/thumbnail");
+}
+
+#[test]
+fn media_message() {
+    let mut audio_info = AudioInfo::new();
+    audio_info.mimetype = Some("audio/ogg".to_owned());
+    audio_info.size = Some(uint!(1_024));
+    let audio = MessageType::Audio(
+        AudioMessageEventContent::plain(
+            "Upload: my_song.ogg".to_owned(),
+            mxc_uri!("mxc://notareal.hs/audio").to_owned(),
+        )
+        .info(Box::new(audio_info)),
+    );
+
+    let file = MessageType::File(FileMessageEventContent::plain(
+        "Upload: my_file.txt".to_owned(),
+        mxc_uri!("mxc://notareal.hs/file").to_owned(),
+    ));
+
+    let mut image_info = ImageInfo::new();
+    image_info.mimetype = Some("image/png".to_owned());
+    image_info.size = Some(uint!(2_048));
+    image_info.thumbnail_source =
+        Some(MediaSource::Plain(mxc_uri!("mxc://notareal.hs/imagethumbnail").to_owned()));
+    let image = MessageType::Image(
+        ImageMessageEventContent::plain(
+            "Upload: my_image.png".to_owned(),
+            mxc_uri!("mxc://notareal.hs/image").to_owned(),
+        )
+        .info(Box::new(image_info)),
+    );
+
+    let mut video_info = VideoInfo::new();
+    video_info.mimetype = Some("video/mp4".to_owned());
+    video_info.thumbnail_source =
+        Some(MediaSource::Plain(mxc_uri!("mxc://notareal.hs/videothumbnail").to_owned()));
+    let video = MessageType::Video(
+        VideoMessageEventContent::plain(
+            "Upload: my_video.mp4".to_owned(),
+            mxc_uri!("mxc://notareal.hs/video").to_owned(),
+        )
+        .info(Box::new(video_info)),
+    );
+
+    let media = audio.as_media().unwrap();
+    assert_matches!(media.source(), MediaSource::Plain(url));
+    assert_eq!(url, "mxc://notareal.hs/audio");
+    assert_eq!(media.info_size(), Some(uint!(1_024)));
+    assert_eq!(media.info_mimetype(), Some("audio/ogg"));
+    assert_matches!(media.thumbnail_source(), None);
+
+    let media = file.as_media().unwrap();
+    assert_matches!(media.source(), MediaSource::Plain(url));
+    assert_eq!(url, "mxc://notareal.hs/file");
+    assert_eq!(media.info_size(), None);
+    assert_eq!(media.info_mimetype(), None);
+    assert_matches!(media.thumbnail_source(), None);
+
+    let media = image.as_media().unwrap();
+    assert_matches!(media.source(), MediaSource::Plain(url));
+    assert_eq!(url, "mxc://notareal.hs/image");
+    assert_eq!(media.info_size(), Some(uint!(2_048)));
+    assert_eq!(media.info_mimetype(), Some("image/png"));
+    assert_matches!(media.thumbnail_source(), Some(MediaSource::Plain(url)));
+    assert_eq!(url, "mxc://notareal.hs/imagethumbnail");
+
+    let media = video.as_media().unwrap();
+    assert_matches!(media.source(), MediaSource::Plain(url));
+    assert_eq!(url, "mxc://notareal.hs/video");
+    assert_eq!(media.info_size(), None);
+    assert_eq!(media.info_mimetype(), Some("video/mp4"));
+    assert_matches!(media.thumbnail_source(), Some(MediaSource::Plain(url)));
+    assert_eq!(url, "mxc://notareal.hs/videothumbnail");
+
+    assert!(MessageType::text_plain("Not a media").as_media().is_none());
+}
+
 #[test]
 fn set_mentions() {
     let mut content = RoomMessageEventContent::text_plain("you!");
@@ -1173,6 +2402,46 @@ fn set_mentions() {
     assert_eq!(mentions.user_ids, [user_id].into());
 }
 
+#[test]
+#[cfg(feature = "html")]
+fn mentions_with_user_pills() {
+    let alice = owned_user_id!("@alice:localhost");
+    let bob = owned_user_id!("@bob:localhost");
+    let carl = owned_user_id!("@carl:localhost");
+
+    let html = "\
+        <a href=\"https://matrix.to/#/@alice:localhost\">Alice</a>, \
+        <a href='https://matrix.to/#/%40alice%3Alocalhost'>Alice again</a>, \
+        <a href=\"matrix:u/bob:localhost?action=chat\">Bob</a>, \
+        <a href=\"https://matrix.to/#/@me:localhost\">me</a>, \
+        <a href=\"https://matrix.to/#/#room:localhost\">a room</a> and \
+        <a href=\"https://example.org/@alice:localhost\">a website</a>\
+    ";
+    let mentions = Mentions::with_user_ids([bob.clone(), carl.clone()])
+        .with_user_pills(html)
+        .exclude_self(user_id!("@me:localhost"));
+
+    assert_eq!(mentions.user_ids, [alice, bob, carl].into());
+    assert!(!mentions.room);
+
+    // Only the links of `<a>` elements are used, with their character references decoded.
+    let html = "\
+        <span data-href=\"https://matrix.to/#/@alice:localhost\">Alice</span>, \
+        href=\"https://matrix.to/#/@bob:localhost\" in the text, \
+        <a href=\"https://matrix.to/#/&#64;carl&#x3A;localhost\">Carl</a> and \
+        <a href=\"matrix:u/dave:localhost?action=chat&amp;via=localhost\">Dave</a>\
+    ";
+    let mentions = Mentions::new().with_user_pills(html);
+    assert_eq!(
+        mentions.user_ids,
+        [owned_user_id!("@carl:localhost"), owned_user_id!("@dave:localhost")].into()
+    );
+
+    // Excluding a user that is not mentioned doesn't change anything.
+    let mentions = Mentions::new().exclude_self(user_id!("@me:localhost"));
+    assert!(mentions.user_ids.is_empty());
+}
+
 #[test]
 fn make_replacement_set_mentions() {
     let alice = owned_user_id!("@alice:localhost");
@@ -1230,3 +2499,526 @@ fn mentions_room_deserialization() {
     let mentions = content.mentions.unwrap();
     assert!(mentions.room);
 }
+
+#[test]
+fn mentions_notice_and_emote_round_trip() {
+    let alice = owned_user_id!("@alice:localhost");
+
+    let notice = RoomMessageEventContent::notice_plain("Heads up")
+        .set_mentions(Mentions::with_user_ids([alice.clone()]));
+    let notice_json = json!({
+        "body": "Heads up",
+        "msgtype": "m.notice",
+        "m.mentions": {
+            "user_ids": ["@alice:localhost"],
+        },
+    });
+    assert_eq!(to_json_value(&notice).unwrap(), notice_json);
+    let content = from_json_value::<RoomMessageEventContent>(notice_json).unwrap();
+    assert_matches!(content.msgtype, MessageType::Notice(_));
+    assert_eq!(content.mentions.unwrap().user_ids, [alice].into());
+
+    let emote = RoomMessageEventContent::emote_plain("waves at everyone")
+        .set_mentions(Mentions::with_room_mention());
+    let emote_json = json!({
+        "body": "waves at everyone",
+        "msgtype": "m.emote",
+        "m.mentions": {
+            "room": true,
+        },
+    });
+    assert_eq!(to_json_value(&emote).unwrap(), emote_json);
+    let content = from_json_value::<RoomMessageEventContent>(emote_json).unwrap();
+    assert_matches!(content.msgtype, MessageType::Emote(_));
+    assert!(content.mentions.unwrap().room);
+
+    // Without mentions, the key is omitted.
+    let text = RoomMessageEventContent::text_plain("Hello");
+    assert_eq!(to_json_value(&text).unwrap(), json!({ "body": "Hello", "msgtype": "m.text" }));
+
+    // Empty mentions are kept, since they signal that nobody is mentioned on purpose.
+    let text = text.set_mentions(Mentions::new());
+    assert_eq!(
+        to_json_value(&text).unwrap(),
+        json!({ "body": "Hello", "msgtype": "m.text", "m.mentions": {} })
+    );
+}
+
+#[test]
+fn mentions_stable_and_unstable_keys_deserialization() {
+    let mentions = json!({
+        "user_ids": ["@alice:example.org"],
+        "room": true,
+    });
+    let stable = json!({
+        "body": "Hey @room and Alice",
+        "msgtype": "m.text",
+        "m.mentions": mentions,
+    });
+    let unstable = json!({
+        "body": "Hey @room and Alice",
+        "msgtype": "m.text",
+        "org.matrix.msc3952.mentions": mentions,
+    });
+
+    let stable_content = from_json_value::<RoomMessageEventContent>(stable.clone()).unwrap();
+    let unstable_content = from_json_value::<RoomMessageEventContent>(unstable).unwrap();
+    for content in [&stable_content, &unstable_content] {
+        let mentions = content.mentions.as_ref().unwrap();
+        assert_eq!(mentions.user_ids, [owned_user_id!("@alice:example.org")].into());
+        assert!(mentions.room);
+    }
+
+    // The stable key is always serialized.
+    assert_eq!(to_json_value(&stable_content).unwrap(), stable);
+    assert_eq!(to_json_value(&unstable_content).unwrap(), stable);
+
+    // The stable key has priority if both are present.
+    let content = from_json_value::<RoomMessageEventContent>(json!({
+        "body": "Hey Alice",
+        "msgtype": "m.text",
+        "m.mentions": { "user_ids": ["@alice:example.org"] },
+        "org.matrix.msc3952.mentions": { "room": true },
+    }))
+    .unwrap();
+    let mentions = content.mentions.unwrap();
+    assert_eq!(mentions.user_ids, [owned_user_id!("@alice:example.org")].into());
+    assert!(!mentions.room);
+
+    // The message content in a replacement also accepts both keys.
+    let content = from_json_value::<RoomMessageEventContentWithoutRelation>(json!({
+        "body": "Hey @room",
+        "msgtype": "m.text",
+        "org.matrix.msc3952.mentions": { "room": true },
+    }))
+    .unwrap();
+    assert!(content.mentions.unwrap().room);
+}
+
+#[test]
+fn text_with_spoiler() {
+    let content = TextMessageEventContent::plain("Alice dies at the end")
+        .with_spoiler("dies at the end", None);
+    assert_eq!(content.body, "Alice [Spoiler]");
+    let formatted = content.formatted.unwrap();
+    assert_eq!(formatted.body, "Alice <span data-mx-spoiler>dies at the end</span>");
+
+    let content =
+        TextMessageEventContent::html("Bob & Alice marry", "<b>Bob &amp; Alice</b> marry")
+            .with_spoiler("Bob & Alice", Some("ship"));
+    assert_eq!(content.body, "[Spoiler for ship] marry");
+    let formatted = content.formatted.unwrap();
+    assert_eq!(
+        formatted.body,
+        "<b><span data-mx-spoiler=\"ship\">Bob &amp; Alice</span></b> marry"
+    );
+
+    // Tags and attributes are not matched.
+    let content = TextMessageEventContent::html("bold", "<b>bold</b>").with_spoiler("b", Some("b"));
+    assert_eq!(content.body, "[Spoiler for b]old");
+    let formatted = content.formatted.unwrap();
+    assert_eq!(formatted.body, "<b><span data-mx-spoiler=\"b\">b</span>old</b>");
+
+    let content = TextMessageEventContent::html(
+        "Go to the title",
+        "<a href=\"https://title.example.org\" title=\"title > tag\">Go</a> to the title",
+    )
+    .with_spoiler("title", None);
+    assert_eq!(content.body, "Go to the [Spoiler]");
+    let formatted = content.formatted.unwrap();
+    assert_eq!(
+        formatted.body,
+        "<a href=\"https://title.example.org\" title=\"title > tag\">Go</a> to the \
+         <span data-mx-spoiler>title</span>"
+    );
+
+    // Character references are not matched.
+    let content = TextMessageEventContent::html("& amp", "&amp; amp").with_spoiler("amp", None);
+    assert_eq!(content.body, "& [Spoiler]");
+    let formatted = content.formatted.unwrap();
+    assert_eq!(formatted.body, "&amp; <span data-mx-spoiler>amp</span>");
+
+    let content = TextMessageEventContent::plain("Nothing to hide").with_spoiler("secret", None);
+    assert_eq!(content.body, "Nothing to hide");
+    assert_matches!(content.formatted, None);
+}
+
+#[test]
+#[cfg(feature = "html")]
+fn text_with_spoiler_round_trip() {
+    use ruma_html::Html;
+
+    let content = RoomMessageEventContent::new(MessageType::Text(
+        TextMessageEventContent::plain("The butler <did it>")
+            .with_spoiler("<did it>", Some("plot")),
+    ));
+    let json = to_json_value(&content).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "msgtype": "m.text",
+            "body": "The butler [Spoiler for plot]",
+            "format": "org.matrix.custom.html",
+            "formatted_body": "The butler <span data-mx-spoiler=\"plot\">&lt;did it&gt;</span>",
+        })
+    );
+
+    let content = from_json_value::<RoomMessageEventContent>(json).unwrap();
+    assert_matches!(content.msgtype, MessageType::Text(text));
+    let spoilers = Html::parse(&text.formatted.unwrap().body).spoilers();
+    assert_eq!(spoilers.len(), 1);
+    assert_eq!(spoilers[0].reason.as_deref(), Some("plot"));
+    assert_eq!(spoilers[0].content, "&lt;did it&gt;");
+}
+
+#[test]
+fn text_collapsible() {
+    let content = TextMessageEventContent::plain("line 1\nline <2>").collapsible("Build log");
+    assert_eq!(content.body, "Build log\nline 1\nline <2>");
+    let formatted = content.formatted.unwrap();
+    assert_eq!(
+        formatted.body,
+        "<details><summary>Build log</summary>line 1<br>line &lt;2&gt;</details>"
+    );
+
+    let content = TextMessageEventContent::html("Full output", "<code>Full output</code>")
+        .collapsible("Step 1")
+        .collapsible("Job");
+    assert_eq!(content.body, "Job\nStep 1\nFull output");
+    let formatted = content.formatted.unwrap();
+    assert_eq!(
+        formatted.body,
+        "<details><summary>Job</summary>\
+         <details><summary>Step 1</summary><code>Full output</code></details>\
+         </details>"
+    );
+
+    let content = TextMessageEventContent::plain("Hidden").collapsible("");
+    assert_eq!(content.body, "Hidden");
+    let formatted = content.formatted.unwrap();
+    assert_eq!(formatted.body, "<details>Hidden</details>");
+}
+
+#[test]
+fn text_expanded_shortcodes() {
+    let shortcodes = BTreeMap::from([
+        ("smile".to_owned(), "😄".to_owned()),
+        ("+1".to_owned(), "👍".to_owned()),
+        ("lt".to_owned(), "<".to_owned()),
+    ]);
+
+    // Known and unknown shortcodes.
+    let content = TextMessageEventContent::plain("Hi :smile: :unknown::+1: ::smile:")
+        .with_expanded_shortcodes(&shortcodes);
+    assert_eq!(content.body, "Hi 😄 :unknown:👍 :😄");
+    assert_matches!(content.formatted, None);
+
+    // URLs and code.
+    let content = TextMessageEventContent::plain(
+        "See https://example.org/:smile: and `:smile:` or ``a ` :smile:``\n```\n:smile:\n```\n:smile:",
+    )
+    .with_expanded_shortcodes(&shortcodes);
+    assert_eq!(
+        content.body,
+        "See https://example.org/:smile: and `:smile:` or ``a ` :smile:``\n```\n:smile:\n```\n😄"
+    );
+
+    // An unclosed backtick doesn't start a code span.
+    let content = TextMessageEventContent::plain("` :smile:").with_expanded_shortcodes(&shortcodes);
+    assert_eq!(content.body, "` 😄");
+
+    // HTML body.
+    let content = TextMessageEventContent::html(
+        ":smile: `:smile:` :lt:",
+        "<p title=\":smile:\">:smile: <code>:smile:</code> <a href=\"https://example.org/:smile:\">https://example.org/:smile:</a> :lt:</p>",
+    )
+    .with_expanded_shortcodes(&shortcodes);
+    assert_eq!(content.body, "😄 `:smile:` <");
+    assert_eq!(
+        content.formatted.unwrap().body,
+        "<p title=\":smile:\">😄 <code>:smile:</code> <a href=\"https://example.org/:smile:\">https://example.org/:smile:</a> &lt;</p>"
+    );
+
+    // A `>` in a quoted attribute value doesn't end the tag.
+    let content = TextMessageEventContent::html(
+        ":smile:",
+        "<span title=\"a > b :smile:\" data-emoji=':+1: > :smile:'>:smile:</span>",
+    )
+    .with_expanded_shortcodes(&shortcodes);
+    assert_eq!(
+        content.formatted.unwrap().body,
+        "<span title=\"a > b :smile:\" data-emoji=':+1: > :smile:'>😄</span>"
+    );
+}
+
+#[test]
+fn text_quote() {
+    let content = TextMessageEventContent::quote("To be, or not to be");
+    assert_eq!(content.body, "> To be, or not to be");
+    assert_eq!(content.formatted.unwrap().body, "<blockquote>To be, or not to be</blockquote>");
+
+    // Multi-line quote, with an empty line.
+    let content = TextMessageEventContent::quote("line 1\n\nline <3>");
+    assert_eq!(content.body, "> line 1\n>\n> line <3>");
+    assert_eq!(
+        content.formatted.unwrap().body,
+        "<blockquote>line 1<br><br>line &lt;3&gt;</blockquote>"
+    );
+
+    // Nested quotes.
+    let content = TextMessageEventContent::quote("> Hello\n>> Hi\nWhat they said");
+    assert_eq!(content.body, "> > Hello\n> >> Hi\n> What they said");
+    assert_eq!(
+        content.formatted.unwrap().body,
+        "<blockquote><blockquote>Hello<blockquote>Hi</blockquote></blockquote>\
+         What they said</blockquote>"
+    );
+
+    // Quoting a quote.
+    let quoted = TextMessageEventContent::quote("Hi");
+    let content = TextMessageEventContent::quote(quoted.body);
+    assert_eq!(content.body, "> > Hi");
+    assert_eq!(
+        content.formatted.unwrap().body,
+        "<blockquote><blockquote>Hi</blockquote></blockquote>"
+    );
+}
+
+#[test]
+#[cfg(feature = "html")]
+fn text_collapsible_round_trip() {
+    use ruma_html::{Html, SanitizerConfig};
+
+    let content = RoomMessageEventContent::new(MessageType::Text(
+        TextMessageEventContent::plain("Everything went fine")
+            .collapsible("Details")
+            .collapsible("Report"),
+    ));
+    let json = to_json_value(&content).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "msgtype": "m.text",
+            "body": "Report\nDetails\nEverything went fine",
+            "format": "org.matrix.custom.html",
+            "formatted_body": "<details><summary>Report</summary>\
+                               <details><summary>Details</summary>Everything went fine</details>\
+                               </details>",
+        })
+    );
+
+    let content = from_json_value::<RoomMessageEventContent>(json).unwrap();
+    assert_matches!(content.msgtype, MessageType::Text(text));
+    let formatted_body = text.formatted.unwrap().body;
+    let mut html = Html::parse(&formatted_body);
+    html.sanitize_with(SanitizerConfig::strict());
+    assert_eq!(html.to_string(), formatted_body);
+}
+
+#[test]
+fn text_with_autolinks() {
+    let content = TextMessageEventContent::plain(
+        "See https://example.org/docs?a=1&b=2, or (https://en.wikipedia.org/wiki/Rust_(language)).",
+    )
+    .with_autolinks();
+    assert_eq!(
+        content.formatted.unwrap().body,
+        "See <a href=\"https://example.org/docs?a=1&amp;b=2\">https://example.org/docs?a=1&amp;b=2</a>, \
+         or (<a href=\"https://en.wikipedia.org/wiki/Rust_(language)\">\
+         https://en.wikipedia.org/wiki/Rust_(language)</a>)."
+    );
+    assert_eq!(
+        content.body,
+        "See https://example.org/docs?a=1&b=2, or (https://en.wikipedia.org/wiki/Rust_(language))."
+    );
+
+    // Matrix identifiers are linked to their matrix.to URI.
+    let content =
+        TextMessageEventContent::plain("Ask @alice:example.org in #help:example.org!\n<3")
+            .with_autolinks();
+    assert_eq!(
+        content.formatted.unwrap().body,
+        "Ask <a href=\"https://matrix.to/#/@alice:example.org\">@alice:example.org</a> in \
+         <a href=\"https://matrix.to/#/%23help:example.org\">#help:example.org</a>!<br>&lt;3"
+    );
+
+    // Not links.
+    let content = TextMessageEventContent::plain(
+        "email me@home, nothttps://example.org or https:// #hashtag",
+    )
+    .with_autolinks();
+    assert!(content.formatted.is_none());
+
+    // Formatted messages are left alone.
+    let content =
+        TextMessageEventContent::html("https://example.org", "<b>https://example.org</b>")
+            .with_autolinks();
+    assert_eq!(content.formatted.unwrap().body, "<b>https://example.org</b>");
+}
+
+#[test]
+fn text_base_direction() {
+    let english = TextMessageEventContent::plain("Hello, how are you?");
+    assert_eq!(english.base_direction(), Direction::Ltr);
+
+    let arabic = TextMessageEventContent::plain("مرحبا، كيف حالك؟");
+    assert_eq!(arabic.base_direction(), Direction::Rtl);
+
+    // Neutral characters and numbers before the first strong character are ignored.
+    let hebrew = TextMessageEventContent::plain("123, ... \"שלום\" hello");
+    assert_eq!(hebrew.base_direction(), Direction::Rtl);
+    let arabic_numbers = TextMessageEventContent::plain("٣٤ - Hello");
+    assert_eq!(arabic_numbers.base_direction(), Direction::Ltr);
+
+    // Isolated text is skipped, explicit marks are strong.
+    let isolate = TextMessageEventContent::plain("\u{2067}Hello\u{2069} مرحبا");
+    assert_eq!(isolate.base_direction(), Direction::Rtl);
+    let mark = TextMessageEventContent::plain("\u{200F}123 Hello");
+    assert_eq!(mark.base_direction(), Direction::Rtl);
+
+    // Without strong characters, default to left-to-right.
+    assert_eq!(TextMessageEventContent::plain("42 + 42 = 84 🎉").base_direction(), Direction::Ltr);
+    assert_eq!(TextMessageEventContent::plain("").base_direction(), Direction::Ltr);
+}
+
+#[test]
+fn text_mentions_room() {
+    assert!(TextMessageEventContent::plain("@room").mentions_room());
+    assert!(TextMessageEventContent::plain("Hey @room, lunch is ready!").mentions_room());
+    assert!(TextMessageEventContent::plain("Meeting in 5 min (@room)").mentions_room());
+    assert!(TextMessageEventContent::plain("Not @roommate but @room").mentions_room());
+    assert!(TextMessageEventContent::plain("ATTENTION @ROOM").mentions_room());
+
+    assert!(!TextMessageEventContent::plain("Hello everyone").mentions_room());
+    assert!(!TextMessageEventContent::plain("Say hi to my @roommate").mentions_room());
+    assert!(!TextMessageEventContent::plain("Write to alice@room").mentions_room());
+    assert!(!TextMessageEventContent::plain("@room_1 is booked").mentions_room());
+}
+
+#[test]
+fn msgtype_content_into_any_message_like_event_content() {
+    use ruma_events::{
+        room::message::{
+            LocationMessageEventContent, NoticeMessageEventContent,
+            ServerNoticeMessageEventContent, ServerNoticeType,
+        },
+        AnyMessageLikeEventContent, EventContent, MessageLikeEventType,
+    };
+
+    fn send(content: impl Into<AnyMessageLikeEventContent>) -> &'static str {
+        let content = content.into();
+        assert_eq!(content.event_type(), MessageLikeEventType::RoomMessage);
+        assert_matches!(content, AnyMessageLikeEventContent::RoomMessage(content));
+        match content.msgtype {
+            MessageType::Audio(_) => "m.audio",
+            MessageType::Emote(_) => "m.emote",
+            MessageType::File(_) => "m.file",
+            MessageType::Image(_) => "m.image",
+            MessageType::Location(_) => "m.location",
+            MessageType::Notice(_) => "m.notice",
+            MessageType::ServerNotice(_) => "m.server_notice",
+            MessageType::Text(_) => "m.text",
+            MessageType::Video(_) => "m.video",
+            MessageType::VerificationRequest(_) => "m.key.verification.request",
+            _ => "custom",
+        }
+    }
+
+    let url = mxc_uri!("mxc://notareal.hs/file");
+
+    assert_eq!(
+        send(AudioMessageEventContent::plain("audio".to_owned(), url.to_owned())),
+        "m.audio"
+    );
+    assert_eq!(send(EmoteMessageEventContent::plain("emote")), "m.emote");
+    assert_eq!(send(FileMessageEventContent::plain("file".to_owned(), url.to_owned())), "m.file");
+    assert_eq!(
+        send(ImageMessageEventContent::plain("image".to_owned(), url.to_owned())),
+        "m.image"
+    );
+    assert_eq!(
+        send(LocationMessageEventContent::new(
+            "location".to_owned(),
+            "geo:51.5008,0.1247".to_owned()
+        )),
+        "m.location"
+    );
+    assert_eq!(send(NoticeMessageEventContent::plain("notice")), "m.notice");
+    assert_eq!(
+        send(ServerNoticeMessageEventContent::new(
+            "server notice".to_owned(),
+            ServerNoticeType::UsageLimitReached,
+        )),
+        "m.server_notice"
+    );
+    assert_eq!(send(TextMessageEventContent::plain("text")), "m.text");
+    assert_eq!(
+        send(VideoMessageEventContent::plain("video".to_owned(), url.to_owned())),
+        "m.video"
+    );
+    assert_eq!(
+        send(KeyVerificationRequestEventContent::new(
+            "verification request".to_owned(),
+            vec![VerificationMethod::SasV1],
+            OwnedDeviceId::from("ABCDEFG"),
+            owned_user_id!("@alice:example.org"),
+        )),
+        "m.key.verification.request"
+    );
+    assert_eq!(send(RoomMessageEventContent::text_plain("room message")), "m.text");
+
+    let content: RoomMessageEventContent = TextMessageEventContent::plain("text").into();
+    assert_matches!(content.msgtype, MessageType::Text(_));
+}
+
+#[test]
+#[cfg(feature = "html")]
+fn formatted_body_truncate_html() {
+    use ruma_events::room::message::FormattedBody;
+
+    let mut formatted = FormattedBody::html(
+        "<p>Deploy <b>finished &amp; <a href=\"https://ci.example.org/job/1\">logs</a></b></p>\
+         <p>All tests passed</p>",
+    );
+    assert!(formatted.truncate_html(17));
+    assert_eq!(formatted.body, "<p>Deploy <b>finished &amp;</b></p>");
+
+    let mut formatted = FormattedBody::html("<p>Short <em>message</em></p>");
+    assert!(!formatted.truncate_html(100));
+    assert_eq!(formatted.body, "<p>Short <em>message</em></p>");
+
+    #[cfg(feature = "markdown")]
+    {
+        let content = TextMessageEventContent::markdown("Some **bold** and *italic* text");
+        let mut formatted = content.formatted.unwrap();
+        assert!(formatted.truncate_html(7));
+        assert_eq!(formatted.body, "<p>Some <strong>bo</strong></p>");
+    }
+}
+
+#[test]
+fn system_notice() {
+    let server_notice = RoomMessageEventContent::new(MessageType::ServerNotice(
+        ServerNoticeMessageEventContent::new(
+            "Your account is over its limit".to_owned(),
+            ServerNoticeType::UsageLimitReached,
+        ),
+    ));
+    let notice = RoomMessageEventContent::notice_plain("The build succeeded");
+    let text = RoomMessageEventContent::text_plain("Hello");
+
+    let mut server_notice_tags = Tags::new();
+    server_notice_tags.insert(TagName::ServerNotice, TagInfo::new());
+    let mut favourite_tags = Tags::new();
+    favourite_tags.insert(TagName::Favorite, TagInfo::new());
+
+    assert!(is_system_notice(&server_notice, None));
+    assert!(is_system_notice(&server_notice, Some(&favourite_tags)));
+
+    assert!(!is_system_notice(&notice, None));
+    assert!(!is_system_notice(&notice, Some(&favourite_tags)));
+    assert!(is_system_notice(&notice, Some(&server_notice_tags)));
+
+    assert!(!is_system_notice(&text, None));
+    assert!(is_system_notice(&text, Some(&server_notice_tags)));
+}
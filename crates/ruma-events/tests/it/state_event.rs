@@ -1,11 +1,20 @@
 use assert_matches2::assert_matches;
 use js_int::uint;
-use ruma_common::{mxc_uri, room_alias_id, serde::CanBeEmpty, MilliSecondsSinceUnixEpoch};
+use ruma_common::{
+    mxc_uri, owned_user_id, room_alias_id, serde::CanBeEmpty, MilliSecondsSinceUnixEpoch,
+};
 use ruma_events::{
-    room::aliases::RoomAliasesEventContent, AnyStateEvent, AnySyncStateEvent, AnyTimelineEvent,
-    StateEvent, SyncStateEvent,
+    room::{
+        aliases::RoomAliasesEventContent,
+        member::{MembershipState, RoomMemberEvent, RoomMemberEventContent},
+        name::{RoomNameEvent, RoomNameEventContent},
+    },
+    AnyStateEvent, AnySyncStateEvent, AnyTimelineEvent, InitialStateEvent, StateEvent,
+    SyncStateEvent,
+};
+use serde_json::{
+    from_value as from_json_value, json, to_value as to_json_value, Value as JsonValue,
 };
-use serde_json::{from_value as from_json_value, json, Value as JsonValue};
 
 fn aliases_event_with_prev_content() -> JsonValue {
     json!({
@@ -173,3 +182,70 @@ fn deserialize_full_event_convert_to_sync() {
     );
     assert_eq!(sync_ev.sender, "@carl:example.com");
 }
+
+#[test]
+fn deserialize_event_with_wrong_state_key_shape() {
+    let member_event = |state_key: &str| {
+        json!({
+            "content": { "membership": "join" },
+            "event_id": "$h29iv0s8:example.com",
+            "room_id": "!room:localhost",
+            "origin_server_ts": 1,
+            "sender": "@example:localhost",
+            "state_key": state_key,
+            "type": "m.room.member",
+        })
+    };
+    let name_event = |state_key: &str| {
+        json!({
+            "content": { "name": "The Room" },
+            "event_id": "$h29iv0s8:example.com",
+            "room_id": "!room:localhost",
+            "origin_server_ts": 1,
+            "sender": "@example:localhost",
+            "state_key": state_key,
+            "type": "m.room.name",
+        })
+    };
+
+    // `m.room.member` requires a user ID.
+    let ev = from_json_value::<RoomMemberEvent>(member_event("@example:localhost")).unwrap();
+    assert_eq!(ev.state_key(), "@example:localhost");
+    from_json_value::<RoomMemberEvent>(member_event("")).unwrap_err();
+    from_json_value::<RoomMemberEvent>(member_event("example")).unwrap_err();
+
+    // `m.room.name` requires an empty state key.
+    let ev = from_json_value::<RoomNameEvent>(name_event("")).unwrap();
+    assert_eq!(ev.state_key().as_ref(), "");
+    from_json_value::<RoomNameEvent>(name_event("@example:localhost")).unwrap_err();
+
+    // The state key is also checked when deserializing the enum.
+    from_json_value::<AnyStateEvent>(member_event("")).unwrap_err();
+    from_json_value::<AnyStateEvent>(name_event("something")).unwrap_err();
+}
+
+#[test]
+fn serialize_initial_state_events_with_typed_state_keys() {
+    let name = InitialStateEvent::new(RoomNameEventContent::new("The Room".to_owned()));
+    assert_eq!(
+        to_json_value(name).unwrap(),
+        json!({
+            "content": { "name": "The Room" },
+            "state_key": "",
+            "type": "m.room.name",
+        })
+    );
+
+    let member = InitialStateEvent {
+        content: RoomMemberEventContent::new(MembershipState::Invite),
+        state_key: owned_user_id!("@alice:localhost"),
+    };
+    assert_eq!(
+        to_json_value(member).unwrap(),
+        json!({
+            "content": { "membership": "invite" },
+            "state_key": "@alice:localhost",
+            "type": "m.room.member",
+        })
+    );
+}
@@ -4,7 +4,7 @@ use js_int::{uint, UInt};
 use ruma_common::{mxc_uri, serde::CanBeEmpty, MilliSecondsSinceUnixEpoch};
 use ruma_events::{
     room::{ImageInfo, MediaSource, ThumbnailInfo},
-    sticker::StickerEventContent,
+    sticker::{StickerEventContent, StickerMediaSource},
     AnyMessageLikeEvent, MessageLikeEvent,
 };
 use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
@@ -79,7 +79,8 @@ fn content_deserialization() {
 
     let content = from_json_value::<StickerEventContent>(json_data).unwrap();
     assert_eq!(content.body, "Upload: my_image.jpg");
-    assert_eq!(content.url, "mxc://notareal.hs/file");
+    assert_matches!(content.source, StickerMediaSource::Plain(sticker_url));
+    assert_eq!(sticker_url, "mxc://notareal.hs/file");
 }
 
 #[test]
@@ -126,7 +127,8 @@ fn event_deserialization() {
     assert_eq!(content.info.width, Some(uint!(1011)));
     assert_eq!(content.info.mimetype.as_deref(), Some("image/png"));
     assert_eq!(content.info.size, Some(uint!(84242)));
-    assert_eq!(content.url, "mxc://matrix.org/jxPXTKpyydzdHJkdFNZjTZrD");
+    assert_matches!(content.source, StickerMediaSource::Plain(sticker_url));
+    assert_eq!(sticker_url, "mxc://matrix.org/jxPXTKpyydzdHJkdFNZjTZrD");
 
     assert_matches!(content.info.thumbnail_source, Some(MediaSource::Plain(thumbnail_url)));
     assert_eq!(thumbnail_url, "mxc://matrix.org/irnsNRS2879");
@@ -136,3 +138,81 @@ fn event_deserialization() {
     assert_eq!(thumbnail_info.mimetype.as_deref(), Some("image/png"));
     assert_eq!(thumbnail_info.size, Some(uint!(82595)));
 }
+
+#[test]
+fn content_thumbnail_serialization() {
+    let content = StickerEventContent::new(
+        "Hello".to_owned(),
+        assign!(ImageInfo::new(), { width: Some(uint!(256)), height: Some(uint!(256)) }),
+        mxc_uri!("mxc://notareal.hs/sticker").to_owned(),
+    )
+    .thumbnail(
+        MediaSource::Plain(mxc_uri!("mxc://notareal.hs/thumbnail").to_owned()),
+        Box::new(
+            assign!(ThumbnailInfo::new(), { width: Some(uint!(64)), height: Some(uint!(64)) }),
+        ),
+    );
+
+    assert_eq!(
+        to_json_value(&content).unwrap(),
+        json!({
+            "body": "Hello",
+            "info": {
+                "h": 256,
+                "w": 256,
+                "thumbnail_info": {
+                    "h": 64,
+                    "w": 64,
+                },
+                "thumbnail_url": "mxc://notareal.hs/thumbnail",
+            },
+            "url": "mxc://notareal.hs/sticker",
+        })
+    );
+}
+
+#[test]
+fn content_missing_url_deserialization() {
+    let json_data = json!({
+        "body": "Hello",
+        "info": {},
+    });
+
+    from_json_value::<StickerEventContent>(json_data).unwrap_err();
+}
+
+#[cfg(feature = "compat-encrypted-stickers")]
+#[test]
+fn encrypted_content_round_trip() {
+    use ruma_common::serde::Base64;
+    use ruma_events::room::{EncryptedFileInit, JsonWebKeyInit};
+
+    let file = EncryptedFileInit {
+        url: mxc_uri!("mxc://notareal.hs/encrypted").to_owned(),
+        key: JsonWebKeyInit {
+            kty: "oct".to_owned(),
+            key_ops: vec!["encrypt".to_owned(), "decrypt".to_owned()],
+            alg: "A256CTR".to_owned(),
+            k: Base64::new(vec![0; 32]),
+            ext: true,
+        }
+        .into(),
+        iv: Base64::new(vec![0; 16]),
+        hashes: [("sha256".to_owned(), Base64::new(vec![0; 32]))].into(),
+        v: "v2".to_owned(),
+    }
+    .into();
+    let content = StickerEventContent::encrypted("Hello".to_owned(), ImageInfo::new(), file);
+
+    let json_data = to_json_value(&content).unwrap();
+    assert_eq!(json_data["file"]["url"], "mxc://notareal.hs/encrypted");
+    assert!(json_data.get("url").is_none());
+
+    let content = from_json_value::<StickerEventContent>(json_data).unwrap();
+    assert_matches!(content.source, StickerMediaSource::Encrypted(file));
+    assert_eq!(file.url, "mxc://notareal.hs/encrypted");
+    assert_matches!(
+        MediaSource::from(StickerMediaSource::Encrypted(file)),
+        MediaSource::Encrypted(_)
+    );
+}
@@ -0,0 +1,23 @@
+use ruma_common::user_id;
+use ruma_events::{
+    room::{
+        member::{MembershipState, RoomMemberEventContent},
+        name::RoomNameEventContent,
+    },
+    EmptyStateKey, InitialStateEvent,
+};
+
+fn main() {
+    // `m.room.name` requires an empty state key.
+    let _ = InitialStateEvent {
+        content: RoomNameEventContent::new("The Room".to_owned()),
+        state_key: user_id!("@alice:example.org").to_owned(),
+    };
+
+    // `m.room.member` requires a user ID as the state key.
+    let _ = InitialStateEvent {
+        content: RoomMemberEventContent::new(MembershipState::Join),
+        state_key: EmptyStateKey,
+    };
+    let _ = InitialStateEvent::new(RoomMemberEventContent::new(MembershipState::Join));
+}
@@ -52,6 +52,180 @@ impl Html {
         config.clean(self);
     }
 
+    /// Get the [spoilers] in this HTML, in document order.
+    ///
+    /// Nested spoilers are also returned, after the spoiler that contains them.
+    ///
+    /// [spoilers]: https://spec.matrix.org/latest/client-server-api/#spoiler-messages
+    pub fn spoilers(&self) -> Vec<Spoiler> {
+        self.descendants(0)
+            .filter_map(|node_id| {
+                let data = self.nodes[node_id].as_element()?;
+                let reason =
+                    data.attrs.iter().find(|attr| &*attr.name.local == "data-mx-spoiler")?;
+                let reason = (!reason.value.is_empty()).then(|| reason.value.to_string());
+                let content = self.inner_html(node_id);
+
+                Some(Spoiler { reason, content })
+            })
+            .collect()
+    }
+
+    /// Get the [mathematical expressions] in this HTML, in document order.
+    ///
+    /// [mathematical expressions]: https://spec.matrix.org/latest/client-server-api/#mathematical-messages
+    pub fn maths(&self) -> Vec<Maths> {
+        self.descendants(0)
+            .filter_map(|node_id| {
+                let data = self.nodes[node_id].as_element()?;
+                let block = match &*data.name.local {
+                    "span" => false,
+                    "div" => true,
+                    _ => return None,
+                };
+                let latex = data.attrs.iter().find(|attr| &*attr.name.local == "data-mx-maths")?;
+                let latex = latex.value.to_string();
+                let fallback = self.inner_html(node_id);
+
+                Some(Maths { latex, block, fallback })
+            })
+            .collect()
+    }
+
+    /// Get the MXC URIs of the [inline images] in this HTML, in document order.
+    ///
+    /// Only the `src` attributes of `<img>` elements that use the `mxc` scheme are returned, so
+    /// they can be preloaded by clients. Images with other sources are ignored, like the
+    /// sanitizer does.
+    ///
+    /// [inline images]: https://spec.matrix.org/latest/client-server-api/#mroommessage-msgtypes
+    pub fn inline_images(&self) -> Vec<String> {
+        self.descendants(0)
+            .filter_map(|node_id| {
+                let data = self.nodes[node_id].as_element()?;
+                if &*data.name.local != "img" {
+                    return None;
+                }
+
+                let src = data.attrs.iter().find(|attr| &*attr.name.local == "src")?;
+                src.value.starts_with("mxc://").then(|| src.value.to_string())
+            })
+            .collect()
+    }
+
+    /// Get the targets of the links in this HTML, in document order.
+    ///
+    /// Only the `href` attributes of `<a>` elements are returned, with their character references
+    /// decoded.
+    pub fn links(&self) -> Vec<String> {
+        self.descendants(0)
+            .filter_map(|node_id| {
+                let data = self.nodes[node_id].as_element()?;
+                if &*data.name.local != "a" {
+                    return None;
+                }
+
+                let href = data.attrs.iter().find(|attr| &*attr.name.local == "href")?;
+                Some(href.value.to_string())
+            })
+            .collect()
+    }
+
+    /// Truncate this HTML to the given number of visible characters.
+    ///
+    /// Only the characters of text nodes are counted, after decoding HTML entities, so the HTML is
+    /// never cut inside a tag, an attribute or an entity. All the content after the truncation
+    /// point is removed, and the elements that contain it are kept, so they are properly closed
+    /// when this HTML is serialized.
+    ///
+    /// Returns `true` if some content was removed.
+    pub fn truncate(&mut self, max_chars: usize) -> bool {
+        let mut remaining = max_chars;
+        let cut_node_id = self.descendants(0).find(|&node_id| {
+            let NodeData::Text(text) = &self.nodes[node_id].data else {
+                return false;
+            };
+
+            let len = text.chars().count();
+            if len > remaining {
+                true
+            } else {
+                remaining -= len;
+                false
+            }
+        });
+        let Some(cut_node_id) = cut_node_id else {
+            return false;
+        };
+
+        // Remove everything after the node that is cut.
+        let mut node_id = cut_node_id;
+        loop {
+            while let Some(next_sibling) = self.nodes[node_id].next_sibling {
+                self.detach(next_sibling);
+            }
+
+            match self.nodes[node_id].parent {
+                Some(parent) => node_id = parent,
+                None => break,
+            }
+        }
+
+        if remaining == 0 {
+            // Remove the cut node, and its ancestors that would be left empty.
+            let mut node_id = cut_node_id;
+            while let Some(parent_id) = self.nodes[node_id].parent {
+                self.detach(node_id);
+
+                let is_root = self.nodes[parent_id]
+                    .parent
+                    .map_or(true, |grandparent_id| self.nodes[grandparent_id].parent.is_none());
+                if is_root || self.nodes[parent_id].first_child.is_some() {
+                    break;
+                }
+                node_id = parent_id;
+            }
+        } else if let Some(text) = self.nodes[cut_node_id].as_text_mut() {
+            if let Some((idx, _)) = text.char_indices().nth(remaining) {
+                *text = text.subtendril(0, idx as u32);
+            }
+        }
+
+        true
+    }
+
+    /// Iterate over the descendants of the given node, in depth-first order.
+    pub(crate) fn descendants(&self, node_id: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut stack: Vec<usize> = self.nodes[node_id].first_child.into_iter().collect();
+
+        std::iter::from_fn(move || {
+            let current = stack.pop()?;
+            let node = &self.nodes[current];
+
+            if let Some(next_sibling) = node.next_sibling {
+                stack.push(next_sibling);
+            }
+            if let Some(first_child) = node.first_child {
+                stack.push(first_child);
+            }
+
+            Some(current)
+        })
+    }
+
+    /// Serialize the children of the given node.
+    pub(crate) fn inner_html(&self, node_id: usize) -> String {
+        let mut u8_vec = Vec::new();
+        serialize(
+            &mut u8_vec,
+            &ChildrenOf { html: self, node_id },
+            SerializeOpts { traversal_scope: TraversalScope::IncludeNode, ..Default::default() },
+        )
+        .unwrap();
+
+        String::from_utf8(u8_vec).unwrap()
+    }
+
     /// Construct a new `Node` with the given data and add it to this `Html`.
     ///
     /// Returns the index of the new node.
@@ -268,6 +442,28 @@ impl Serialize for Html {
     }
 }
 
+/// The children of a node, to be able to serialize them without their parent.
+struct ChildrenOf<'a> {
+    html: &'a Html,
+    node_id: usize,
+}
+
+impl Serialize for ChildrenOf<'_> {
+    fn serialize<S>(&self, serializer: &mut S, _traversal_scope: TraversalScope) -> io::Result<()>
+    where
+        S: Serializer,
+    {
+        let mut next_child = self.html.nodes[self.node_id].first_child;
+        while let Some(child) = next_child {
+            let child = &self.html.nodes[child];
+            child.serialize(self.html, serializer)?;
+            next_child = child.next_sibling;
+        }
+
+        Ok(())
+    }
+}
+
 impl fmt::Display for Html {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut u8_vec = Vec::new();
@@ -392,6 +588,37 @@ pub struct ElementData {
     pub attrs: BTreeSet<Attribute>,
 }
 
+/// A [spoiler] in an HTML document.
+///
+/// [spoiler]: https://spec.matrix.org/latest/client-server-api/#spoiler-messages
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Spoiler {
+    /// The reason of the spoiler, if any.
+    pub reason: Option<String>,
+
+    /// The serialized HTML content hidden by the spoiler.
+    pub content: String,
+}
+
+/// A [mathematical expression] in an HTML document.
+///
+/// [mathematical expression]: https://spec.matrix.org/latest/client-server-api/#mathematical-messages
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Maths {
+    /// The mathematical expression, in LaTeX format.
+    pub latex: String,
+
+    /// Whether this expression should be displayed as a block, instead of inline.
+    ///
+    /// This is `true` for expressions in a `<div>` and `false` for expressions in a `<span>`.
+    pub block: bool,
+
+    /// The serialized HTML fallback to display for clients that can't render the expression.
+    pub fallback: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::Html;
@@ -35,7 +35,12 @@ pub struct SanitizerConfig {
 }
 
 impl SanitizerConfig {
-    /// Constructs an empty `SanitizerConfig` that will not filter any tag or attribute.
+    /// Constructs an empty `SanitizerConfig` that will not filter any tag or attribute, except
+    /// those that can never be used safely.
+    ///
+    /// That means that elements that can run scripts or embed external content, like `<script>`
+    /// or `<iframe>`, are always removed with their content, as well as event handler attributes,
+    /// like `onclick`, and links using the `javascript`, `vbscript` or `data` schemes.
     pub fn new() -> Self {
         Self::default()
     }
@@ -118,6 +123,7 @@ impl SanitizerConfig {
 
                 if (self.remove_reply_fallback && tag == RICH_REPLY_TAG)
                     || self.max_depth.is_some_and(|max| depth >= max)
+                    || UNSAFE_TAGS.contains(tag)
                 {
                     NodeAction::Remove
                 } else if self
@@ -161,6 +167,12 @@ impl SanitizerConfig {
                 let value = &attr.value;
                 let name: &str = &attr.name.local;
 
+                // Event handler attributes and links to scripts are never allowed.
+                if name.starts_with("on") || (URL_ATTRIBUTES.contains(name) && is_unsafe_url(value))
+                {
+                    return Some(AttributeAction::Remove(attr.to_owned()));
+                }
+
                 if self
                     .allowed_attrs
                     .is_some_and(|m| !m.get(tag).is_some_and(|attrs| attrs.contains(name)))
@@ -222,6 +234,23 @@ impl SanitizerConfig {
     }
 }
 
+/// Whether the given URL uses a scheme that can run scripts.
+///
+/// Browsers ignore ASCII whitespace and control characters in the scheme, and schemes are
+/// case-insensitive, so this normalizes the scheme before checking it.
+fn is_unsafe_url(url: &str) -> bool {
+    let Some((scheme, _)) = url.split_once(':') else {
+        return false;
+    };
+    let scheme: String = scheme
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_ascii_control())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+
+    UNSAFE_SCHEMES.contains(&*scheme)
+}
+
 /// The possible actions to apply to an element node.
 #[derive(Debug, PartialEq, Eq)]
 enum NodeAction {
@@ -253,6 +282,23 @@ static ALLOWED_TAGS_WITHOUT_REPLY_STRICT: Set<&str> = phf_set! {
     "caption", "pre", "span", "img", "details", "summary",
 };
 
+/// List of HTML tags that are always removed with their content, because they can embed or run
+/// external content.
+static UNSAFE_TAGS: Set<&str> = phf_set! {
+    "script", "style", "iframe", "frame", "frameset", "object", "embed", "applet", "noscript",
+    "template", "svg", "math", "base", "link", "meta", "form",
+};
+
+/// List of HTML attributes that contain a URL.
+static URL_ATTRIBUTES: Set<&str> = phf_set! {
+    "href", "src", "action", "formaction", "background", "poster", "xlink:href",
+};
+
+/// List of URI schemes that are never allowed in URL attributes, because they can run scripts.
+static UNSAFE_SCHEMES: Set<&str> = phf_set! {
+    "javascript", "vbscript", "data",
+};
+
 /// The HTML tag name for a rich reply fallback.
 const RICH_REPLY_TAG: &str = "mx-reply";
 
@@ -260,6 +306,7 @@ const RICH_REPLY_TAG: &str = "mx-reply";
 static ALLOWED_ATTRIBUTES_STRICT: Map<&str, &Set<&str>> = phf_map! {
     "font" => &ALLOWED_ATTRIBUTES_FONT_STRICT,
     "span" => &ALLOWED_ATTRIBUTES_SPAN_STRICT,
+    "div" => &ALLOWED_ATTRIBUTES_DIV_STRICT,
     "a" => &ALLOWED_ATTRIBUTES_A_STRICT,
     "img" => &ALLOWED_ATTRIBUTES_IMG_STRICT,
     "ol" => &ALLOWED_ATTRIBUTES_OL_STRICT,
@@ -268,7 +315,8 @@ static ALLOWED_ATTRIBUTES_STRICT: Map<&str, &Set<&str>> = phf_map! {
 static ALLOWED_ATTRIBUTES_FONT_STRICT: Set<&str> =
     phf_set! { "data-mx-bg-color", "data-mx-color", "color" };
 static ALLOWED_ATTRIBUTES_SPAN_STRICT: Set<&str> =
-    phf_set! { "data-mx-bg-color", "data-mx-color", "data-mx-spoiler" };
+    phf_set! { "data-mx-bg-color", "data-mx-color", "data-mx-spoiler", "data-mx-maths" };
+static ALLOWED_ATTRIBUTES_DIV_STRICT: Set<&str> = phf_set! { "data-mx-maths" };
 static ALLOWED_ATTRIBUTES_A_STRICT: Set<&str> = phf_set! { "name", "target", "href" };
 static ALLOWED_ATTRIBUTES_IMG_STRICT: Set<&str> =
     phf_set! { "width", "height", "alt", "title", "src" };
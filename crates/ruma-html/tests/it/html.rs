@@ -1 +1,6 @@
+mod inline_images;
+mod links;
+mod maths;
 mod sanitize;
+mod spoiler;
+mod truncate;
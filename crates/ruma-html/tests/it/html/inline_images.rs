@@ -0,0 +1,49 @@
+use ruma_html::{Html, SanitizerConfig};
+
+#[test]
+fn no_inline_images() {
+    let html = Html::parse("<p>Just <a href=\"https://example.org\">a link</a></p>");
+    assert_eq!(html.inline_images(), Vec::<String>::new());
+}
+
+#[test]
+fn inline_images_mxc_only() {
+    let html = Html::parse(
+        "\
+        <p>Look <img src=\"mxc://notareal.hs/abcdef\" alt=\"first\"> and \
+        <img src=\"https://notareal.hs/ghijkl\"> and \
+        <img src=\"data:image/png;base64,AAAA\"> and \
+        <img alt=\"no source\"></p>\
+        <blockquote><img src=\"mxc://notareal.hs/mnopqr\"></blockquote>\
+        ",
+    );
+
+    assert_eq!(html.inline_images(), ["mxc://notareal.hs/abcdef", "mxc://notareal.hs/mnopqr"]);
+}
+
+#[test]
+fn sanitize_inline_images() {
+    let mut html = Html::parse(
+        "\
+        <p>Look \
+        <img src=\"mxc://notareal.hs/abcdef\" alt=\"kept\">\
+        <img src=\"http://notareal.hs/ghijkl\" alt=\"http\">\
+        <img src=\"data:image/png;base64,AAAA\" alt=\"data\">\
+        <img src=\"mxcfake://notareal.hs/stuvwx\" alt=\"lookalike\">\
+        </p>\
+        ",
+    );
+    html.sanitize_with(SanitizerConfig::compat());
+
+    assert_eq!(html.to_string(), "<p>Look <img alt=\"kept\" src=\"mxc://notareal.hs/abcdef\"></p>");
+    assert_eq!(html.inline_images(), ["mxc://notareal.hs/abcdef"]);
+}
+
+#[test]
+fn sanitize_data_inline_image_without_filter() {
+    let mut html = Html::parse("<img src=\"data:image/png;base64,AAAA\" alt=\"data\">");
+    html.sanitize_with(SanitizerConfig::new());
+
+    assert_eq!(html.to_string(), "<img alt=\"data\">");
+    assert_eq!(html.inline_images(), Vec::<String>::new());
+}
@@ -0,0 +1,23 @@
+use ruma_html::Html;
+
+#[test]
+fn no_links() {
+    let html = Html::parse("<p>Just <img src=\"mxc://notareal.hs/abcdef\"> and text</p>");
+    assert_eq!(html.links(), Vec::<String>::new());
+}
+
+#[test]
+fn links_of_anchors_only() {
+    let html = Html::parse(
+        "\
+        <p><a href=\"https://example.org/?a=1&amp;b=2\">first</a> and \
+        <span data-href=\"https://example.org/span\">not a link</span> and \
+        <link href=\"https://example.org/style.css\"> and \
+        text with href=\"https://example.org/text\" and \
+        <a name=\"anchor\">no target</a></p>\
+        <blockquote><a href='https://example.org/&#x40;second'>second</a></blockquote>\
+        ",
+    );
+
+    assert_eq!(html.links(), ["https://example.org/?a=1&b=2", "https://example.org/@second"]);
+}
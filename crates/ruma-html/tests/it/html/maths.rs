@@ -0,0 +1,51 @@
+use ruma_html::{Html, SanitizerConfig};
+
+#[test]
+fn no_maths() {
+    let html = Html::parse("<p>Nothing to <span data-mx-color=\"#ff0000\">compute</span></p>");
+    assert_eq!(html.maths(), vec![]);
+}
+
+#[test]
+fn inline_and_block_maths() {
+    let html = Html::parse(
+        "\
+        <p>The famous <span data-mx-maths=\"E = mc^2\"><code>E = mc^2</code></span> equation.</p>\
+        <div data-mx-maths=\"\\sum_{i=1}^{n} i = \\frac{n(n+1)}{2}\">\
+            <code>\\sum_{i=1}^{n} i = \\frac{n(n+1)}{2}</code>\
+        </div>\
+        ",
+    );
+    let maths = html.maths();
+
+    assert_eq!(maths.len(), 2);
+    assert_eq!(maths[0].latex, "E = mc^2");
+    assert!(!maths[0].block);
+    assert_eq!(maths[0].fallback, "<code>E = mc^2</code>");
+    assert_eq!(maths[1].latex, "\\sum_{i=1}^{n} i = \\frac{n(n+1)}{2}");
+    assert!(maths[1].block);
+    assert_eq!(maths[1].fallback, "<code>\\sum_{i=1}^{n} i = \\frac{n(n+1)}{2}</code>");
+}
+
+#[test]
+fn maths_on_other_tags() {
+    let html = Html::parse("<p data-mx-maths=\"x\">x</p>");
+    assert_eq!(html.maths(), vec![]);
+}
+
+#[test]
+fn maths_sanitize_round_trip() {
+    let original = "\
+        <span data-mx-maths=\"x < y &amp;&amp; y > z\">x &lt; y &amp;&amp; y &gt; z</span>\
+        <div data-mx-maths=\"\\sqrt{2}\"><code>\\sqrt{2}</code></div>\
+    ";
+    let mut html = Html::parse(original);
+    html.sanitize_with(SanitizerConfig::strict());
+    assert_eq!(html.to_string(), original);
+
+    let maths = Html::parse(&html.to_string()).maths();
+    assert_eq!(maths.len(), 2);
+    assert_eq!(maths[0].latex, "x < y && y > z");
+    assert_eq!(maths[0].fallback, "x &lt; y &amp;&amp; y &gt; z");
+    assert_eq!(maths[1].latex, "\\sqrt{2}");
+}
@@ -246,3 +246,162 @@ fn depth_remove() {
     assert!(res.contains("I should be fine."));
     assert!(!res.contains("I am in too deep!"));
 }
+
+#[test]
+fn tables() {
+    let config = SanitizerConfig::strict();
+    let mut html = Html::parse(
+        "\
+        <table>\
+            <caption>Scores</caption>\
+            <thead><tr><th style=\"text-align: left\">Name</th><th align=\"right\">Score</th></tr></thead>\
+            <tbody><tr><td onclick=\"alert()\">Alice</td><td>42</td></tr></tbody>\
+        </table>\
+        ",
+    );
+    html.sanitize_with(config);
+
+    assert_eq!(
+        html.to_string(),
+        "\
+        <table>\
+            <caption>Scores</caption>\
+            <thead><tr><th>Name</th><th>Score</th></tr></thead>\
+            <tbody><tr><td>Alice</td><td>42</td></tr></tbody>\
+        </table>\
+        "
+    );
+}
+
+#[test]
+fn malformed_tables() {
+    let config = SanitizerConfig::strict();
+    let mut html = Html::parse(
+        "\
+        <td>Stray cell</td>\
+        <table><tr><td>Missing body<td>Unclosed cell</table>\
+        ",
+    );
+    html.sanitize_with(config);
+
+    assert_eq!(
+        html.to_string(),
+        "\
+        Stray cell\
+        <table><tbody><tr><td>Missing body</td><td>Unclosed cell</td></tr></tbody></table>\
+        "
+    );
+}
+
+#[test]
+fn details() {
+    let config = SanitizerConfig::strict();
+    let mut html = Html::parse(
+        "\
+        <details>\
+            <summary>Outer</summary>\
+            <details open>\
+                <summary onclick=\"alert()\">Inner</summary>\
+                <p>Content</p>\
+            </details>\
+        </details>\
+        <details><p>No summary</p></details>\
+        ",
+    );
+    html.sanitize_with(config);
+
+    assert_eq!(
+        html.to_string(),
+        "\
+        <details>\
+            <summary>Outer</summary>\
+            <details>\
+                <summary>Inner</summary>\
+                <p>Content</p>\
+            </details>\
+        </details>\
+        <details><p>No summary</p></details>\
+        "
+    );
+}
+
+#[test]
+fn unsafe_tags_are_removed_with_content() {
+    for config in [SanitizerConfig::new(), SanitizerConfig::strict()] {
+        let mut html = Html::parse(
+            "\
+            <p>Before</p>\
+            <script>alert('script')</script>\
+            <SCRIPT SRC=\"https://evil.example/xss.js\"></SCRIPT>\
+            <iframe src=\"https://evil.example/\"><p>Frame content</p></iframe>\
+            <style>body { display: none; }</style>\
+            <object data=\"https://evil.example/flash.swf\"><embed src=\"https://evil.example/\"></object>\
+            <svg><script>alert('svg')</script></svg>\
+            <noscript><p>No script</p></noscript>\
+            <p>After</p>\
+            ",
+        );
+        html.sanitize_with(config);
+
+        assert_eq!(html.to_string(), "<p>Before</p><p>After</p>");
+    }
+}
+
+#[test]
+fn event_handler_attributes_are_removed() {
+    for config in [SanitizerConfig::new(), SanitizerConfig::strict()] {
+        let mut html = Html::parse(
+            "\
+            <p onclick=\"alert(1)\">Click</p>\
+            <img src=\"mxc://notareal.hs/abcdef\" onerror=\"alert(2)\" OnLoad=\"alert(3)\">\
+            <span data-mx-color=\"#ff0000\" onmouseover=\"alert(4)\">Hover</span>\
+            ",
+        );
+        html.sanitize_with(config);
+
+        assert_eq!(
+            html.to_string(),
+            "\
+            <p>Click</p>\
+            <img src=\"mxc://notareal.hs/abcdef\">\
+            <span data-mx-color=\"#ff0000\">Hover</span>\
+            "
+        );
+    }
+}
+
+#[test]
+fn unsafe_url_schemes_are_removed() {
+    let input = "\
+        <a href=\"javascript:alert(1)\">A</a>\
+        <a href=\"JaVaScRiPt:alert(2)\">B</a>\
+        <a href=\"  javascript:alert(3)\">C</a>\
+        <a href=\"java\tscript:alert(4)\">D</a>\
+        <a href=\"javascript&#58;alert(5)\">E</a>\
+        <a href=\"vbscript:msgbox(6)\">F</a>\
+        <a href=\"data:text/html;base64,PHNjcmlwdD5hbGVydCg3KTwvc2NyaXB0Pg==\">G</a>\
+        <img src=\"javascript:alert(8)\">\
+        <a href=\"https://matrix.org/\">Safe</a>\
+    ";
+
+    let mut html = Html::parse(input);
+    html.sanitize_with(SanitizerConfig::new());
+    assert_eq!(
+        html.to_string(),
+        "\
+        <a>A</a>\
+        <a>B</a>\
+        <a>C</a>\
+        <a>D</a>\
+        <a>E</a>\
+        <a>F</a>\
+        <a>G</a>\
+        <img>\
+        <a href=\"https://matrix.org/\">Safe</a>\
+        "
+    );
+
+    let mut html = Html::parse(input);
+    html.sanitize_with(SanitizerConfig::strict());
+    assert_eq!(html.to_string(), "ABCDEFG<a href=\"https://matrix.org/\">Safe</a>");
+}
@@ -0,0 +1,44 @@
+use ruma_html::Html;
+
+#[test]
+fn no_spoiler() {
+    let html = Html::parse("<p>Nothing to <span data-mx-color=\"#ff0000\">hide</span></p>");
+    assert_eq!(html.spoilers(), vec![]);
+}
+
+#[test]
+fn spoiler_with_and_without_reason() {
+    let html = Html::parse(
+        "\
+        Alice <span data-mx-spoiler>lived happily</span> ever after, \
+        but <span data-mx-spoiler=\"movie\">Bob died</span>.\
+        ",
+    );
+    let spoilers = html.spoilers();
+
+    assert_eq!(spoilers.len(), 2);
+    assert_eq!(spoilers[0].reason, None);
+    assert_eq!(spoilers[0].content, "lived happily");
+    assert_eq!(spoilers[1].reason.as_deref(), Some("movie"));
+    assert_eq!(spoilers[1].content, "Bob died");
+}
+
+#[test]
+fn spoiler_with_nested_formatting() {
+    let html = Html::parse(
+        "\
+        <p><span data-mx-spoiler=\"ending\">It was <em>all</em> a \
+        <span data-mx-spoiler>dream</span></span></p>\
+        ",
+    );
+    let spoilers = html.spoilers();
+
+    assert_eq!(spoilers.len(), 2);
+    assert_eq!(spoilers[0].reason.as_deref(), Some("ending"));
+    assert_eq!(
+        spoilers[0].content,
+        "It was <em>all</em> a <span data-mx-spoiler=\"\">dream</span>"
+    );
+    assert_eq!(spoilers[1].reason, None);
+    assert_eq!(spoilers[1].content, "dream");
+}
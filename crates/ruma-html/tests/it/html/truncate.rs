@@ -0,0 +1,67 @@
+use ruma_html::Html;
+
+#[test]
+fn no_truncation() {
+    let mut html = Html::parse("<p>Short <b>message</b></p>");
+    assert!(!html.truncate(13));
+    assert_eq!(html.to_string(), "<p>Short <b>message</b></p>");
+
+    let mut html = Html::parse("<p>Short <b>message</b></p>");
+    assert!(!html.truncate(100));
+    assert_eq!(html.to_string(), "<p>Short <b>message</b></p>");
+}
+
+#[test]
+fn truncate_closes_open_tags() {
+    let mut html =
+        Html::parse("<p>Hello <b>beautiful <i>world</i></b>!</p><p>Second paragraph</p>");
+    assert!(html.truncate(13));
+    assert_eq!(html.to_string(), "<p>Hello <b>beautif</b></p>");
+
+    let mut html = Html::parse("<ul><li>One</li><li>Two</li><li>Three</li></ul>");
+    assert!(html.truncate(5));
+    assert_eq!(html.to_string(), "<ul><li>One</li><li>Tw</li></ul>");
+}
+
+#[test]
+fn truncate_at_node_boundary() {
+    let mut html = Html::parse("<p>Hello</p><p>world</p>");
+    assert!(html.truncate(5));
+    assert_eq!(html.to_string(), "<p>Hello</p>");
+
+    let mut html = Html::parse("<p>Hello</p><p>world</p>");
+    assert!(html.truncate(0));
+    assert_eq!(html.to_string(), "");
+
+    let mut html = Html::parse("<p>Hello <b>world</b> <i>foo</i></p>");
+    assert!(html.truncate(6));
+    assert_eq!(html.to_string(), "<p>Hello </p>");
+}
+
+#[test]
+fn truncate_never_cuts_attributes() {
+    let mut html = Html::parse(
+        "<a href=\"https://matrix.org/a/very/long/path\">Link</a> and \
+         <span data-mx-color=\"#ff0000\">color</span>",
+    );
+    assert!(html.truncate(2));
+    assert_eq!(html.to_string(), "<a href=\"https://matrix.org/a/very/long/path\">Li</a>");
+}
+
+#[test]
+fn truncate_never_cuts_entities() {
+    let mut html = Html::parse("<p>Tom &amp; Jerry &lt;3</p>");
+    assert!(html.truncate(5));
+    assert_eq!(html.to_string(), "<p>Tom &amp;</p>");
+
+    let mut html = Html::parse("<p>Tom &amp; Jerry &lt;3</p>");
+    assert!(html.truncate(4));
+    assert_eq!(html.to_string(), "<p>Tom </p>");
+}
+
+#[test]
+fn truncate_multibyte_characters() {
+    let mut html = Html::parse("<b>Ça marche 🎉🎉</b>");
+    assert!(html.truncate(11));
+    assert_eq!(html.to_string(), "<b>Ça marche 🎉</b>");
+}
@@ -1,10 +1,17 @@
 //! Details of the `#[ruma_api(...)]` attributes.
 
+use std::collections::BTreeSet;
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
 use syn::{
-    parse::{Parse, ParseStream},
-    Ident, Token, Type,
+    parse::{Lookahead1, Parse, ParseStream},
+    punctuated::Punctuated,
+    Ident, LitInt, LitStr, Path, Token, Type,
 };
 
+use super::version::MatrixVersionLiteral;
+
 mod kw {
     syn::custom_keyword!(body);
     syn::custom_keyword!(raw_body);
@@ -12,8 +19,15 @@ mod kw {
     syn::custom_keyword!(query);
     syn::custom_keyword!(query_map);
     syn::custom_keyword!(header);
+    syn::custom_keyword!(default);
+    syn::custom_keyword!(status);
     syn::custom_keyword!(error);
     syn::custom_keyword!(manual_body_serde);
+    syn::custom_keyword!(derive);
+    syn::custom_keyword!(body_format);
+    syn::custom_keyword!(deprecated);
+    syn::custom_keyword!(removed);
+    syn::custom_keyword!(note);
 }
 
 pub enum RequestMeta {
@@ -22,7 +36,8 @@ pub enum RequestMeta {
     Path,
     Query,
     QueryMap,
-    Header(Ident),
+    Header(HeaderName),
+    Default(Option<LitStr>),
 }
 
 impl Parse for RequestMeta {
@@ -47,6 +62,14 @@ impl Parse for RequestMeta {
             let _: kw::header = input.parse()?;
             let _: Token![=] = input.parse()?;
             input.parse().map(Self::Header)
+        } else if lookahead.peek(kw::default) {
+            let _: kw::default = input.parse()?;
+            if input.peek(Token![=]) {
+                let _: Token![=] = input.parse()?;
+                input.parse().map(|path| Self::Default(Some(path)))
+            } else {
+                Ok(Self::Default(None))
+            }
         } else {
             Err(lookahead.error())
         }
@@ -55,6 +78,9 @@ impl Parse for RequestMeta {
 
 pub enum DeriveRequestMeta {
     Error(Type),
+    Derive(ExtraDerives),
+    BodyFormat(BodyFormat),
+    Deprecation(DeprecationMeta),
 }
 
 impl Parse for DeriveRequestMeta {
@@ -64,6 +90,14 @@ impl Parse for DeriveRequestMeta {
             let _: kw::error = input.parse()?;
             let _: Token![=] = input.parse()?;
             input.parse().map(Self::Error)
+        } else if lookahead.peek(kw::derive) {
+            input.parse().map(Self::Derive)
+        } else if lookahead.peek(kw::body_format) {
+            let _: kw::body_format = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            input.parse().map(Self::BodyFormat)
+        } else if DeprecationMeta::peek(&lookahead) {
+            input.parse().map(Self::Deprecation)
         } else {
             Err(lookahead.error())
         }
@@ -73,7 +107,8 @@ impl Parse for DeriveRequestMeta {
 pub enum ResponseMeta {
     NewtypeBody,
     RawBody,
-    Header(Ident),
+    Header(HeaderName),
+    Status,
 }
 
 impl Parse for ResponseMeta {
@@ -89,6 +124,9 @@ impl Parse for ResponseMeta {
             let _: kw::header = input.parse()?;
             let _: Token![=] = input.parse()?;
             input.parse().map(Self::Header)
+        } else if lookahead.peek(kw::status) {
+            let _: kw::status = input.parse()?;
+            Ok(Self::Status)
         } else {
             Err(lookahead.error())
         }
@@ -98,7 +136,10 @@ impl Parse for ResponseMeta {
 #[allow(clippy::large_enum_variant)]
 pub enum DeriveResponseMeta {
     ManualBodySerde,
-    Error(Type),
+    Error(ResponseErrorType),
+    Derive(ExtraDerives),
+    BodyFormat(BodyFormat),
+    Deprecation(DeprecationMeta),
 }
 
 impl Parse for DeriveResponseMeta {
@@ -111,8 +152,409 @@ impl Parse for DeriveResponseMeta {
             let _: kw::error = input.parse()?;
             let _: Token![=] = input.parse()?;
             input.parse().map(Self::Error)
+        } else if lookahead.peek(kw::derive) {
+            input.parse().map(Self::Derive)
+        } else if lookahead.peek(kw::body_format) {
+            let _: kw::body_format = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            input.parse().map(Self::BodyFormat)
+        } else if DeprecationMeta::peek(&lookahead) {
+            input.parse().map(Self::Deprecation)
         } else {
             Err(lookahead.error())
         }
     }
 }
+
+/// The error type of a response, set with `error = MyError` in the `#[response]` attribute.
+///
+/// It can be followed by a mapping of status codes to variants of the error type, like
+/// `error = MyError { 429 => RateLimited, 404 => NotFound }`.
+pub struct ResponseErrorType {
+    pub ty: Type,
+    pub status_variants: Punctuated<StatusVariant, Token![,]>,
+}
+
+impl ResponseErrorType {
+    /// Produces an expression that converts the error `response` to the error type.
+    ///
+    /// Responses with a mapped status code are converted to the corresponding variant, and the
+    /// others with the `EndpointError` implementation of the error type.
+    pub fn expand_from_http_response(
+        &self,
+        response: TokenStream,
+        ruma_common: &TokenStream,
+    ) -> TokenStream {
+        let ty = &self.ty;
+        let arms = self.status_variants.iter().map(|StatusVariant { status, variant, .. }| {
+            quote! {
+                #status => <#ty>::#variant(
+                    #ruma_common::api::EndpointError::from_http_response(#response),
+                ),
+            }
+        });
+
+        quote! {
+            match #response.status().as_u16() {
+                #( #arms )*
+                _ => <#ty as #ruma_common::api::EndpointError>::from_http_response(#response),
+            }
+        }
+    }
+}
+
+impl Parse for ResponseErrorType {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let ty = input.parse()?;
+        let mut status_variants = Punctuated::new();
+
+        if input.peek(syn::token::Brace) {
+            let content;
+            syn::braced!(content in input);
+            status_variants = Punctuated::parse_terminated(&content)?;
+        }
+
+        let mut statuses = BTreeSet::new();
+        for StatusVariant { status, .. } in &status_variants {
+            if !statuses.insert(status.base10_parse::<u16>()?) {
+                return Err(syn::Error::new_spanned(status, "duplicate status code"));
+            }
+        }
+
+        Ok(Self { ty, status_variants })
+    }
+}
+
+impl ToTokens for ResponseErrorType {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.ty.to_tokens(tokens);
+
+        if !self.status_variants.is_empty() {
+            let status_variants = &self.status_variants;
+            tokens.extend(quote! { { #status_variants } });
+        }
+    }
+}
+
+/// The mapping of an error status code to a variant of the error type, like `429 => RateLimited`.
+pub struct StatusVariant {
+    pub status: LitInt,
+    pub arrow: Token![=>],
+    pub variant: Ident,
+}
+
+impl Parse for StatusVariant {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let status: LitInt = input.parse()?;
+        if !matches!(status.base10_parse::<u16>(), Ok(400..=599)) {
+            return Err(syn::Error::new_spanned(
+                status,
+                "only error status codes, between 400 and 599, can be mapped to a variant",
+            ));
+        }
+
+        Ok(Self { status, arrow: input.parse()?, variant: input.parse()? })
+    }
+}
+
+impl ToTokens for StatusVariant {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.status.to_tokens(tokens);
+        self.arrow.to_tokens(tokens);
+        self.variant.to_tokens(tokens);
+    }
+}
+
+/// Additional derives for the generated struct, set with `derive(...)` in the `#[request]` or
+/// `#[response]` attribute.
+pub struct ExtraDerives {
+    pub keyword: kw::derive,
+    pub paths: Punctuated<Path, Token![,]>,
+}
+
+impl Parse for ExtraDerives {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let keyword = input.parse()?;
+        let content;
+        syn::parenthesized!(content in input);
+
+        let paths = Punctuated::parse_terminated(&content).map_err(|e| {
+            syn::Error::new(e.span(), "expected a comma-separated list of paths to derive macros")
+        })?;
+
+        Ok(Self { keyword, paths })
+    }
+}
+
+/// A part of the deprecation of an endpoint, set in the `#[request]` or `#[response]` attribute.
+pub enum DeprecationMeta {
+    /// `deprecated = X.Y`.
+    Deprecated(kw::deprecated, MatrixVersionLiteral),
+
+    /// `removed = X.Y`.
+    Removed(kw::removed, MatrixVersionLiteral),
+
+    /// `note = "..."`.
+    Note(kw::note, LitStr),
+}
+
+impl DeprecationMeta {
+    fn peek(lookahead: &Lookahead1<'_>) -> bool {
+        lookahead.peek(kw::deprecated) || lookahead.peek(kw::removed) || lookahead.peek(kw::note)
+    }
+
+    /// The keyword of this meta, to be used as the span of errors.
+    pub fn keyword(&self) -> &dyn ToTokens {
+        match self {
+            Self::Deprecated(keyword, _) => keyword,
+            Self::Removed(keyword, _) => keyword,
+            Self::Note(keyword, _) => keyword,
+        }
+    }
+}
+
+impl Parse for DeprecationMeta {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::deprecated) {
+            let keyword = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(Self::Deprecated(keyword, input.parse()?))
+        } else if lookahead.peek(kw::removed) {
+            let keyword = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(Self::Removed(keyword, input.parse()?))
+        } else if lookahead.peek(kw::note) {
+            let keyword = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(Self::Note(keyword, input.parse()?))
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+/// The deprecation of an endpoint, set with `deprecated = X.Y`, `removed = X.Y` and
+/// `note = "..."` in the `#[request]` or `#[response]` attribute.
+///
+/// The versions must match the `deprecated` and `removed` entries of the history of the
+/// `METADATA` in scope.
+#[derive(Default)]
+pub struct Deprecation {
+    deprecated: Option<MatrixVersionLiteral>,
+    removed: Option<(kw::removed, MatrixVersionLiteral)>,
+    note: Option<LitStr>,
+}
+
+impl Deprecation {
+    /// Apply the given part of the deprecation.
+    pub fn set(&mut self, meta: DeprecationMeta) -> syn::Result<()> {
+        let keyword = meta.keyword().to_token_stream();
+        let is_duplicate = match meta {
+            DeprecationMeta::Deprecated(_, version) => self.deprecated.replace(version).is_some(),
+            DeprecationMeta::Removed(keyword, version) => {
+                self.removed.replace((keyword, version)).is_some()
+            }
+            DeprecationMeta::Note(_, note) => self.note.replace(note).is_some(),
+        };
+
+        if is_duplicate {
+            return Err(syn::Error::new_spanned(keyword, "duplicate deprecation attribute"));
+        }
+
+        Ok(())
+    }
+
+    /// Produces the `#[deprecated]` attribute of the type, if the endpoint is deprecated.
+    pub fn expand_attribute(&self) -> syn::Result<TokenStream> {
+        let Some(deprecated) = &self.deprecated else {
+            if let Some((keyword, _)) = &self.removed {
+                return Err(syn::Error::new_spanned(
+                    keyword,
+                    "a removed endpoint must also have a `deprecated` version",
+                ));
+            }
+            if let Some(note) = &self.note {
+                return Err(syn::Error::new_spanned(
+                    note,
+                    "a deprecation note requires a `deprecated` version",
+                ));
+            }
+
+            return Ok(TokenStream::new());
+        };
+
+        let mut message = format!("Deprecated in Matrix {deprecated}");
+        if let Some((_, removed)) = &self.removed {
+            message.push_str(&format!(", removed in Matrix {removed}"));
+        }
+        message.push('.');
+        if let Some(note) = &self.note {
+            message.push(' ');
+            message.push_str(&note.value());
+        }
+
+        Ok(quote! { #[deprecated(note = #message)] })
+    }
+
+    /// Produces the check of the versions against the history of the `METADATA` in scope, if the
+    /// endpoint is deprecated.
+    pub fn expand_check(&self, ruma_common: &TokenStream) -> TokenStream {
+        let Some(deprecated) = &self.deprecated else {
+            return TokenStream::new();
+        };
+
+        let deprecated = deprecated.expand(ruma_common);
+        let removed = match &self.removed {
+            Some((_, removed)) => {
+                let removed = removed.expand(ruma_common);
+                quote! { ::std::option::Option::Some(#removed) }
+            }
+            None => quote! { ::std::option::Option::None },
+        };
+
+        quote! {
+            const _: () = {
+                ::std::assert!(
+                    ::std::matches!(
+                        METADATA.deprecated_in(),
+                        ::std::option::Option::Some(#deprecated)
+                    ),
+                    "the `deprecated` version doesn't match the history of `METADATA`",
+                );
+                ::std::assert!(
+                    ::std::matches!(METADATA.removed_in(), #removed),
+                    "the `removed` version doesn't match the history of `METADATA`",
+                );
+            };
+        }
+    }
+}
+
+/// The format of the body of the request or response, set with `body_format = "..."` in the
+/// `#[request]` or `#[response]` attribute.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum BodyFormat {
+    /// JSON, the default.
+    #[default]
+    Json,
+
+    /// CBOR, which requires the `cbor` feature of `ruma-common`.
+    Cbor,
+}
+
+impl BodyFormat {
+    /// The value of the `Content-Type` header for a body in this format.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Cbor => "application/cbor",
+        }
+    }
+
+    /// Produces an expression that serializes `value` to a buffer in this format.
+    pub fn expand_to_buf(self, value: TokenStream, ruma_common: &TokenStream) -> TokenStream {
+        match self {
+            Self::Json => quote! { #ruma_common::serde::json_to_buf(&#value)? },
+            Self::Cbor => quote! {
+                #ruma_common::__require_cbor_feature! {
+                    #ruma_common::serde::cbor_to_buf(&#value)?
+                }
+            },
+        }
+    }
+
+    /// Produces an expression that deserializes the byte slice `body` in this format.
+    ///
+    /// An empty body is deserialized like an empty object, to allow requests or responses with
+    /// only optional body parameters to be deserialized in that case.
+    pub fn expand_from_slice(self, body: TokenStream, ruma_common: &TokenStream) -> TokenStream {
+        match self {
+            Self::Json => quote! {
+                #ruma_common::exports::serde_json::from_slice(match #body {
+                    [] => b"{}",
+                    b => b,
+                })?
+            },
+            Self::Cbor => quote! {
+                #ruma_common::__require_cbor_feature! {
+                    #ruma_common::serde::cbor_from_slice(#body)?
+                }
+            },
+        }
+    }
+}
+
+impl Parse for BodyFormat {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let lit: LitStr = input.parse()?;
+
+        match lit.value().as_str() {
+            "json" => Ok(Self::Json),
+            "cbor" => Ok(Self::Cbor),
+            _ => Err(syn::Error::new_spanned(
+                lit,
+                "unsupported body format, expected \"json\" or \"cbor\"",
+            )),
+        }
+    }
+}
+
+impl ToTokens for BodyFormat {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let format = match self {
+            Self::Json => "json",
+            Self::Cbor => "cbor",
+        };
+        tokens.extend(quote! { #format });
+    }
+}
+
+/// The name of a header, set with `#[ruma_api(header = ...)]`.
+pub enum HeaderName {
+    /// A constant of type `http::header::HeaderName` in scope, like `CONTENT_TYPE`.
+    Ident(Ident),
+
+    /// The raw name of the header, like `"X-Custom-Name"`.
+    ///
+    /// It is always lowercase.
+    Literal(LitStr),
+}
+
+impl HeaderName {
+    /// The name of the header, to be used in error messages.
+    pub fn name(&self) -> String {
+        match self {
+            Self::Ident(ident) => ident.to_string(),
+            Self::Literal(lit) => lit.value(),
+        }
+    }
+
+    /// Produces an expression of type `http::header::HeaderName`.
+    pub fn expand(&self, http: &TokenStream) -> TokenStream {
+        match self {
+            Self::Ident(ident) => quote! { #ident },
+            Self::Literal(lit) => quote! { #http::header::HeaderName::from_static(#lit) },
+        }
+    }
+}
+
+impl Parse for HeaderName {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        if !input.peek(LitStr) {
+            return input.parse().map(Self::Ident);
+        }
+
+        let lit: LitStr = input.parse()?;
+        let name = lit.value();
+
+        // See the definition of `token` in RFC 9110.
+        let is_valid = !name.is_empty()
+            && name.bytes().all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b));
+        if !is_valid {
+            return Err(syn::Error::new_spanned(lit, "invalid header name"));
+        }
+
+        Ok(Self::Literal(LitStr::new(&name.to_ascii_lowercase(), lit.span())))
+    }
+}
@@ -1,13 +1,14 @@
-use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
 use syn::{
     parse::{Parse, ParseStream},
+    parse_quote,
     punctuated::Punctuated,
-    DeriveInput, Field, Generics, Ident, ItemStruct, Token, Type,
+    DeriveInput, Field, Generics, Ident, ItemStruct, LitStr, Token, Type,
 };
 
 use super::{
-    attribute::{DeriveRequestMeta, RequestMeta},
+    attribute::{BodyFormat, Deprecation, DeriveRequestMeta, HeaderName, RequestMeta},
     ensure_feature_presence,
 };
 use crate::util::{import_ruma_common, PrivateField};
@@ -21,17 +22,45 @@ pub fn expand_request(attr: RequestAttr, item: ItemStruct) -> TokenStream {
 
     let maybe_feature_error = ensure_feature_presence().map(syn::Error::to_compile_error);
 
-    let error_ty = attr.0.first().map_or_else(
-        || quote! { #ruma_common::api::error::MatrixError },
-        |DeriveRequestMeta::Error(ty)| quote! { #ty },
-    );
+    let mut error_ty = None;
+    let mut extra_derives = vec![];
+    let mut body_format = BodyFormat::default();
+    let mut deprecation = Deprecation::default();
+
+    for meta in attr.0 {
+        match meta {
+            DeriveRequestMeta::Error(ty) => error_ty = Some(quote! { #ty }),
+            DeriveRequestMeta::Derive(derives) => extra_derives.extend(derives.paths),
+            DeriveRequestMeta::BodyFormat(format) => body_format = format,
+            DeriveRequestMeta::Deprecation(meta) => {
+                if let Err(e) = deprecation.set(meta) {
+                    return e.into_compile_error();
+                }
+            }
+        }
+    }
+
+    let error_ty = error_ty.unwrap_or_else(|| quote! { #ruma_common::api::error::MatrixError });
+    let deprecated_attr = match deprecation.expand_attribute() {
+        Ok(attr) => attr,
+        Err(e) => return e.into_compile_error(),
+    };
+    let deprecation_check = deprecation.expand_check(&ruma_common);
 
     quote! {
         #maybe_feature_error
-
-        #[derive(Clone, Debug, #ruma_macros::Request, #ruma_common::serde::_FakeDeriveSerde)]
+        #deprecation_check
+
+        #[derive(
+            Clone,
+            Debug,
+            #( #extra_derives, )*
+            #ruma_macros::Request,
+            #ruma_common::serde::_FakeDeriveSerde,
+        )]
         #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
-        #[ruma_api(error = #error_ty)]
+        #[ruma_api(error = #error_ty, body_format = #body_format)]
+        #deprecated_attr
         #item
     }
 }
@@ -50,9 +79,14 @@ pub fn expand_derive_request(input: DeriveInput) -> syn::Result<TokenStream> {
         _ => panic!("This derive macro only works on structs"),
     };
 
-    let fields = fields.into_iter().map(RequestField::try_from).collect::<syn::Result<_>>()?;
+    let ruma_common = import_ruma_common();
+    let fields = fields
+        .into_iter()
+        .map(|field| RequestField::from_field(field, &ruma_common))
+        .collect::<syn::Result<_>>()?;
 
     let mut error_ty = None;
+    let mut body_format = BodyFormat::default();
 
     for attr in input.attrs {
         if !attr.path().is_ident("ruma_api") {
@@ -64,6 +98,19 @@ pub fn expand_derive_request(input: DeriveInput) -> syn::Result<TokenStream> {
         for meta in metas {
             match meta {
                 DeriveRequestMeta::Error(t) => error_ty = Some(t),
+                DeriveRequestMeta::BodyFormat(format) => body_format = format,
+                DeriveRequestMeta::Derive(derives) => {
+                    return Err(syn::Error::new_spanned(
+                        derives.keyword,
+                        "extra derives must be set in the `#[request]` attribute",
+                    ));
+                }
+                DeriveRequestMeta::Deprecation(meta) => {
+                    return Err(syn::Error::new_spanned(
+                        meta.keyword(),
+                        "the deprecation must be set in the `#[request]` attribute",
+                    ));
+                }
             }
         }
     }
@@ -73,9 +120,9 @@ pub fn expand_derive_request(input: DeriveInput) -> syn::Result<TokenStream> {
         generics: input.generics,
         fields,
         error_ty: error_ty.expect("missing error_ty attribute"),
+        body_format,
     };
 
-    let ruma_common = import_ruma_common();
     let test = request.check(&ruma_common)?;
     let types_impls = request.expand_all(&ruma_common);
 
@@ -96,6 +143,7 @@ struct Request {
     fields: Vec<RequestField>,
 
     error_ty: Type,
+    body_format: BodyFormat,
 }
 
 impl Request {
@@ -125,7 +173,7 @@ impl Request {
         self.fields.iter().any(|f| matches!(&f.kind, RequestFieldKind::Query))
     }
 
-    fn header_fields(&self) -> impl Iterator<Item = (&Field, &Ident)> {
+    fn header_fields(&self) -> impl Iterator<Item = (&Field, &HeaderName)> {
         self.fields.iter().filter_map(RequestField::as_header_field)
     }
 
@@ -133,6 +181,16 @@ impl Request {
         self.fields.iter().filter_map(RequestField::as_path_field)
     }
 
+    /// The path fields that are not optional.
+    fn required_path_fields(&self) -> impl Iterator<Item = &Field> {
+        self.path_fields().filter(|f| !is_option_type(&f.ty))
+    }
+
+    /// The path fields that are optional, they match the path arguments with a trailing `?`.
+    fn optional_path_fields(&self) -> impl Iterator<Item = &Field> {
+        self.path_fields().filter(|f| is_option_type(&f.ty))
+    }
+
     fn raw_body_field(&self) -> Option<&Field> {
         self.fields.iter().find_map(RequestField::as_raw_body_field)
     }
@@ -161,14 +219,16 @@ impl Request {
             }
         });
 
-        let request_query_def = if let Some(f) = self.query_map_field() {
-            let field = Field { ident: None, colon_token: None, ..f.clone() };
-            let field = PrivateField(&field);
-            Some(quote! { (#field); })
-        } else if self.has_query_fields() {
+        // When regular query fields are present, the query map field is (de)serialized separately
+        // from them, so it is not part of `RequestQuery`.
+        let request_query_def = if self.has_query_fields() {
             let fields =
                 self.fields.iter().filter_map(RequestField::as_query_field).map(PrivateField);
             Some(quote! { { #(#fields),* } })
+        } else if let Some(f) = self.query_map_field() {
+            let field = Field { ident: None, colon_token: None, ..f.clone() };
+            let field = PrivateField(&field);
+            Some(quote! { (#field); })
         } else {
             None
         };
@@ -184,12 +244,16 @@ impl Request {
             }
         });
 
+        let default_impl = self.expand_default();
+        let is_default_fns = self.fields.iter().filter_map(RequestField::expand_is_default_fn);
         let outgoing_request_impl = self.expand_outgoing(ruma_common);
         let incoming_request_impl = self.expand_incoming(ruma_common);
 
         quote! {
             #request_body_struct
             #request_query_struct
+            #default_impl
+            #( #is_default_fns )*
 
             #[allow(deprecated)]
             mod __request_impls {
@@ -200,6 +264,43 @@ impl Request {
         }
     }
 
+    /// Implement `Default` for the request if all its fields have a default value.
+    fn expand_default(&self) -> Option<TokenStream> {
+        if self.fields.is_empty() || self.fields.iter().any(|f| f.default.is_none()) {
+            return None;
+        }
+
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let fields = self.fields.iter().map(|field| {
+            let field_name =
+                field.inner.ident.as_ref().expect("expected field to have an identifier");
+            let cfg_attrs =
+                field.inner.attrs.iter().filter(|a| a.path().is_ident("cfg")).collect::<Vec<_>>();
+            let value = match field.default.as_ref().unwrap() {
+                RequestFieldDefault::Trait => quote! { ::std::default::Default::default() },
+                RequestFieldDefault::Function(path) => match path.parse::<syn::ExprPath>() {
+                    Ok(path) => quote! { #path() },
+                    Err(e) => e.into_compile_error(),
+                },
+            };
+
+            quote! {
+                #( #cfg_attrs )*
+                #field_name: #value,
+            }
+        });
+
+        Some(quote! {
+            #[automatically_derived]
+            impl #impl_generics ::std::default::Default for #ident #ty_generics #where_clause {
+                fn default() -> Self {
+                    Self { #( #fields )* }
+                }
+            }
+        })
+    }
+
     pub(super) fn check(&self, ruma_common: &TokenStream) -> syn::Result<TokenStream> {
         let http = quote! { #ruma_common::exports::http };
 
@@ -220,22 +321,13 @@ impl Request {
             }
         };
 
-        let query_map_fields =
-            self.fields.iter().filter(|f| matches!(&f.kind, RequestFieldKind::QueryMap));
-        let has_query_map_field = match query_map_fields.count() {
-            0 => false,
-            1 => true,
-            _ => {
-                return Err(syn::Error::new_spanned(
-                    &self.ident,
-                    "Can't have more than one query_map field",
-                ))
-            }
-        };
+        let mut query_map_fields = self.fields.iter().filter_map(RequestField::as_query_map_field);
+        let query_map_field = query_map_fields.next();
+        if let Some(field) = query_map_fields.next() {
+            return Err(syn::Error::new_spanned(field, "Can't have more than one query_map field"));
+        }
 
         let has_body_fields = self.fields.iter().any(|f| matches!(&f.kind, RequestFieldKind::Body));
-        let has_query_fields =
-            self.fields.iter().any(|f| matches!(&f.kind, RequestFieldKind::Query));
 
         if has_newtype_body_field && has_body_fields {
             return Err(syn::Error::new_spanned(
@@ -244,14 +336,32 @@ impl Request {
             ));
         }
 
-        if has_query_map_field && has_query_fields {
+        if let Some(field) = query_map_field.filter(|_| self.has_newtype_body()) {
             return Err(syn::Error::new_spanned(
-                &self.ident,
-                "Can't have both a query map field and regular query fields",
+                field,
+                "Can't have both a query_map field and a newtype body field",
+            ));
+        }
+
+        if let Some(field) = self
+            .path_fields()
+            .skip_while(|f| !is_option_type(&f.ty))
+            .find(|f| !is_option_type(&f.ty))
+        {
+            return Err(syn::Error::new_spanned(
+                field,
+                "Can't have a non-optional path field after an optional path field",
             ));
         }
 
-        let path_fields = self.path_fields().map(|f| f.ident.as_ref().unwrap().to_string());
+        let path_fields = self.path_fields().map(|f| {
+            let name = f.ident.as_ref().unwrap().to_string();
+            if is_option_type(&f.ty) {
+                format!("{name}?")
+            } else {
+                name
+            }
+        });
         let mut tests = quote! {
             #[::std::prelude::v1::test]
             #[allow(deprecated)]
@@ -281,10 +391,45 @@ impl Request {
     }
 }
 
+/// Whether the given field has a `#[serde]` attribute containing the given meta item.
+fn has_serde_meta(field: &Field, name: &str) -> bool {
+    field.attrs.iter().filter(|attr| attr.path().is_ident("serde")).any(|attr| match &attr.meta {
+        syn::Meta::List(list) => list
+            .tokens
+            .clone()
+            .into_iter()
+            .any(|token| matches!(token, TokenTree::Ident(ident) if ident == name)),
+        _ => false,
+    })
+}
+
+/// Whether the given type is an `Option`.
+fn is_option_type(ty: &Type) -> bool {
+    matches!(
+        ty,
+        Type::Path(syn::TypePath { path: syn::Path { segments, .. }, .. })
+            if segments.last().is_some_and(|s| s.ident == "Option")
+    )
+}
+
 /// A field of the request struct.
 pub(super) struct RequestField {
     pub(super) inner: Field,
     pub(super) kind: RequestFieldKind,
+    pub(super) default: Option<RequestFieldDefault>,
+
+    /// The name of the generated function that checks whether the value of the field is equal to
+    /// the value returned by its default function, to skip its serialization.
+    is_default_fn: Option<Ident>,
+}
+
+/// The default value of a request field, set with `#[ruma_api(default)]`.
+pub(super) enum RequestFieldDefault {
+    /// Use the `Default` implementation of the field type.
+    Trait,
+
+    /// Call the function at the given path.
+    Function(LitStr),
 }
 
 /// The kind of a request field.
@@ -293,7 +438,7 @@ pub(super) enum RequestFieldKind {
     Body,
 
     /// Data in an HTTP header.
-    Header(Ident),
+    Header(HeaderName),
 
     /// A specific data type in the body of the request.
     NewtypeBody,
@@ -313,7 +458,12 @@ pub(super) enum RequestFieldKind {
 
 impl RequestField {
     /// Creates a new `RequestField`.
-    fn new(inner: Field, kind_attr: Option<RequestMeta>) -> Self {
+    fn new(
+        mut inner: Field,
+        kind_attr: Option<RequestMeta>,
+        default: Option<RequestFieldDefault>,
+        ruma_common: &TokenStream,
+    ) -> syn::Result<Self> {
         let kind = match kind_attr {
             Some(RequestMeta::NewtypeBody) => RequestFieldKind::NewtypeBody,
             Some(RequestMeta::RawBody) => RequestFieldKind::RawBody,
@@ -321,10 +471,121 @@ impl RequestField {
             Some(RequestMeta::Query) => RequestFieldKind::Query,
             Some(RequestMeta::QueryMap) => RequestFieldKind::QueryMap,
             Some(RequestMeta::Header(header)) => RequestFieldKind::Header(header),
+            Some(RequestMeta::Default(_)) => unreachable!("default is not a field kind"),
             None => RequestFieldKind::Body,
         };
 
-        Self { inner, kind }
+        let mut is_default_fn = None;
+
+        if let Some(default) = &default {
+            if !matches!(kind, RequestFieldKind::Body | RequestFieldKind::Query) {
+                return Err(syn::Error::new_spanned(
+                    &inner,
+                    "`default` is only supported on body and query fields",
+                ));
+            }
+
+            // Don't send values that are equal to the default, unless there is already a custom
+            // condition.
+            let skip_default = !has_serde_meta(&inner, "skip_serializing_if");
+
+            // The attributes of the field are only used for the `RequestBody` and
+            // `RequestQuery` structs, so the default can be handled by serde.
+            let is_default: Option<syn::Path> = match default {
+                RequestFieldDefault::Trait => {
+                    inner.attrs.push(parse_quote! { #[serde(default)] });
+                    skip_default.then(|| parse_quote! { #ruma_common::serde::is_default })
+                }
+                RequestFieldDefault::Function(path) => {
+                    inner.attrs.push(parse_quote! { #[serde(default = #path)] });
+                    skip_default.then(|| {
+                        let field_name =
+                            inner.ident.as_ref().expect("expected field to have an identifier");
+                        let ident = format_ident!("__request_is_default_{field_name}");
+                        is_default_fn = Some(ident.clone());
+                        parse_quote! { #ident }
+                    })
+                }
+            };
+
+            if let Some(is_default) = is_default {
+                // serde parses the string as a path, so the spacing of the tokens doesn't matter.
+                let is_default =
+                    LitStr::new(&is_default.to_token_stream().to_string(), Span::call_site());
+                inner.attrs.push(parse_quote! { #[serde(skip_serializing_if = #is_default)] });
+            }
+        }
+
+        Ok(Self { inner, kind, default, is_default_fn })
+    }
+
+    /// Creates a new `RequestField` from a field of the request struct and its attributes.
+    fn from_field(mut field: Field, ruma_common: &TokenStream) -> syn::Result<Self> {
+        let (api_attrs, attrs) =
+            field.attrs.into_iter().partition::<Vec<_>, _>(|attr| attr.path().is_ident("ruma_api"));
+        field.attrs = attrs;
+
+        let mut kind_attr = None;
+        let mut default = None;
+
+        for attr in api_attrs {
+            let metas =
+                attr.parse_args_with(Punctuated::<RequestMeta, Token![,]>::parse_terminated)?;
+
+            for meta in metas {
+                match meta {
+                    RequestMeta::Default(path) => {
+                        if default.is_some() {
+                            return Err(syn::Error::new_spanned(
+                                &attr,
+                                "multiple default attributes found, there can only be one",
+                            ));
+                        }
+
+                        default = Some(match path {
+                            Some(path) => RequestFieldDefault::Function(path),
+                            None => RequestFieldDefault::Trait,
+                        });
+                    }
+                    meta => {
+                        if kind_attr.is_some() {
+                            return Err(syn::Error::new_spanned(
+                                &attr,
+                                "multiple field kind attribute found, there can only be one",
+                            ));
+                        }
+
+                        kind_attr = Some(meta);
+                    }
+                }
+            }
+        }
+
+        RequestField::new(field, kind_attr, default, ruma_common)
+    }
+
+    /// Produces the function that checks whether the value of this field is equal to the value
+    /// returned by its default function, if it is needed.
+    fn expand_is_default_fn(&self) -> Option<TokenStream> {
+        let ident = self.is_default_fn.as_ref()?;
+        let Some(RequestFieldDefault::Function(path)) = &self.default else {
+            return None;
+        };
+
+        let ty = &self.inner.ty;
+        let cfg_attrs = self.inner.attrs.iter().filter(|a| a.path().is_ident("cfg"));
+        let default_value = match path.parse::<syn::ExprPath>() {
+            Ok(path) => quote! { #path() },
+            Err(e) => e.into_compile_error(),
+        };
+
+        Some(quote! {
+            #( #cfg_attrs )*
+            #[cfg(feature = "client")]
+            fn #ident(value: &#ty) -> bool {
+                *value == #default_value
+            }
+        })
     }
 
     /// Return the contained field if this request field is a body kind.
@@ -368,7 +629,7 @@ impl RequestField {
     }
 
     /// Return the contained field and header ident if this request field is a header kind.
-    pub fn as_header_field(&self) -> Option<(&Field, &Ident)> {
+    pub fn as_header_field(&self) -> Option<(&Field, &HeaderName)> {
         match &self.kind {
             RequestFieldKind::Header(header_name) => Some((&self.inner, header_name)),
             _ => None,
@@ -376,32 +637,9 @@ impl RequestField {
     }
 }
 
-impl TryFrom<Field> for RequestField {
-    type Error = syn::Error;
-
-    fn try_from(mut field: Field) -> syn::Result<Self> {
-        let (mut api_attrs, attrs) =
-            field.attrs.into_iter().partition::<Vec<_>, _>(|attr| attr.path().is_ident("ruma_api"));
-        field.attrs = attrs;
-
-        let kind_attr = match api_attrs.as_slice() {
-            [] => None,
-            [_] => Some(api_attrs.pop().unwrap().parse_args::<RequestMeta>()?),
-            _ => {
-                return Err(syn::Error::new_spanned(
-                    &api_attrs[1],
-                    "multiple field kind attribute found, there can only be one",
-                ));
-            }
-        };
-
-        Ok(RequestField::new(field, kind_attr))
-    }
-}
-
 impl Parse for RequestField {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
-        input.call(Field::parse_named)?.try_into()
+        RequestField::from_field(input.call(Field::parse_named)?, &import_ruma_common())
     }
 }
 
@@ -1,6 +1,6 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{TokenStream, TokenTree};
 use quote::quote;
-use syn::Field;
+use syn::{ext::IdentExt, Expr, Field, LitStr, Token};
 
 use super::{Request, RequestField};
 
@@ -9,13 +9,44 @@ impl Request {
         let http = quote! { #ruma_common::exports::http };
         let serde = quote! { #ruma_common::exports::serde };
         let serde_html_form = quote! { #ruma_common::exports::serde_html_form };
-        let serde_json = quote! { #ruma_common::exports::serde_json };
 
         let error_ty = &self.error_ty;
 
         // FIXME: the rest of the field initializer expansions are gated `cfg(...)` except this one.
         // If we get errors about missing fields in Request for a path field look here.
-        let (parse_request_path, path_vars) = if self.has_path_fields() {
+        let (parse_request_path, path_vars) = if self.optional_path_fields().next().is_some() {
+            let required_path_vars: Vec<_> =
+                self.required_path_fields().filter_map(|f| f.ident.as_ref()).collect();
+            let optional_path_vars: Vec<_> =
+                self.optional_path_fields().filter_map(|f| f.ident.as_ref()).collect();
+            let required_count = required_path_vars.len();
+
+            // The optional path arguments are the ones after the required ones, if they are
+            // present.
+            let parse_request_path = quote! {
+                let (#(#required_path_vars,)*) = #serde::Deserialize::deserialize(
+                    #serde::de::value::SeqDeserializer::<_, #serde::de::value::Error>::new(
+                        path_args.iter().take(#required_count).map(::std::convert::AsRef::as_ref)
+                    )
+                )?;
+
+                let mut optional_path_args = path_args.iter().skip(#required_count);
+                #(
+                    let #optional_path_vars = optional_path_args
+                        .next()
+                        .map(|arg| {
+                            #serde::Deserialize::deserialize(
+                                #serde::de::value::StrDeserializer::<
+                                    #serde::de::value::Error,
+                                >::new(arg.as_ref())
+                            )
+                        })
+                        .transpose()?;
+                )*
+            };
+
+            (parse_request_path, quote! { #(#required_path_vars,)* #(#optional_path_vars,)* })
+        } else if self.has_path_fields() {
             let path_vars: Vec<_> = self.path_fields().filter_map(|f| f.ident.as_ref()).collect();
 
             let parse_request_path = quote! {
@@ -31,7 +62,60 @@ impl Request {
             (TokenStream::new(), TokenStream::new())
         };
 
-        let (parse_query, query_vars) = if let Some(field) = self.query_map_field() {
+        let (parse_query, query_vars) = if self.has_query_fields() {
+            let (decls, names) = vars(
+                self.fields.iter().filter_map(RequestField::as_query_field),
+                quote! { request_query },
+            );
+
+            let mut parse = quote! {
+                let request_query: RequestQuery =
+                    #serde_html_form::from_str(&request.uri().query().unwrap_or(""))?;
+
+                #decls
+            };
+            let mut names = names;
+
+            if let Some(field) = self.query_map_field() {
+                let cfg_attrs =
+                    field.attrs.iter().filter(|a| a.path().is_ident("cfg")).collect::<Vec<_>>();
+                let field_name =
+                    field.ident.as_ref().expect("expected field to have an identifier");
+                let known_keys = match self
+                    .fields
+                    .iter()
+                    .filter_map(RequestField::as_query_field)
+                    .map(query_field_keys)
+                    .collect::<syn::Result<Vec<_>>>()
+                {
+                    Ok(keys) => keys.concat(),
+                    Err(e) => return e.into_compile_error(),
+                };
+
+                // Every key that was not consumed by a regular query field ends up in the map.
+                parse.extend(quote! {
+                    #( #cfg_attrs )*
+                    let #field_name = {
+                        let known_keys: &[&::std::primitive::str] = &[#(#known_keys),*];
+                        let pairs: ::std::vec::Vec<(
+                            ::std::string::String,
+                            ::std::string::String,
+                        )> = #serde_html_form::from_str(&request.uri().query().unwrap_or(""))?;
+
+                        pairs
+                            .into_iter()
+                            .filter(|(key, _)| !known_keys.contains(&key.as_str()))
+                            .collect()
+                    };
+                });
+                names.extend(quote! {
+                    #( #cfg_attrs )*
+                    #field_name,
+                });
+            }
+
+            (parse, names)
+        } else if let Some(field) = self.query_map_field() {
             let cfg_attrs =
                 field.attrs.iter().filter(|a| a.path().is_ident("cfg")).collect::<Vec<_>>();
             let field_name = field.ident.as_ref().expect("expected field to have an identifier");
@@ -48,20 +132,6 @@ impl Request {
                     #field_name,
                 },
             )
-        } else if self.has_query_fields() {
-            let (decls, names) = vars(
-                self.fields.iter().filter_map(RequestField::as_query_field),
-                quote! { request_query },
-            );
-
-            let parse = quote! {
-                let request_query: RequestQuery =
-                    #serde_html_form::from_str(&request.uri().query().unwrap_or(""))?;
-
-                #decls
-            };
-
-            (parse, names)
         } else {
             (TokenStream::new(), TokenStream::new())
         };
@@ -74,7 +144,8 @@ impl Request {
                         field.attrs.iter().filter(|a| a.path().is_ident("cfg")).collect::<Vec<_>>();
 
                     let field_name = &field.ident;
-                    let header_name_string = header_name.to_string();
+                    let header_name_string = header_name.name();
+                    let header_name = header_name.expand(&http);
 
                     let (some_case, none_case) = match &field.ty {
                         syn::Type::Path(syn::TypePath {
@@ -127,19 +198,15 @@ impl Request {
         };
 
         let extract_body = self.has_body_fields().then(|| {
+            let deserialize_body = self.body_format.expand_from_slice(quote! { body }, ruma_common);
+
             quote! {
                 let request_body: RequestBody = {
                     let body = ::std::convert::AsRef::<[::std::primitive::u8]>::as_ref(
                         request.body(),
                     );
 
-                    #serde_json::from_slice(match body {
-                        // If the request body is completely empty, pretend it is an empty JSON
-                        // object instead. This allows requests with only optional body parameters
-                        // to be deserialized in that case.
-                        [] => b"{}",
-                        b => b,
-                    })?
+                    #deserialize_body
                 };
             }
         });
@@ -228,3 +295,44 @@ fn vars<'a>(
         })
         .unzip()
 }
+
+/// Get the query string keys a query field is deserialized from, taking `#[serde(rename)]` and
+/// `#[serde(alias)]` into account.
+fn query_field_keys(field: &Field) -> syn::Result<Vec<String>> {
+    let field_name = field.ident.as_ref().expect("expected field to have an identifier");
+    let mut name = field_name.unraw().to_string();
+    let mut aliases = Vec::new();
+
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                if meta.input.peek(syn::token::Paren) {
+                    meta.parse_nested_meta(|meta| {
+                        let value: LitStr = meta.value()?.parse()?;
+                        if meta.path.is_ident("deserialize") {
+                            name = value.value();
+                        }
+                        Ok(())
+                    })
+                } else {
+                    name = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                }
+            } else if meta.path.is_ident("alias") {
+                aliases.push(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else if meta.input.peek(Token![=]) {
+                meta.value()?.parse::<Expr>()?;
+                Ok(())
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.input.parse::<TokenTree>()?;
+                Ok(())
+            } else {
+                Ok(())
+            }
+        })?;
+    }
+
+    aliases.insert(0, name);
+    Ok(aliases)
+}
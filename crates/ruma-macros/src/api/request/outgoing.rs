@@ -2,7 +2,7 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::Field;
 
-use super::{Request, RequestField};
+use super::{BodyFormat, Request, RequestField};
 
 impl Request {
     pub fn expand_outgoing(&self, ruma_common: &TokenStream) -> TokenStream {
@@ -12,13 +12,44 @@ impl Request {
 
         let error_ty = &self.error_ty;
 
-        let path_fields =
-            self.path_fields().map(|f| f.ident.as_ref().expect("path fields have a name"));
+        let path_args = if self.optional_path_fields().next().is_some() {
+            let required_path_fields = self
+                .required_path_fields()
+                .map(|f| f.ident.as_ref().expect("path fields have a name"));
+            let optional_path_fields = self
+                .optional_path_fields()
+                .map(|f| f.ident.as_ref().expect("path fields have a name"));
 
-        let request_query_string = if let Some(field) = self.query_map_field() {
+            // Optional path arguments are only added until the first one that is `None`, the
+            // following segments are omitted from the URL.
+            quote! {
+                &{
+                    let mut path_args: ::std::vec::Vec<&dyn ::std::fmt::Display> =
+                        ::std::vec![ #( &self.#required_path_fields ),* ];
+                    path_args.extend(
+                        [
+                            #(
+                                self.#optional_path_fields
+                                    .as_ref()
+                                    .map(|arg| arg as &dyn ::std::fmt::Display),
+                            )*
+                        ]
+                        .into_iter()
+                        .map_while(|arg| arg),
+                    );
+                    path_args
+                }
+            }
+        } else {
+            let path_fields =
+                self.path_fields().map(|f| f.ident.as_ref().expect("path fields have a name"));
+            quote! { &[ #( &self.#path_fields ),* ] }
+        };
+
+        let request_query_map = self.query_map_field().map(|field| {
             let field_name = field.ident.as_ref().expect("expected field to have identifier");
 
-            quote! {{
+            quote! {
                 // This function exists so that the compiler will throw an error when the type of
                 // the field with the query_map attribute doesn't implement
                 // `IntoIterator<Item = (String, String)>`.
@@ -36,21 +67,52 @@ impl Request {
                     >,
                 {}
 
-                let request_query = RequestQuery(self.#field_name);
-                assert_trait_impl(&request_query.0);
+                assert_trait_impl(&self.#field_name);
+            }
+        });
 
-                &#serde_html_form::to_string(request_query)?
-            }}
-        } else if self.has_query_fields() {
+        let request_query_string = if self.has_query_fields() {
             let request_query_init_fields = struct_init_fields(
                 self.fields.iter().filter_map(RequestField::as_query_field),
                 quote! { self },
             );
 
+            let append_query_map = self.query_map_field().map(|field| {
+                let field_name = field.ident.as_ref().expect("expected field to have identifier");
+
+                quote! {
+                    #request_query_map
+
+                    let query_map_string = #serde_html_form::to_string(self.#field_name)?;
+                    if !query_map_string.is_empty() {
+                        if !query_string.is_empty() {
+                            query_string.push('&');
+                        }
+                        query_string.push_str(&query_map_string);
+                    }
+                }
+            });
+
+            quote! {
+                &{
+                    let request_query = RequestQuery {
+                        #request_query_init_fields
+                    };
+
+                    #[allow(unused_mut)]
+                    let mut query_string = #serde_html_form::to_string(request_query)?;
+                    #append_query_map
+
+                    query_string
+                }
+            }
+        } else if let Some(field) = self.query_map_field() {
+            let field_name = field.ident.as_ref().expect("expected field to have identifier");
+
             quote! {{
-                let request_query = RequestQuery {
-                    #request_query_init_fields
-                };
+                #request_query_map
+
+                let request_query = RequestQuery(self.#field_name);
 
                 &#serde_html_form::to_string(request_query)?
             }}
@@ -63,10 +125,16 @@ impl Request {
         // policies that don't allow the `Content-Type` header (for things such as `.well-known`
         // that are commonly handled by something else than a homeserver).
         let mut header_kvs = if self.raw_body_field().is_some() || self.has_body_fields() {
+            let content_type = if self.raw_body_field().is_some() {
+                BodyFormat::Json.content_type()
+            } else {
+                self.body_format.content_type()
+            };
+
             quote! {
                 req_headers.insert(
                     #http::header::CONTENT_TYPE,
-                    #http::header::HeaderValue::from_static("application/json"),
+                    #http::header::HeaderValue::from_static(#content_type),
                 );
             }
         } else {
@@ -75,6 +143,7 @@ impl Request {
 
         header_kvs.extend(self.header_fields().map(|(field, header_name)| {
             let field_name = &field.ident;
+            let header_name = header_name.expand(&http);
 
             match &field.ty {
                 syn::Type::Path(syn::TypePath { path: syn::Path { segments, .. }, .. })
@@ -108,9 +177,7 @@ impl Request {
         } else if self.has_body_fields() {
             let initializers = struct_init_fields(self.body_fields(), quote! { self });
 
-            quote! {
-                #ruma_common::serde::json_to_buf(&RequestBody { #initializers })?
-            }
+            self.body_format.expand_to_buf(quote! { RequestBody { #initializers } }, ruma_common)
         } else {
             quote! { METADATA.empty_request_body::<T>() }
         };
@@ -137,7 +204,7 @@ impl Request {
                         .uri(METADATA.make_endpoint_url(
                             considering_versions,
                             base_url,
-                            &[ #( &self.#path_fields ),* ],
+                            #path_args,
                             #request_query_string,
                         )?);
 
@@ -10,7 +10,9 @@ use syn::{
 };
 
 use super::{
-    attribute::{DeriveResponseMeta, ResponseMeta},
+    attribute::{
+        BodyFormat, Deprecation, DeriveResponseMeta, HeaderName, ResponseErrorType, ResponseMeta,
+    },
     ensure_feature_presence,
 };
 use crate::util::{import_ruma_common, PrivateField};
@@ -24,21 +26,46 @@ pub fn expand_response(attr: ResponseAttr, item: ItemStruct) -> TokenStream {
 
     let maybe_feature_error = ensure_feature_presence().map(syn::Error::to_compile_error);
 
-    let error_ty = attr
-        .0
-        .iter()
-        .find_map(|a| match a {
-            DeriveResponseMeta::Error(ty) => Some(quote! { #ty }),
-            _ => None,
-        })
-        .unwrap_or_else(|| quote! { #ruma_common::api::error::MatrixError });
+    let mut error_ty = None;
+    let mut extra_derives = vec![];
+    let mut body_format = BodyFormat::default();
+    let mut deprecation = Deprecation::default();
+
+    for meta in attr.0 {
+        match meta {
+            DeriveResponseMeta::Error(ty) => error_ty = Some(quote! { #ty }),
+            DeriveResponseMeta::Derive(derives) => extra_derives.extend(derives.paths),
+            DeriveResponseMeta::BodyFormat(format) => body_format = format,
+            DeriveResponseMeta::Deprecation(meta) => {
+                if let Err(e) = deprecation.set(meta) {
+                    return e.into_compile_error();
+                }
+            }
+            DeriveResponseMeta::ManualBodySerde => {}
+        }
+    }
+
+    let error_ty = error_ty.unwrap_or_else(|| quote! { #ruma_common::api::error::MatrixError });
+    let deprecated_attr = match deprecation.expand_attribute() {
+        Ok(attr) => attr,
+        Err(e) => return e.into_compile_error(),
+    };
+    let deprecation_check = deprecation.expand_check(&ruma_common);
 
     quote! {
         #maybe_feature_error
-
-        #[derive(Clone, Debug, #ruma_macros::Response, #ruma_common::serde::_FakeDeriveSerde)]
+        #deprecation_check
+
+        #[derive(
+            Clone,
+            Debug,
+            #( #extra_derives, )*
+            #ruma_macros::Response,
+            #ruma_common::serde::_FakeDeriveSerde,
+        )]
         #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
-        #[ruma_api(error = #error_ty)]
+        #[ruma_api(error = #error_ty, body_format = #body_format)]
+        #deprecated_attr
         #item
     }
 }
@@ -60,6 +87,7 @@ pub fn expand_derive_response(input: DeriveInput) -> syn::Result<TokenStream> {
     let fields = fields.into_iter().map(ResponseField::try_from).collect::<syn::Result<_>>()?;
     let mut manual_body_serde = false;
     let mut error_ty = None;
+    let mut body_format = BodyFormat::default();
     for attr in input.attrs {
         if !attr.path().is_ident("ruma_api") {
             continue;
@@ -71,6 +99,19 @@ pub fn expand_derive_response(input: DeriveInput) -> syn::Result<TokenStream> {
             match meta {
                 DeriveResponseMeta::ManualBodySerde => manual_body_serde = true,
                 DeriveResponseMeta::Error(t) => error_ty = Some(t),
+                DeriveResponseMeta::BodyFormat(format) => body_format = format,
+                DeriveResponseMeta::Derive(derives) => {
+                    return Err(syn::Error::new_spanned(
+                        derives.keyword,
+                        "extra derives must be set in the `#[response]` attribute",
+                    ));
+                }
+                DeriveResponseMeta::Deprecation(meta) => {
+                    return Err(syn::Error::new_spanned(
+                        meta.keyword(),
+                        "the deprecation must be set in the `#[response]` attribute",
+                    ));
+                }
             }
         }
     }
@@ -81,6 +122,7 @@ pub fn expand_derive_response(input: DeriveInput) -> syn::Result<TokenStream> {
         fields,
         manual_body_serde,
         error_ty: error_ty.unwrap(),
+        body_format,
     };
 
     response.check()?;
@@ -92,7 +134,8 @@ struct Response {
     generics: Generics,
     fields: Vec<ResponseField>,
     manual_body_serde: bool,
-    error_ty: Type,
+    error_ty: ResponseErrorType,
+    body_format: BodyFormat,
 }
 
 impl Response {
@@ -179,6 +222,13 @@ impl Response {
             }
         };
 
+        if self.fields.iter().filter(|f| matches!(&f.kind, ResponseFieldKind::Status)).count() > 1 {
+            return Err(syn::Error::new_spanned(
+                &self.ident,
+                "Can't have more than one status field",
+            ));
+        }
+
         let has_body_fields =
             self.fields.iter().any(|f| matches!(&f.kind, ResponseFieldKind::Body));
         if has_newtype_body_field && has_body_fields {
@@ -204,13 +254,16 @@ enum ResponseFieldKind {
     Body,
 
     /// Data in an HTTP header.
-    Header(Ident),
+    Header(HeaderName),
 
     /// A specific data type in the body of the response.
     NewtypeBody,
 
     /// Arbitrary bytes in the body of the response.
     RawBody,
+
+    /// The HTTP status code of the response.
+    Status,
 }
 
 impl ResponseField {
@@ -220,6 +273,7 @@ impl ResponseField {
             Some(ResponseMeta::NewtypeBody) => ResponseFieldKind::NewtypeBody,
             Some(ResponseMeta::RawBody) => ResponseFieldKind::RawBody,
             Some(ResponseMeta::Header(header)) => ResponseFieldKind::Header(header),
+            Some(ResponseMeta::Status) => ResponseFieldKind::Status,
             None => ResponseFieldKind::Body,
         };
 
@@ -242,8 +296,16 @@ impl ResponseField {
         }
     }
 
+    /// Return the contained field if this response field is a status kind.
+    fn as_status_field(&self) -> Option<&Field> {
+        match &self.kind {
+            ResponseFieldKind::Status => Some(&self.inner),
+            _ => None,
+        }
+    }
+
     /// Return the contained field and HTTP header ident if this response field is a header kind.
-    fn as_header_field(&self) -> Option<(&Field, &Ident)> {
+    fn as_header_field(&self) -> Option<(&Field, &HeaderName)> {
         match &self.kind {
             ResponseFieldKind::Header(ident) => Some((&self.inner, ident)),
             _ => None,
@@ -1,13 +1,18 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::Type;
 
-use super::{Response, ResponseFieldKind};
+use super::{Response, ResponseErrorType, ResponseFieldKind};
 
 impl Response {
-    pub fn expand_incoming(&self, error_ty: &Type, ruma_common: &TokenStream) -> TokenStream {
+    pub fn expand_incoming(
+        &self,
+        error_ty: &ResponseErrorType,
+        ruma_common: &TokenStream,
+    ) -> TokenStream {
         let http = quote! { #ruma_common::exports::http };
-        let serde_json = quote! { #ruma_common::exports::serde_json };
+        let from_http_response =
+            error_ty.expand_from_http_response(quote! { response }, ruma_common);
+        let error_ty = &error_ty.ty;
 
         let extract_response_headers = self.has_header_fields().then(|| {
             quote! {
@@ -16,19 +21,15 @@ impl Response {
         });
 
         let typed_response_body_decl = self.has_body_fields().then(|| {
+            let deserialize_body = self.body_format.expand_from_slice(quote! { body }, ruma_common);
+
             quote! {
                 let response_body: ResponseBody = {
                     let body = ::std::convert::AsRef::<[::std::primitive::u8]>::as_ref(
                         response.body(),
                     );
 
-                    #serde_json::from_slice(match body {
-                        // If the response body is completely empty, pretend it is an empty
-                        // JSON object instead. This allows responses with only optional body
-                        // parameters to be deserialized in that case.
-                        [] => b"{}",
-                        b => b,
-                    })?
+                    #deserialize_body
                 };
             }
         });
@@ -52,6 +53,9 @@ impl Response {
                         }
                     }
                     ResponseFieldKind::Header(header_name) => {
+                        let header_name_string = header_name.name();
+                        let header_name = header_name.expand(&http);
+
                         let optional_header = match &field.ty {
                             syn::Type::Path(syn::TypePath {
                                 path: syn::Path { segments, .. },
@@ -70,7 +74,11 @@ impl Response {
                                 #( #cfg_attrs )*
                                 #field_name: {
                                     headers.remove(#header_name)
-                                        .expect("response missing expected header")
+                                        .ok_or_else(|| {
+                                            #ruma_common::api::error::HeaderDeserializationError::MissingHeader(
+                                                #header_name_string.into()
+                                            )
+                                        })?
                                         .to_str()?
                                         .to_owned()
                                 }
@@ -78,6 +86,10 @@ impl Response {
                         };
                         quote! { #optional_header }
                     }
+                    ResponseFieldKind::Status => quote! {
+                        #( #cfg_attrs )*
+                        #field_name: response.status()
+                    },
                     // This field must be instantiated last to avoid `use of move value` error.
                     // We are guaranteed only one new body field because of a check in
                     // `parse_response`.
@@ -125,9 +137,7 @@ impl Response {
                         })
                     } else {
                         Err(#ruma_common::api::error::FromHttpResponseError::Server(
-                            <#error_ty as #ruma_common::api::EndpointError>::from_http_response(
-                                response,
-                            )
+                            #from_http_response
                         ))
                     }
                 }
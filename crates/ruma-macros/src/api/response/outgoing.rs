@@ -1,7 +1,7 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 
-use super::{Response, ResponseField};
+use super::{BodyFormat, Response, ResponseField};
 
 impl Response {
     pub fn expand_outgoing(&self, ruma_common: &TokenStream) -> TokenStream {
@@ -12,6 +12,7 @@ impl Response {
             response_field.as_header_field().map(|(field, header_name)| {
                 let field_name =
                     field.ident.as_ref().expect("expected field to have an identifier");
+                let header_name = header_name.expand(&http);
 
                 match &field.ty {
                     syn::Type::Path(syn::TypePath { path: syn::Path { segments, .. }, .. })
@@ -36,9 +37,21 @@ impl Response {
             })
         });
 
-        let body = if let Some(field) =
-            self.fields.iter().find_map(ResponseField::as_raw_body_field)
-        {
+        let set_status = self.fields.iter().find_map(ResponseField::as_status_field).map(|field| {
+            let field_name = field.ident.as_ref().expect("expected field to have an identifier");
+            quote! {
+                resp_builder = resp_builder.status(self.#field_name);
+            }
+        });
+
+        let raw_body_field = self.fields.iter().find_map(ResponseField::as_raw_body_field);
+        let content_type = if raw_body_field.is_some() {
+            BodyFormat::Json.content_type()
+        } else {
+            self.body_format.content_type()
+        };
+
+        let body = if let Some(field) = raw_body_field {
             let field_name = field.ident.as_ref().expect("expected field to have an identifier");
             quote! { #ruma_common::serde::slice_to_buf(&self.#field_name) }
         } else {
@@ -55,9 +68,7 @@ impl Response {
                 })
             });
 
-            quote! {
-                #ruma_common::serde::json_to_buf(&ResponseBody { #(#fields)* })?
-            }
+            self.body_format.expand_to_buf(quote! { ResponseBody { #(#fields)* } }, ruma_common)
         };
 
         quote! {
@@ -68,7 +79,8 @@ impl Response {
                     self,
                 ) -> ::std::result::Result<#http::Response<T>, #ruma_common::api::error::IntoHttpError> {
                     let mut resp_builder = #http::Response::builder()
-                        .header(#http::header::CONTENT_TYPE, "application/json");
+                        .header(#http::header::CONTENT_TYPE, #content_type);
+                    #set_status
 
                     if let Some(mut headers) = resp_builder.headers_mut() {
                         #(#serialize_response_headers)*
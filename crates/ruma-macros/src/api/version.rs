@@ -1,4 +1,4 @@
-use std::num::NonZeroU8;
+use std::{fmt, num::NonZeroU8};
 
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, ToTokens};
@@ -15,6 +15,18 @@ const ONE: NonZeroU8 = unsafe { NonZeroU8::new_unchecked(1) };
 impl MatrixVersionLiteral {
     pub const V1_0: Self = Self { major: ONE, minor: 0 };
     pub const V1_1: Self = Self { major: ONE, minor: 1 };
+
+    /// Produces the path to the `MatrixVersion` variant for this version.
+    pub fn expand(&self, ruma_common: &TokenStream) -> TokenStream {
+        let variant = format_ident!("V{}_{}", u8::from(self.major), self.minor);
+        quote! { #ruma_common::api::MatrixVersion::#variant }
+    }
+}
+
+impl fmt::Display for MatrixVersionLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
 }
 
 impl Parse for MatrixVersionLiteral {
@@ -454,5 +454,74 @@ pub mod v1 {
 
             assert_eq!(expected, to_json_value(notice).unwrap());
         }
+
+        #[test]
+        fn spec_example_round_trip() {
+            let json = json!({
+                "content": {
+                    "body": "I'm floating in a most peculiar way.",
+                    "msgtype": "m.text"
+                },
+                "counts": {
+                    "missed_calls": 1,
+                    "unread": 2
+                },
+                "devices": [
+                    {
+                        "app_id": "org.matrix.matrixConsole.ios",
+                        "data": {},
+                        "pushkey": "V2h5IG9uIGVhcnRoIGRpZCB5b3UgZGVjb2RlIHRoaXM/",
+                        "pushkey_ts": 12_345_678,
+                        "tweaks": {
+                            "sound": "bing"
+                        }
+                    }
+                ],
+                "event_id": "$3957tyerfgewrf384",
+                "prio": "high",
+                "room_alias": "#exampleroom:matrix.org",
+                "room_id": "!slw48wfj34rtnrf:example.com",
+                "room_name": "Mission Control",
+                "sender": "@exampleuser:matrix.org",
+                "sender_display_name": "Major Tom",
+                "type": "m.room.message"
+            });
+
+            let notification = from_json_value::<Notification>(json.clone()).unwrap();
+            assert_eq!(notification.event_id.as_deref().unwrap(), "$3957tyerfgewrf384");
+            assert_eq!(notification.room_id.as_deref().unwrap(), "!slw48wfj34rtnrf:example.com");
+            assert_eq!(notification.event_type, Some(TimelineEventType::RoomMessage));
+            assert_eq!(notification.sender.as_deref().unwrap(), "@exampleuser:matrix.org");
+            assert_eq!(notification.sender_display_name.as_deref(), Some("Major Tom"));
+            assert_eq!(notification.room_name.as_deref(), Some("Mission Control"));
+            assert_eq!(notification.room_alias.as_deref().unwrap(), "#exampleroom:matrix.org");
+            assert!(!notification.user_is_target);
+            assert_eq!(notification.prio, NotificationPriority::High);
+            assert_eq!(
+                from_json_value::<JsonValue>(to_json_value(&notification.content).unwrap())
+                    .unwrap(),
+                json["content"]
+            );
+            assert_eq!(notification.counts.unread, uint!(2));
+            assert_eq!(notification.counts.missed_calls, uint!(1));
+
+            assert_eq!(notification.devices.len(), 1);
+            let device = &notification.devices[0];
+            assert_eq!(device.app_id, "org.matrix.matrixConsole.ios");
+            assert_eq!(device.pushkey, "V2h5IG9uIGVhcnRoIGRpZCB5b3UgZGVjb2RlIHRoaXM/");
+            assert_eq!(device.pushkey_ts, Some(SecondsSinceUnixEpoch(uint!(12_345_678))));
+            assert!(device.data.is_empty());
+            // A missing highlight tweak means that the value of highlight is false.
+            assert_eq!(device.tweaks.len(), 2);
+            assert!(matches!(&device.tweaks[0], Tweak::Sound(sound) if sound == "bing"));
+            assert!(matches!(device.tweaks[1], Tweak::Highlight(false)));
+
+            // Default values are not serialized.
+            let mut expected = json;
+            expected.as_object_mut().unwrap().remove("prio");
+            expected["devices"][0].as_object_mut().unwrap().remove("data");
+            expected["devices"][0]["tweaks"]["highlight"] = false.into();
+            assert_eq!(to_json_value(notification).unwrap(), expected);
+        }
     }
 }